@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_user_mappings_chat_id;
+DROP TABLE IF EXISTS user_mappings;
@@ -0,0 +1,12 @@
+CREATE TABLE user_mappings (
+    id SERIAL PRIMARY KEY,
+    chat_id INTEGER NOT NULL REFERENCES chats(id) ON DELETE CASCADE,
+    provider VARCHAR(20) NOT NULL,
+    provider_username VARCHAR(255) NOT NULL,
+    telegram_username VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (chat_id, provider, provider_username)
+);
+
+CREATE INDEX idx_user_mappings_chat_id ON user_mappings(chat_id);
@@ -0,0 +1,11 @@
+CREATE TABLE user_links (
+    id SERIAL PRIMARY KEY,
+    provider VARCHAR(20) NOT NULL,
+    provider_username VARCHAR(255) NOT NULL,
+    telegram_user_id BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (provider, provider_username)
+);
+
+CREATE INDEX idx_user_links_telegram_user_id ON user_links(telegram_user_id);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS health_check_rollups;
+DROP TABLE IF EXISTS health_checks;
@@ -0,0 +1,25 @@
+CREATE TABLE health_checks (
+    id BIGSERIAL PRIMARY KEY,
+    health_url_id INTEGER NOT NULL REFERENCES health_urls(id) ON DELETE CASCADE,
+    checked_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    success BOOLEAN NOT NULL,
+    status_code INTEGER NOT NULL,
+    duration_ms INTEGER NOT NULL,
+    failure_reason VARCHAR(20),
+    error_detail TEXT
+);
+
+CREATE INDEX idx_health_checks_url_checked_at ON health_checks(health_url_id, checked_at);
+CREATE INDEX idx_health_checks_checked_at ON health_checks(checked_at);
+
+CREATE TABLE health_check_rollups (
+    id SERIAL PRIMARY KEY,
+    health_url_id INTEGER NOT NULL REFERENCES health_urls(id) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    total_checks INTEGER NOT NULL,
+    successful_checks INTEGER NOT NULL,
+    p50_duration_ms INTEGER,
+    p95_duration_ms INTEGER,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (health_url_id, day)
+);
@@ -0,0 +1,7 @@
+ALTER TABLE health_urls
+    DROP COLUMN check_interval_secs,
+    DROP COLUMN timeout_secs,
+    DROP COLUMN max_retries,
+    DROP COLUMN confirmation_threshold,
+    DROP COLUMN consecutive_failures,
+    DROP COLUMN is_down;
@@ -0,0 +1,9 @@
+ALTER TABLE health_urls
+    ADD COLUMN check_interval_secs INTEGER NOT NULL DEFAULT 60,
+    ADD COLUMN timeout_secs INTEGER NOT NULL DEFAULT 10,
+    ADD COLUMN max_retries INTEGER NOT NULL DEFAULT 2,
+    ADD COLUMN confirmation_threshold INTEGER NOT NULL DEFAULT 1,
+    ADD COLUMN consecutive_failures INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN is_down BOOLEAN NOT NULL DEFAULT FALSE;
+
+UPDATE health_urls SET is_down = status_code NOT BETWEEN 200 AND 299;
@@ -0,0 +1,10 @@
+CREATE TABLE health_url_assertions (
+    id SERIAL PRIMARY KEY,
+    health_url_id INTEGER NOT NULL REFERENCES health_urls(id) ON DELETE CASCADE,
+    kind VARCHAR(20) NOT NULL,
+    target TEXT NOT NULL,
+    expected TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_health_url_assertions_health_url_id ON health_url_assertions(health_url_id);
@@ -0,0 +1,6 @@
+ALTER TABLE health_urls
+    DROP COLUMN http_method,
+    DROP COLUMN request_headers,
+    DROP COLUMN request_body,
+    DROP COLUMN basic_auth,
+    DROP COLUMN expected_status;
@@ -0,0 +1,6 @@
+ALTER TABLE health_urls
+    ADD COLUMN http_method VARCHAR(10) NOT NULL DEFAULT 'GET',
+    ADD COLUMN request_headers JSONB NOT NULL DEFAULT '{}',
+    ADD COLUMN request_body TEXT,
+    ADD COLUMN basic_auth TEXT,
+    ADD COLUMN expected_status VARCHAR(100);
@@ -0,0 +1,5 @@
+ALTER TABLE health_urls
+    DROP COLUMN tls_expires_at,
+    DROP COLUMN tls_issuer,
+    DROP COLUMN tls_warning_days,
+    DROP COLUMN tls_last_warning_days;
@@ -0,0 +1,5 @@
+ALTER TABLE health_urls
+    ADD COLUMN tls_expires_at TIMESTAMPTZ,
+    ADD COLUMN tls_issuer TEXT,
+    ADD COLUMN tls_warning_days VARCHAR(50) NOT NULL DEFAULT '30,14,7,1',
+    ADD COLUMN tls_last_warning_days INTEGER;
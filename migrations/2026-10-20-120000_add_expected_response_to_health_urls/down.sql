@@ -0,0 +1 @@
+ALTER TABLE health_urls DROP COLUMN expected_response;
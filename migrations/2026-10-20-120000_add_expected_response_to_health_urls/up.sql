@@ -0,0 +1 @@
+ALTER TABLE health_urls ADD COLUMN expected_response TEXT;
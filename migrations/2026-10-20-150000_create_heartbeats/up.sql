@@ -0,0 +1,15 @@
+CREATE TABLE heartbeats (
+    id SERIAL PRIMARY KEY,
+    chat_id INTEGER NOT NULL REFERENCES chats(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    token VARCHAR(64) NOT NULL UNIQUE,
+    period_secs INTEGER NOT NULL,
+    grace_secs INTEGER NOT NULL,
+    last_ping_at TIMESTAMPTZ,
+    last_start_at TIMESTAMPTZ,
+    last_duration_ms BIGINT,
+    is_down BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (chat_id, name)
+);
@@ -0,0 +1,7 @@
+ALTER TABLE health_urls
+    DROP COLUMN recovery_threshold,
+    DROP COLUMN consecutive_successes,
+    DROP COLUMN is_flapping,
+    DROP COLUMN flap_window_started_at,
+    DROP COLUMN flap_transitions,
+    DROP COLUMN last_transition_at;
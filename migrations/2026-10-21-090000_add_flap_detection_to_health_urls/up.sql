@@ -0,0 +1,7 @@
+ALTER TABLE health_urls
+    ADD COLUMN recovery_threshold INTEGER NOT NULL DEFAULT 1,
+    ADD COLUMN consecutive_successes INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN is_flapping BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN flap_window_started_at TIMESTAMPTZ,
+    ADD COLUMN flap_transitions INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN last_transition_at TIMESTAMPTZ;
@@ -0,0 +1,6 @@
+DROP TABLE maintenance_windows;
+
+ALTER TABLE health_urls
+    DROP COLUMN paused_at,
+    DROP COLUMN paused_until,
+    DROP COLUMN maintenance_started_at;
@@ -0,0 +1,16 @@
+ALTER TABLE health_urls
+    ADD COLUMN paused_at TIMESTAMPTZ,
+    ADD COLUMN paused_until TIMESTAMPTZ,
+    ADD COLUMN maintenance_started_at TIMESTAMPTZ;
+
+CREATE TABLE maintenance_windows (
+    id SERIAL PRIMARY KEY,
+    health_url_id INTEGER NOT NULL REFERENCES health_urls(id) ON DELETE CASCADE,
+    days VARCHAR(30) NOT NULL,
+    start_time TIME NOT NULL,
+    duration_mins INTEGER NOT NULL,
+    timezone VARCHAR(64) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_maintenance_windows_health_url_id ON maintenance_windows(health_url_id);
@@ -0,0 +1,4 @@
+DROP TABLE status_pages;
+
+ALTER TABLE health_urls
+    DROP COLUMN show_on_status_page;
@@ -0,0 +1,11 @@
+ALTER TABLE health_urls
+    ADD COLUMN show_on_status_page BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE status_pages (
+    id SERIAL PRIMARY KEY,
+    chat_id INTEGER NOT NULL UNIQUE REFERENCES chats(id) ON DELETE CASCADE,
+    slug VARCHAR(64) NOT NULL UNIQUE,
+    title VARCHAR(100) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,12 @@
+CREATE TABLE incidents (
+    id SERIAL PRIMARY KEY,
+    health_url_id INTEGER NOT NULL REFERENCES health_urls(id) ON DELETE CASCADE,
+    started_at TIMESTAMPTZ NOT NULL,
+    resolved_at TIMESTAMPTZ,
+    cause TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_incidents_health_url_id_started_at ON incidents(health_url_id, started_at);
+-- A monitor has at most one open incident
+CREATE UNIQUE INDEX idx_incidents_open ON incidents(health_url_id) WHERE resolved_at IS NULL;
@@ -0,0 +1,2 @@
+ALTER TABLE health_urls
+    DROP COLUMN label;
@@ -0,0 +1,5 @@
+ALTER TABLE health_urls
+    DROP COLUMN latency_threshold,
+    DROP COLUMN is_degraded,
+    DROP COLUMN latency_streak,
+    DROP COLUMN degraded_since;
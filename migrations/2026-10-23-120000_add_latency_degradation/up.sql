@@ -0,0 +1,5 @@
+ALTER TABLE health_urls
+    ADD COLUMN latency_threshold VARCHAR(20) NOT NULL DEFAULT '',
+    ADD COLUMN is_degraded BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN latency_streak INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN degraded_since TIMESTAMPTZ;
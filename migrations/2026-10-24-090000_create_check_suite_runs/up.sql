@@ -0,0 +1,10 @@
+CREATE TABLE check_suite_runs (
+    webhook_id INTEGER NOT NULL REFERENCES webhooks(id) ON DELETE CASCADE,
+    suite_id BIGINT NOT NULL,
+    name TEXT NOT NULL,
+    conclusion VARCHAR(30) NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (webhook_id, suite_id, name)
+);
+
+CREATE INDEX idx_check_suite_runs_updated_at ON check_suite_runs (updated_at);
@@ -0,0 +1,8 @@
+CREATE TABLE user_link_verifications (
+    provider VARCHAR(20) NOT NULL,
+    telegram_user_id BIGINT NOT NULL,
+    provider_username VARCHAR(255) NOT NULL,
+    code VARCHAR(32) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (provider, telegram_user_id)
+);
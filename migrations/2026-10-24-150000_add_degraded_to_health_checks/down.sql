@@ -0,0 +1 @@
+ALTER TABLE health_checks DROP COLUMN during_degradation;
@@ -0,0 +1,2 @@
+ALTER TABLE health_checks
+    ADD COLUMN during_degradation BOOLEAN NOT NULL DEFAULT FALSE;
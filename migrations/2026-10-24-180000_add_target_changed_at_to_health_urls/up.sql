@@ -0,0 +1,2 @@
+ALTER TABLE health_urls
+    ADD COLUMN target_changed_at TIMESTAMPTZ;
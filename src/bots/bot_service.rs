@@ -1,11 +1,16 @@
 use crate::observability::alerts::Severity;
 use crate::observability::{ALERTS, METRICS};
+use crate::services::actions::{handle_action_callback, handle_remove_token, handle_token};
 use crate::services::broadcast::commands::{
     handle_approve_all, handle_broadcast, handle_broadcast_cancel, handle_broadcast_status,
     handle_broadcast_test, handle_pending_list, handle_reject_all,
 };
 use crate::services::broadcast::db::{handle_bot_removed, upsert_chat_bot_subscription};
 use crate::services::broadcast::types::BotType;
+use crate::services::mentions::{
+    handle_link, handle_map, handle_mappings, handle_unlink, handle_unmap, Provider,
+};
+use crate::services::queries::{handle_issues, handle_pipeline, handle_prs, handle_repo};
 use crate::services::stats::{record_churn_event, record_new_chat_event};
 use crate::utils::telegram_admin::send_message_to_admin;
 use html_escape::encode_text;
@@ -16,8 +21,10 @@ use teloxide::dptree::case;
 use teloxide::macros::BotCommands;
 use teloxide::payloads::SendMessageSetters;
 use teloxide::prelude::LoggingErrorHandler;
-use teloxide::prelude::{ChatId, ChatMemberUpdated, Message, Requester, ResponseResult, Update};
-use teloxide::types::{ChatMemberKind, ParseMode};
+use teloxide::prelude::{
+    CallbackQuery, ChatId, ChatMemberUpdated, Message, Requester, ResponseResult, Update,
+};
+use teloxide::types::{ChatMemberKind, InlineKeyboardMarkup, ParseMode};
 use teloxide::{dptree, filter_command, Bot};
 
 #[derive(Debug, Clone)]
@@ -83,6 +90,36 @@ enum Command {
     Approveall,
     #[command(description = "Reject all pending deactivations (admin only)")]
     Rejectall,
+    #[command(
+        description = "Mention a Telegram user for a GitHub/GitLab account. Usage: /map github:alice @alice_tg"
+    )]
+    Map,
+    #[command(description = "Remove a user mapping. Usage: /unmap github:alice")]
+    Unmap,
+    #[command(description = "List user mappings for this chat")]
+    Mappings,
+    #[command(
+        description = "Get direct messages for your reviews, assignments and mentions. Usage: /link alice"
+    )]
+    Link,
+    #[command(description = "Stop personal notifications")]
+    Unlink,
+    #[command(
+        description = "Store an API token to enable action buttons (admins only). Usage: /token <token>"
+    )]
+    Token,
+    #[command(description = "Remove the stored API token (admins only)")]
+    Removetoken,
+    #[command(
+        description = "Set the repository queried by this chat (admins only). Usage: /repo owner/name"
+    )]
+    Repo,
+    #[command(description = "List open pull requests")]
+    Prs,
+    #[command(description = "Show the latest pipeline status. Usage: /pipeline [branch]")]
+    Pipeline,
+    #[command(description = "List open issues. Usage: /issues [label:bug]")]
+    Issues,
 }
 
 impl BotService {
@@ -303,6 +340,15 @@ impl BotService {
     }
 
     pub async fn send_telegram_message(&self, message: TelegramMessage) -> ResponseResult<()> {
+        self.send_telegram_message_with_keyboard(message, None)
+            .await
+    }
+
+    pub async fn send_telegram_message_with_keyboard(
+        &self,
+        message: TelegramMessage,
+        keyboard: Option<InlineKeyboardMarkup>,
+    ) -> ResponseResult<()> {
         let TelegramMessage {
             chat_id,
             thread_id,
@@ -321,6 +367,10 @@ impl BotService {
             request = request.message_thread_id(tid);
         }
 
+        if let Some(keyboard) = keyboard {
+            request = request.reply_markup(keyboard);
+        }
+
         request.await?;
 
         Ok(())
@@ -391,8 +441,77 @@ impl BotService {
                             handle_reject_all(&bot.bot, &msg, &bot.pool, bot.config.admin_chat_id)
                                 .await
                         },
+                    ))
+                    .branch(case![Command::Map].endpoint(
+                        move |msg: Message, bot: BotService| async move {
+                            handle_map(&bot.bot, &msg, &bot.pool).await
+                        },
+                    ))
+                    .branch(case![Command::Unmap].endpoint(
+                        move |msg: Message, bot: BotService| async move {
+                            handle_unmap(&bot.bot, &msg, &bot.pool).await
+                        },
+                    ))
+                    .branch(case![Command::Mappings].endpoint(
+                        move |msg: Message, bot: BotService| async move {
+                            handle_mappings(&bot.bot, &msg, &bot.pool).await
+                        },
+                    ))
+                    .branch(case![Command::Link].endpoint(
+                        move |msg: Message, bot: BotService| async move {
+                            let provider = Provider::parse(&bot.config.bot_name);
+                            handle_link(&bot.bot, &msg, &bot.pool, provider).await
+                        },
+                    ))
+                    .branch(case![Command::Unlink].endpoint(
+                        move |msg: Message, bot: BotService| async move {
+                            let provider = Provider::parse(&bot.config.bot_name);
+                            handle_unlink(&bot.bot, &msg, &bot.pool, provider).await
+                        },
+                    ))
+                    .branch(case![Command::Token].endpoint(
+                        move |msg: Message, bot: BotService| async move {
+                            let provider = Provider::parse(&bot.config.bot_name);
+                            handle_token(&bot.bot, &msg, &bot.pool, provider).await
+                        },
+                    ))
+                    .branch(case![Command::Removetoken].endpoint(
+                        move |msg: Message, bot: BotService| async move {
+                            let provider = Provider::parse(&bot.config.bot_name);
+                            handle_remove_token(&bot.bot, &msg, &bot.pool, provider).await
+                        },
+                    ))
+                    .branch(case![Command::Repo].endpoint(
+                        move |msg: Message, bot: BotService| async move {
+                            let provider = Provider::parse(&bot.config.bot_name);
+                            handle_repo(&bot.bot, &msg, &bot.pool, provider).await
+                        },
+                    ))
+                    .branch(case![Command::Prs].endpoint(
+                        move |msg: Message, bot: BotService| async move {
+                            let provider = Provider::parse(&bot.config.bot_name);
+                            handle_prs(&bot.bot, &msg, &bot.pool, provider).await
+                        },
+                    ))
+                    .branch(case![Command::Pipeline].endpoint(
+                        move |msg: Message, bot: BotService| async move {
+                            let provider = Provider::parse(&bot.config.bot_name);
+                            handle_pipeline(&bot.bot, &msg, &bot.pool, provider).await
+                        },
+                    ))
+                    .branch(case![Command::Issues].endpoint(
+                        move |msg: Message, bot: BotService| async move {
+                            let provider = Provider::parse(&bot.config.bot_name);
+                            handle_issues(&bot.bot, &msg, &bot.pool, provider).await
+                        },
                     )),
             )
+            .branch(Update::filter_callback_query().endpoint(
+                move |q: CallbackQuery, bot: BotService| async move {
+                    let provider = Provider::parse(&bot.config.bot_name);
+                    handle_action_callback(&bot.bot, &q, &bot.pool, provider).await
+                },
+            ))
             .branch(Update::filter_my_chat_member().endpoint(
                 move |upd: ChatMemberUpdated, bot: BotService| async move {
                     bot.handle_my_chat_member_update(upd).await
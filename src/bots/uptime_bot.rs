@@ -7,22 +7,56 @@ use crate::services::broadcast::commands::{
 };
 use crate::services::broadcast::db::{handle_bot_removed, upsert_chat_bot_subscription};
 use crate::services::broadcast::types::BotType;
+use crate::services::heartbeats::{
+    delete_heartbeat, get_heartbeats_by_chat_id, parse_heartbeat_command, ping_url,
+    upsert_heartbeat, Heartbeat, HeartbeatCommand, HEARTBEAT_USAGE,
+};
 use crate::services::stats::{record_churn_event, record_new_chat_event};
-use crate::services::uptime_checker::check_health;
+use crate::services::status_page::{
+    delete_status_page, get_status_page_by_chat_id, get_status_page_by_slug,
+    parse_status_page_command, public_name, status_page_url, update_status_page_title,
+    upsert_status_page, StatusPageCommand, STATUS_PAGE_USAGE,
+};
+use crate::services::uptime_assertions::{
+    create_assertion, delete_assertions_by_health_url_id, get_assertions_by_health_url_id,
+    parse_assertion, ASSERT_USAGE, MAX_ASSERTIONS_PER_MONITOR,
+};
+use crate::services::uptime_checker::{check_monitor, create_monitoring_client, DEFAULT_TIMEOUT};
+use crate::services::uptime_history::get_monitor_stats;
+use crate::services::uptime_incidents::{
+    format_incident_list, get_incidents_by_chat_id_since, resolve_incident, INCIDENT_LIST_DAYS,
+};
+use crate::services::uptime_maintenance::{
+    create_maintenance_window, delete_maintenance_windows_by_health_url_id,
+    get_maintenance_windows_by_health_url_id, parse_maintenance_window, parse_pause_duration,
+    MAINTENANCE_USAGE, MAX_WINDOWS_PER_MONITOR, PAUSE_USAGE,
+};
+use crate::services::uptime_probes::MonitorKind;
+use crate::services::uptime_request::{
+    parse_edit_monitor_args, parse_new_monitor_args, parse_rename_args, MonitorRequest, EDIT_USAGE,
+    NEW_USAGE, RENAME_USAGE,
+};
+use crate::services::uptime_settings::{
+    format_monitor_settings, parse_monitor_settings, SETTINGS_USAGE,
+};
+use crate::utils::chat_commands::ensure_chat_admin;
+use crate::utils::duration::format_duration;
 use crate::utils::telegram_admin::send_message_to_admin;
 use html_escape::encode_text;
 use notifine::db::DbPool;
+use notifine::models::HealthUrl;
 use notifine::{
     create_chat, create_health_url, delete_health_url_by_id, find_chat_by_telegram_chat_id,
-    get_health_url_by_chat_id_and_url, get_health_urls_by_chat_id, CreateChatInput,
+    get_health_url_by_chat_id_and_url, get_health_urls_by_chat_id, pause_health_url,
+    rename_health_url, resume_health_url, set_health_url_on_status_page,
+    update_health_url_settings, update_health_url_target, CreateChatInput,
 };
-use reqwest::Client;
 use teloxide::dispatching::{Dispatcher, UpdateFilterExt};
 use teloxide::dptree;
 use teloxide::macros::BotCommands;
 use teloxide::prelude::*;
 use teloxide::types::{
-    CallbackQuery, ChatMemberKind, InlineKeyboardButton, InlineKeyboardMarkup, ParseMode,
+    CallbackQuery, ChatMemberKind, InlineKeyboardButton, InlineKeyboardMarkup, MessageId, ParseMode,
 };
 use url::Url;
 
@@ -34,12 +68,52 @@ use url::Url;
 enum Command {
     #[command(description = "Starts the bot.")]
     Start,
-    #[command(description = "Add a new health check endpoint.")]
+    #[command(
+        description = "Add a new health check endpoint. Usage: /new <url> [--method POST] [--header \"Name: value\"] [--body ...] [--auth user:pass] [--status 200-299], /new tcp://host:port [--banner text] or /new dns://name [--expect 1.2.3.4]"
+    )]
     New(String),
     #[command(description = "List all health check endpoints.")]
     List,
     #[command(description = "Delete a health check endpoint by ID.")]
     Delete(String),
+    #[command(
+        description = "Change the URL and request of an endpoint. Usage: /edit <id> <url> [options]"
+    )]
+    Edit(String),
+    #[command(
+        description = "Show an endpoint by name instead of URL. Usage: /rename <id> <label>"
+    )]
+    Rename(String),
+    #[command(description = "Show uptime and response times of an endpoint by ID.")]
+    Stats(String),
+    #[command(
+        description = "Show or change check interval, timeout, retries, alert and latency thresholds. Usage: /settings <id> interval=5m timeout=10s retries=2 confirm=3 recover=2 latency=2s"
+    )]
+    Settings(String),
+    #[command(
+        description = "Check the response body of an endpoint. Usage: /assert <id> contains <text>"
+    )]
+    Assert(String),
+    #[command(
+        description = "Show incidents of the last 30 days, optionally of one endpoint. Usage: /incidents [id]"
+    )]
+    Incidents(String),
+    #[command(description = "Pause alerts of an endpoint. Usage: /pause <id> [duration]")]
+    Pause(String),
+    #[command(description = "Resume alerts of a paused endpoint. Usage: /resume <id>")]
+    Resume(String),
+    #[command(
+        description = "Schedule recurring maintenance windows. Usage: /maintenance <id> <days> <HH:MM-HH:MM> [timezone]"
+    )]
+    Maintenance(String),
+    #[command(
+        description = "Publish a status page of selected endpoints (admins only). Usage: /statuspage [on|off|title|add|remove]"
+    )]
+    Statuspage(String),
+    #[command(
+        description = "Get alerted when a cron job stops pinging. Usage: /heartbeat <name> <period> [grace]"
+    )]
+    Heartbeat(String),
     #[command(description = "Show help information.")]
     Help,
     #[command(
@@ -70,6 +144,7 @@ async fn command_handler(
     command: Command,
     pool: DbPool,
     admin_chat_id: Option<i64>,
+    webhook_base_url: String,
 ) -> ResponseResult<()> {
     let inviter_username = match msg.from() {
         Some(user) => user.username.clone(),
@@ -92,13 +167,14 @@ async fn command_handler(
             )
             .await?
         }
-        Command::New(health_url) => {
+        Command::New(args) => {
             handle_new_health_url(
                 &pool,
                 &bot,
-                health_url,
+                args,
                 msg.chat.id.0,
                 thread_id,
+                msg.id,
                 inviter_username,
             )
             .await?
@@ -109,8 +185,69 @@ async fn command_handler(
         Command::Delete(id_str) => {
             handle_delete_endpoint(&pool, &bot, msg.chat.id.0, thread_id, id_str).await?;
         }
+        Command::Edit(args) => {
+            handle_edit_endpoint(&pool, &bot, msg.chat.id.0, thread_id, msg.id, args).await?;
+        }
+        Command::Rename(args) => {
+            handle_rename_endpoint(&pool, &bot, msg.chat.id.0, thread_id, args).await?;
+        }
+        Command::Stats(id_str) => {
+            handle_stats_endpoint(&pool, &bot, msg.chat.id.0, thread_id, id_str).await?;
+        }
+        Command::Settings(args) => {
+            handle_settings_endpoint(&pool, &bot, msg.chat.id.0, thread_id, args).await?;
+        }
+        Command::Assert(args) => {
+            handle_assert_endpoint(&pool, &bot, msg.chat.id.0, thread_id, args).await?;
+        }
+        Command::Incidents(id_str) => {
+            handle_incidents_endpoint(&pool, &bot, msg.chat.id.0, thread_id, id_str).await?;
+        }
+        Command::Pause(args) => {
+            handle_pause_endpoint(&pool, &bot, msg.chat.id.0, thread_id, args).await?;
+        }
+        Command::Resume(id_str) => {
+            handle_resume_endpoint(&pool, &bot, msg.chat.id.0, thread_id, id_str).await?;
+        }
+        Command::Maintenance(args) => {
+            handle_maintenance_endpoint(&pool, &bot, msg.chat.id.0, thread_id, args).await?;
+        }
+        Command::Statuspage(args) => {
+            // Anyone may see the page's address, only admins can change what it publishes
+            if !args.trim().is_empty()
+                && !ensure_chat_admin(
+                    &bot,
+                    &msg,
+                    "Only chat administrators can change the status page.",
+                )
+                .await?
+            {
+                return Ok(());
+            }
+            handle_status_page_endpoint(
+                &pool,
+                &bot,
+                msg.chat.id.0,
+                thread_id,
+                args,
+                &webhook_base_url,
+            )
+            .await?;
+        }
+        Command::Heartbeat(args) => {
+            handle_heartbeat_endpoint(
+                &pool,
+                &bot,
+                msg.chat.id.0,
+                thread_id,
+                args,
+                &webhook_base_url,
+            )
+            .await?;
+        }
         Command::Help => {
-            let help_text = "Commands available:\n/start\n/new\n/list\n/delete\n/help";
+            let help_text =
+                "Commands available:\n/start\n/new\n/list\n/delete\n/edit\n/rename\n/stats\n/settings\n/assert\n/incidents\n/pause\n/resume\n/maintenance\n/statuspage\n/heartbeat\n/help";
             send_message_simple(&bot, msg.chat.id.0, msg.thread_id, help_text).await?;
         }
         Command::Broadcast => handle_broadcast(&bot, &msg, &pool, admin_chat_id).await?,
@@ -132,11 +269,55 @@ async fn command_handler(
 async fn handle_new_health_url(
     pool: &DbPool,
     bot: &Bot,
-    health_url: String,
+    args: String,
     telegram_chat_id: i64,
     thread_id: Option<i32>,
+    message_id: MessageId,
     inviter_username: Option<String>,
 ) -> ResponseResult<()> {
+    let (health_url, monitor_request) = match parse_new_monitor_args(&args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                &format!("{}\n\n{}", e, NEW_USAGE),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    // Don't leave credentials readable in the chat history
+    if monitor_request.has_secrets() {
+        if let Err(e) = bot
+            .delete_message(ChatId(telegram_chat_id), message_id)
+            .await
+        {
+            tracing::warn!(
+                "Failed to delete /new message with credentials in {}: {}",
+                telegram_chat_id,
+                e
+            );
+        }
+    }
+
+    let request_record = match monitor_request.to_record() {
+        Ok(record) => record,
+        Err(e) => {
+            tracing::error!("Failed to encrypt monitor credentials: {}", e);
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                "Storing credentials is not configured on this server.",
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
     let parsed_url = Url::parse(&health_url);
     if parsed_url.is_err() || health_url.trim().is_empty() {
         send_telegram_message(
@@ -227,15 +408,38 @@ async fn handle_new_health_url(
         return Ok(());
     }
 
-    let client = Client::new();
-    let health_result = check_health(&client, &health_url).await;
+    let client = create_monitoring_client();
+    let health_result =
+        check_monitor(&client, &health_url, &monitor_request, DEFAULT_TIMEOUT, &[]).await;
+
+    // Failures without a response, e.g. a refused private address, are shown with the reason
+    if !health_result.success
+        && (MonitorKind::from_url(&health_url) != MonitorKind::Http
+            || health_result.status_code == 0)
+    {
+        send_telegram_message(
+            bot,
+            TelegramMessage {
+                chat_id: telegram_chat_id,
+                thread_id,
+                message: format!(
+                    "Error! The first check should succeed for the monitor to be added.\n\n\
+                    Failed to add new monitor: {}\nReason: {}",
+                    encode_text(&health_url),
+                    encode_text(health_result.error_message.as_deref().unwrap_or("unknown"))
+                ),
+            },
+        )
+        .await?;
+        return Ok(());
+    }
 
     if !health_result.success {
         send_telegram_message(bot, TelegramMessage {
             chat_id: telegram_chat_id,
             thread_id,
             message: format!(
-                "Error! Endpoint should return success status code (2xx) on first check to be added.\n\n\
+                "Error! Endpoint should return an expected status code (2xx unless set with --status) on first check to be added.\n\n\
                 Failed to add new health check endpoint: {}\nStatus code: {}\nResponse time: {:.2}s",
                 health_url, health_result.status_code, health_result.duration.as_secs_f64()
             ),
@@ -244,34 +448,46 @@ async fn handle_new_health_url(
         return Ok(());
     }
 
-    let message = format!("New health check endpoint added: {}", health_url);
+    let message = match monitor_request.describe() {
+        Some(options) => format!(
+            "New health check endpoint added: {} ({})",
+            health_url,
+            encode_text(&options)
+        ),
+        None => format!("New health check endpoint added: {}", health_url),
+    };
 
-    let new_health_url =
-        match create_health_url(pool, &health_url, chat.id, health_result.status_code as i32) {
-            Ok(h) => h,
-            Err(e) => {
-                tracing::error!("Failed to create health URL: {:?}", e);
-                METRICS.increment_errors();
-                ALERTS
-                    .send_alert(
-                        bot,
-                        Severity::Error,
-                        "Database",
-                        &format!("Failed to create health URL: {}", e),
-                    )
-                    .await;
-                send_telegram_message(
+    let new_health_url = match create_health_url(
+        pool,
+        &health_url,
+        chat.id,
+        health_result.status_code as i32,
+        &request_record,
+    ) {
+        Ok(h) => h,
+        Err(e) => {
+            tracing::error!("Failed to create health URL: {:?}", e);
+            METRICS.increment_errors();
+            ALERTS
+                .send_alert(
                     bot,
-                    TelegramMessage {
-                        chat_id: telegram_chat_id,
-                        thread_id,
-                        message: "Failed to save the health check endpoint.".to_string(),
-                    },
+                    Severity::Error,
+                    "Database",
+                    &format!("Failed to create health URL: {}", e),
                 )
-                .await?;
-                return Ok(());
-            }
-        };
+                .await;
+            send_telegram_message(
+                bot,
+                TelegramMessage {
+                    chat_id: telegram_chat_id,
+                    thread_id,
+                    message: "Failed to save the health check endpoint.".to_string(),
+                },
+            )
+            .await?;
+            return Ok(());
+        }
+    };
 
     send_telegram_message(
         bot,
@@ -399,19 +615,67 @@ fn build_endpoint_list(
     let mut keyboard: Vec<Vec<InlineKeyboardButton>> = vec![];
 
     for health_url in health_urls {
-        let status_emoji = if (200..300).contains(&health_url.status_code) {
-            "✅"
-        } else {
+        let status_emoji = if health_url.is_paused(chrono::Utc::now()) {
+            "⏸"
+        } else if health_url.is_down {
             "❌"
+        } else if health_url.is_degraded {
+            "🐢"
+        } else {
+            "✅"
+        };
+        let status = match MonitorKind::from_url(&health_url.url) {
+            MonitorKind::Http => format!("<b>Status:</b> {}", health_url.status_code),
+            kind => format!("<b>{}</b>", kind.as_str()),
         };
         message.push_str(&format!(
-            "{} <b>Status:</b> {} | {}\n",
-            status_emoji, health_url.status_code, health_url.url
+            "{} <b>ID {}</b> | {} | {}\n",
+            status_emoji,
+            health_url.id,
+            status,
+            encode_text(health_url.display_name())
         ));
-        keyboard.push(vec![InlineKeyboardButton::callback(
-            format!("🗑️ Delete {}", health_url.url),
-            format!("delete:{}", health_url.id),
-        )]);
+        if health_url.label.is_some() {
+            message.push_str(&format!("    {}\n", encode_text(&health_url.url)));
+        }
+        if let Some(options) = MonitorRequest::from_health_url(health_url).describe() {
+            message.push_str(&format!("    {}\n", encode_text(&options)));
+        }
+        if let (Some(expires_at), Some(issuer)) =
+            (health_url.tls_expires_at, &health_url.tls_issuer)
+        {
+            message.push_str(&format!(
+                "    TLS certificate by {} expires {}\n",
+                encode_text(issuer),
+                expires_at.format("%Y-%m-%d")
+            ));
+        }
+        let pause_button = if health_url.is_paused(chrono::Utc::now()) {
+            InlineKeyboardButton::callback(
+                format!("▶️ Resume {}", health_url.id),
+                format!("resume:{}", health_url.id),
+            )
+        } else {
+            InlineKeyboardButton::callback(
+                format!("⏸ Pause {}", health_url.id),
+                format!("pause:{}", health_url.id),
+            )
+        };
+        keyboard.push(vec![
+            pause_button,
+            InlineKeyboardButton::callback(
+                format!("✏️ Edit {}", health_url.id),
+                format!("edit:{}", health_url.id),
+            ),
+            InlineKeyboardButton::callback(
+                format!("📊 Stats {}", health_url.id),
+                format!("stats:{}", health_url.id),
+            ),
+            InlineKeyboardButton::callback(
+                format!("🗑️ Delete {}", health_url.id),
+                format!("delete:{}", health_url.id),
+            ),
+        ]);
     }
 
     (message, keyboard)
@@ -559,203 +823,1432 @@ async fn handle_delete_endpoint(
     Ok(())
 }
 
-async fn callback_handler(bot: Bot, q: CallbackQuery, pool: DbPool) -> ResponseResult<()> {
-    if let Some(data) = q.data {
-        if let Some(id_str) = data.strip_prefix("delete:") {
-            if let Ok(health_url_id) = id_str.parse::<i32>() {
-                if let Some(msg) = q.message {
-                    let telegram_chat_id = msg.chat.id.0;
-                    let chat =
-                        match find_chat_by_telegram_chat_id(&pool, &telegram_chat_id.to_string()) {
-                            Ok(c) => c,
-                            Err(e) => {
-                                tracing::error!("Database error: {:?}", e);
-                                METRICS.increment_errors();
-                                ALERTS
-                                    .send_alert(
-                                        &bot,
-                                        Severity::Error,
-                                        "Database",
-                                        &format!("Failed to find chat in callback handler: {}", e),
-                                    )
-                                    .await;
-                                bot.answer_callback_query(&q.id)
-                                    .text("Database error occurred")
-                                    .await?;
-                                return Ok(());
-                            }
-                        };
-
-                    if let Some(chat) = chat {
-                        let deleted = match delete_health_url_by_id(&pool, health_url_id, chat.id) {
-                            Ok(d) => d,
-                            Err(e) => {
-                                tracing::error!("Database error: {:?}", e);
-                                METRICS.increment_errors();
-                                ALERTS
-                                    .send_alert(
-                                        &bot,
-                                        Severity::Error,
-                                        "Database",
-                                        &format!(
-                                            "Failed to delete health URL {} in callback: {}",
-                                            health_url_id, e
-                                        ),
-                                    )
-                                    .await;
-                                bot.answer_callback_query(&q.id)
-                                    .text("Database error while deleting")
-                                    .await?;
-                                return Ok(());
-                            }
-                        };
-
-                        if deleted {
-                            bot.answer_callback_query(&q.id).await?;
-                            let health_urls = match get_health_urls_by_chat_id(
-                                &pool,
-                                chat.id as i64,
-                            ) {
-                                Ok(urls) => urls,
-                                Err(e) => {
-                                    tracing::error!("Database error: {:?}", e);
-                                    METRICS.increment_errors();
-                                    ALERTS
-                                        .send_alert(
-                                            &bot,
-                                            Severity::Error,
-                                            "Database",
-                                            &format!(
-                                                "Failed to get health URLs in callback: {}",
-                                                e
-                                            ),
-                                        )
-                                        .await;
-                                    bot.edit_message_text(
-                                        msg.chat.id,
-                                        msg.id,
-                                        "Endpoint deleted but failed to refresh list. Use /list to see current endpoints.",
-                                    )
-                                    .await?;
-                                    return Ok(());
-                                }
-                            };
-
-                            if health_urls.is_empty() {
-                                bot.edit_message_text(
-                                    msg.chat.id,
-                                    msg.id,
-                                    "All endpoints deleted. Use /new <url> to add one.",
-                                )
-                                .await?;
-                            } else {
-                                let (new_message, keyboard) = build_endpoint_list(&health_urls);
-                                bot.edit_message_text(msg.chat.id, msg.id, new_message)
-                                    .parse_mode(ParseMode::Html)
-                                    .reply_markup(InlineKeyboardMarkup::new(keyboard))
-                                    .await?;
-                            }
-                        } else {
-                            bot.answer_callback_query(&q.id)
-                                .text("Endpoint not found")
-                                .await?;
-                        }
-                    }
-                }
-            }
+async fn handle_edit_endpoint(
+    pool: &DbPool,
+    bot: &Bot,
+    telegram_chat_id: i64,
+    thread_id: Option<i32>,
+    message_id: MessageId,
+    args: String,
+) -> ResponseResult<()> {
+    let (id, new_url, monitor_request) = match parse_edit_monitor_args(&args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            let message = format!("{}\n\n{}", e, EDIT_USAGE);
+            send_message_simple(bot, telegram_chat_id, thread_id, &message).await?;
+            return Ok(());
         }
-    }
-    Ok(())
-}
+    };
 
-async fn chat_member_handler(
-    bot: Bot,
-    update: ChatMemberUpdated,
-    pool: DbPool,
-) -> ResponseResult<()> {
-    let chat_id = update.chat.id.0;
-    let bot_name = "Uptime";
-    let chat_title = update.chat.title().map(|t| t.to_string());
+    // Don't leave credentials readable in the chat history
+    if monitor_request.has_secrets() {
+        if let Err(e) = bot
+            .delete_message(ChatId(telegram_chat_id), message_id)
+            .await
+        {
+            tracing::warn!(
+                "Failed to delete /edit message with credentials in {}: {}",
+                telegram_chat_id,
+                e
+            );
+        }
+    }
 
-    tracing::info!(
-        "Received chat member update from {}: {:#?} {:#?}",
-        chat_id,
-        update.old_chat_member,
-        update.new_chat_member
-    );
+    let request_record = match monitor_request.to_record() {
+        Ok(record) => record,
+        Err(e) => {
+            tracing::error!("Failed to encrypt monitor credentials: {}", e);
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                "Storing credentials is not configured on this server.",
+            )
+            .await?;
+            return Ok(());
+        }
+    };
 
-    let old_kind = &update.old_chat_member.kind;
-    let new_kind = &update.new_chat_member.kind;
+    if Url::parse(&new_url).is_err() {
+        let message = format!("Please provide a valid URL.\n\n{}", EDIT_USAGE);
+        send_message_simple(bot, telegram_chat_id, thread_id, &message).await?;
+        return Ok(());
+    }
 
-    if *old_kind == ChatMemberKind::Left && *new_kind == ChatMemberKind::Member {
-        handle_new_chat_and_start_command(
-            &pool,
-            &bot,
-            StartCommand {
-                chat_id,
-                thread_id: None,
-                inviter_username: update.from.username,
-                chat_title,
-            },
-        )
-        .await?
-    } else if matches!(
-        old_kind,
-        ChatMemberKind::Member | ChatMemberKind::Administrator { .. }
-    ) && matches!(
-        new_kind,
-        ChatMemberKind::Left | ChatMemberKind::Banned { .. }
-    ) {
-        tracing::info!("Bot removed from chat {}", chat_id);
-        METRICS.increment_churn();
+    let Some(health_url) = find_chat_health_url(pool, bot, telegram_chat_id, thread_id, id).await?
+    else {
+        return Ok(());
+    };
 
-        if let Err(e) = record_churn_event(&pool, chat_id, bot_name, chat_title.as_deref()) {
-            tracing::warn!("Failed to record churn event: {:?}", e);
+    match get_health_url_by_chat_id_and_url(pool, health_url.chat_id as i64, &new_url) {
+        Ok(Some(existing)) if existing.id != health_url.id => {
+            let message = format!(
+                "This endpoint has already been added with ID {}.",
+                existing.id
+            );
+            send_message_simple(bot, telegram_chat_id, thread_id, &message).await?;
+            return Ok(());
         }
-
-        match handle_bot_removed(&pool, chat_id, BotType::Uptime) {
-            Ok(was_deactivated) => {
-                let message = if was_deactivated {
-                    format!(
-                        "{bot_name} bot removed from chat {chat_id} - chat deactivated (no reachable bots)"
-                    )
-                } else {
-                    format!(
-                        "{bot_name} bot removed from chat {chat_id} but other bots still reachable"
-                    )
-                };
-                send_message_to_admin(&bot, message, 10).await?;
-            }
-            Err(e) => {
-                tracing::error!("Failed to handle bot removal for chat {}: {:?}", chat_id, e);
-                METRICS.increment_errors();
-                ALERTS
-                    .send_alert(
-                        &bot,
-                        Severity::Warning,
-                        "Database",
-                        &format!("Failed to handle bot removal for chat {}: {}", chat_id, e),
-                    )
-                    .await;
-            }
+        Ok(_) => {}
+        Err(e) => {
+            tracing::error!("Failed to check existing health URL: {:?}", e);
+            METRICS.increment_errors();
         }
     }
 
-    Ok(())
-}
-
-pub async fn send_telegram_message(bot: &Bot, message: TelegramMessage) -> ResponseResult<()> {
-    let TelegramMessage {
-        chat_id,
-        thread_id,
-        message,
-    } = message;
-
-    let chat_id = ChatId(chat_id);
+    let client = create_monitoring_client();
+    let health_result =
+        check_monitor(&client, &new_url, &monitor_request, DEFAULT_TIMEOUT, &[]).await;
 
-    let mut request = bot
-        .send_message(chat_id, &message)
-        .parse_mode(ParseMode::Html);
+    if !health_result.success {
+        let reason = health_result
+            .error_message
+            .clone()
+            .unwrap_or_else(|| format!("status code {}", health_result.status_code));
+        let message = format!(
+            "Error! The first check of the new URL should succeed for the endpoint to be changed.\n\n\
+            Failed to check: {}\nReason: {}",
+            new_url, reason
+        );
+        send_message_simple(bot, telegram_chat_id, thread_id, &message).await?;
+        return Ok(());
+    }
+
+    let updated = match update_health_url_target(
+        pool,
+        health_url.id,
+        health_url.chat_id,
+        &new_url,
+        health_result.status_code as i32,
+        &request_record,
+    ) {
+        Ok(Some(updated)) => updated,
+        Ok(None) => {
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                "Endpoint not found. Use /list to see available endpoints.",
+            )
+            .await?;
+            return Ok(());
+        }
+        Err(e) => {
+            tracing::error!("Failed to update health URL {}: {:?}", id, e);
+            METRICS.increment_errors();
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                "Database error occurred while changing the endpoint.",
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    // The outage belonged to the old URL
+    if health_url.is_down {
+        if let Err(e) = resolve_incident(pool, health_url.id, chrono::Utc::now()) {
+            tracing::error!("Failed to resolve incident of health URL {}: {:?}", id, e);
+            METRICS.increment_errors();
+        }
+    }
+
+    let mut message = format!(
+        "✏️ Endpoint {} now checks {}",
+        updated.id,
+        encode_text(&updated.url)
+    );
+    if let Some(options) = monitor_request.describe() {
+        message.push_str(&format!(" ({})", encode_text(&options)));
+    }
+    message.push_str(". Its settings and history are kept.");
+
+    send_telegram_message(
+        bot,
+        TelegramMessage {
+            chat_id: telegram_chat_id,
+            thread_id,
+            message,
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+async fn handle_rename_endpoint(
+    pool: &DbPool,
+    bot: &Bot,
+    telegram_chat_id: i64,
+    thread_id: Option<i32>,
+    args: String,
+) -> ResponseResult<()> {
+    let (id, label) = match parse_rename_args(&args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            let message = format!("{}\n\n{}", e, RENAME_USAGE);
+            send_message_simple(bot, telegram_chat_id, thread_id, &message).await?;
+            return Ok(());
+        }
+    };
+
+    let Some(health_url) = find_chat_health_url(pool, bot, telegram_chat_id, thread_id, id).await?
+    else {
+        return Ok(());
+    };
+
+    let message = match rename_health_url(pool, health_url.id, health_url.chat_id, label.as_deref())
+    {
+        Ok(Some(renamed)) => match &renamed.label {
+            Some(label) => format!("🏷 Endpoint {} is now shown as {}.", renamed.id, label),
+            None => format!(
+                "Endpoint {} is shown by its URL again: {}",
+                renamed.id, renamed.url
+            ),
+        },
+        Ok(None) => "Endpoint not found. Use /list to see available endpoints.".to_string(),
+        Err(e) => {
+            tracing::error!("Failed to rename health URL {}: {:?}", id, e);
+            METRICS.increment_errors();
+            "Database error occurred while renaming the endpoint.".to_string()
+        }
+    };
+
+    send_message_simple(bot, telegram_chat_id, thread_id, &message).await?;
+
+    Ok(())
+}
+
+async fn handle_stats_endpoint(
+    pool: &DbPool,
+    bot: &Bot,
+    telegram_chat_id: i64,
+    thread_id: Option<i32>,
+    id_str: String,
+) -> ResponseResult<()> {
+    let id: i32 = match id_str.trim().parse() {
+        Ok(id) => id,
+        Err(_) => {
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                "Please provide a numeric ID, e.g. /stats 12. Use /list to see your endpoints.",
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let chat = match find_chat_by_telegram_chat_id(pool, &telegram_chat_id.to_string()) {
+        Ok(Some(c)) => c,
+        Ok(None) => {
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                "You should call /start command first to initialize the bot.",
+            )
+            .await?;
+            return Ok(());
+        }
+        Err(e) => {
+            tracing::error!("Database error: {:?}", e);
+            METRICS.increment_errors();
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                "Database error occurred. Please try again later.",
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let health_url = match get_health_urls_by_chat_id(pool, chat.id as i64) {
+        Ok(urls) => urls.into_iter().find(|h| h.id == id),
+        Err(e) => {
+            tracing::error!("Database error: {:?}", e);
+            METRICS.increment_errors();
+            None
+        }
+    };
+
+    let Some(health_url) = health_url else {
+        send_message_simple(
+            bot,
+            telegram_chat_id,
+            thread_id,
+            "Endpoint not found. Use /list to see available endpoints.",
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let stats = match get_monitor_stats(pool, health_url.id) {
+        Ok(stats) => stats,
+        Err(e) => {
+            tracing::error!("Failed to load stats for health URL {}: {:?}", id, e);
+            METRICS.increment_errors();
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                "Database error occurred while loading stats. Please try again later.",
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let message = format!(
+        "📊 <b>Stats for {}</b>\n\n<b>24h:</b> {}\n<b>7d:</b> {}\n<b>30d:</b> {}",
+        encode_text(health_url.display_name()),
+        stats.last_24h.summary(),
+        stats.last_7d.summary(),
+        stats.last_30d.summary()
+    );
+
+    send_telegram_message(
+        bot,
+        TelegramMessage {
+            chat_id: telegram_chat_id,
+            thread_id,
+            message,
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+async fn handle_settings_endpoint(
+    pool: &DbPool,
+    bot: &Bot,
+    telegram_chat_id: i64,
+    thread_id: Option<i32>,
+    args: String,
+) -> ResponseResult<()> {
+    let args: Vec<&str> = args.split_whitespace().collect();
+
+    let Some(id) = args.first().and_then(|id| id.parse::<i32>().ok()) else {
+        send_message_simple(bot, telegram_chat_id, thread_id, SETTINGS_USAGE).await?;
+        return Ok(());
+    };
+
+    let settings = match parse_monitor_settings(&args[1..]) {
+        Ok(settings) => settings,
+        Err(e) => {
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                &format!("{}\n\n{}", e, SETTINGS_USAGE),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let chat = match find_chat_by_telegram_chat_id(pool, &telegram_chat_id.to_string()) {
+        Ok(Some(c)) => c,
+        Ok(None) => {
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                "You should call /start command first to initialize the bot.",
+            )
+            .await?;
+            return Ok(());
+        }
+        Err(e) => {
+            tracing::error!("Database error: {:?}", e);
+            METRICS.increment_errors();
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                "Database error occurred. Please try again later.",
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let health_url = if args.len() == 1 {
+        get_health_urls_by_chat_id(pool, chat.id as i64)
+            .map(|urls| urls.into_iter().find(|h| h.id == id))
+    } else {
+        update_health_url_settings(pool, id, chat.id, &settings)
+    };
+
+    let message = match health_url {
+        Ok(Some(health_url)) => format!(
+            "⚙️ Settings for {}:\n{}",
+            health_url.url,
+            format_monitor_settings(&health_url)
+        ),
+        Ok(None) => "Endpoint not found. Use /list to see available endpoints.".to_string(),
+        Err(e) => {
+            tracing::error!("Failed to update settings for health URL {}: {:?}", id, e);
+            METRICS.increment_errors();
+            "Database error occurred while saving settings. Please try again later.".to_string()
+        }
+    };
+
+    send_message_simple(bot, telegram_chat_id, thread_id, &message).await?;
+
+    Ok(())
+}
+
+async fn handle_assert_endpoint(
+    pool: &DbPool,
+    bot: &Bot,
+    telegram_chat_id: i64,
+    thread_id: Option<i32>,
+    args: String,
+) -> ResponseResult<()> {
+    let args = args.trim();
+    let (id_str, rest) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+    let rest = rest.trim();
+
+    let Ok(id) = id_str.parse::<i32>() else {
+        send_message_simple(bot, telegram_chat_id, thread_id, ASSERT_USAGE).await?;
+        return Ok(());
+    };
+
+    let chat = match find_chat_by_telegram_chat_id(pool, &telegram_chat_id.to_string()) {
+        Ok(Some(c)) => c,
+        Ok(None) => {
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                "You should call /start command first to initialize the bot.",
+            )
+            .await?;
+            return Ok(());
+        }
+        Err(e) => {
+            tracing::error!("Database error: {:?}", e);
+            METRICS.increment_errors();
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                "Database error occurred. Please try again later.",
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let health_url = match get_health_urls_by_chat_id(pool, chat.id as i64) {
+        Ok(urls) => urls.into_iter().find(|h| h.id == id),
+        Err(e) => {
+            tracing::error!("Database error: {:?}", e);
+            METRICS.increment_errors();
+            None
+        }
+    };
+
+    let Some(health_url) = health_url else {
+        send_message_simple(
+            bot,
+            telegram_chat_id,
+            thread_id,
+            "Endpoint not found. Use /list to see available endpoints.",
+        )
+        .await?;
+        return Ok(());
+    };
+
+    if MonitorKind::from_url(&health_url.url) != MonitorKind::Http {
+        send_message_simple(
+            bot,
+            telegram_chat_id,
+            thread_id,
+            "Assertions check HTTP response bodies. Use --banner for TCP and --expect for DNS monitors when adding them with /new.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let assertions = match get_assertions_by_health_url_id(pool, health_url.id) {
+        Ok(assertions) => assertions,
+        Err(e) => {
+            tracing::error!("Failed to load assertions: {:?}", e);
+            METRICS.increment_errors();
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                "Database error occurred. Please try again later.",
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let message = if rest.is_empty() {
+        if assertions.is_empty() {
+            format!(
+                "No assertions for {}. Any 2xx response counts as healthy.\n\n{}",
+                health_url.url, ASSERT_USAGE
+            )
+        } else {
+            let lines: Vec<String> = assertions
+                .iter()
+                .map(|assertion| format!("• {}", assertion.describe()))
+                .collect();
+            format!("Assertions for {}:\n{}", health_url.url, lines.join("\n"))
+        }
+    } else if rest.eq_ignore_ascii_case("clear") {
+        match delete_assertions_by_health_url_id(pool, health_url.id) {
+            Ok(count) => format!("Removed {} assertions from {}.", count, health_url.url),
+            Err(e) => {
+                tracing::error!("Failed to delete assertions: {:?}", e);
+                METRICS.increment_errors();
+                "Database error occurred while removing assertions.".to_string()
+            }
+        }
+    } else if assertions.len() >= MAX_ASSERTIONS_PER_MONITOR {
+        format!(
+            "An endpoint can have at most {} assertions. Use /assert {} clear to start over.",
+            MAX_ASSERTIONS_PER_MONITOR, health_url.id
+        )
+    } else {
+        match parse_assertion(rest) {
+            Ok(input) => match create_assertion(pool, health_url.id, &input) {
+                Ok(assertion) => format!(
+                    "✅ Assertion added to {}: {}",
+                    health_url.url,
+                    assertion.describe()
+                ),
+                Err(e) => {
+                    tracing::error!("Failed to create assertion: {:?}", e);
+                    METRICS.increment_errors();
+                    "Database error occurred while saving the assertion.".to_string()
+                }
+            },
+            Err(e) => format!("{}\n\n{}", e, ASSERT_USAGE),
+        }
+    };
+
+    send_message_simple(bot, telegram_chat_id, thread_id, &message).await?;
+
+    Ok(())
+}
+
+fn build_heartbeat_list(heartbeats: &[Heartbeat], webhook_base_url: &str) -> String {
+    let now = chrono::Utc::now();
+    let mut message = String::from("<b>Heartbeats:</b>\n\n");
+
+    for heartbeat in heartbeats {
+        let status_emoji = if heartbeat.is_down { "❌" } else { "✅" };
+        let last_ping = match heartbeat.last_ping_at {
+            Some(at) => format!(
+                "last ping {} ago",
+                format_duration((now - at).to_std().unwrap_or_default())
+            ),
+            None => "no ping yet".to_string(),
+        };
+        let last_run = heartbeat
+            .last_duration_ms
+            .map(|ms| {
+                format!(
+                    ", took {}",
+                    format_duration(std::time::Duration::from_millis(ms.max(0) as u64))
+                )
+            })
+            .unwrap_or_default();
+
+        message.push_str(&format!(
+            "{} <b>{}</b> {} | {}{}\n    <code>{}</code>\n",
+            status_emoji,
+            encode_text(&heartbeat.name),
+            heartbeat.describe(),
+            last_ping,
+            last_run,
+            encode_text(&ping_url(webhook_base_url, &heartbeat.token))
+        ));
+    }
+
+    message
+}
+
+/// Finds an endpoint of the chat by ID, replying to the user when there is none.
+async fn find_chat_health_url(
+    pool: &DbPool,
+    bot: &Bot,
+    telegram_chat_id: i64,
+    thread_id: Option<i32>,
+    id: i32,
+) -> ResponseResult<Option<HealthUrl>> {
+    let chat = match find_chat_by_telegram_chat_id(pool, &telegram_chat_id.to_string()) {
+        Ok(Some(c)) => c,
+        Ok(None) => {
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                "You should call /start command first to initialize the bot.",
+            )
+            .await?;
+            return Ok(None);
+        }
+        Err(e) => {
+            tracing::error!("Database error: {:?}", e);
+            METRICS.increment_errors();
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                "Database error occurred. Please try again later.",
+            )
+            .await?;
+            return Ok(None);
+        }
+    };
+
+    let health_url = match get_health_urls_by_chat_id(pool, chat.id as i64) {
+        Ok(urls) => urls.into_iter().find(|h| h.id == id),
+        Err(e) => {
+            tracing::error!("Database error: {:?}", e);
+            METRICS.increment_errors();
+            None
+        }
+    };
+
+    if health_url.is_none() {
+        send_message_simple(
+            bot,
+            telegram_chat_id,
+            thread_id,
+            "Endpoint not found. Use /list to see available endpoints.",
+        )
+        .await?;
+    }
+
+    Ok(health_url)
+}
+
+async fn handle_incidents_endpoint(
+    pool: &DbPool,
+    bot: &Bot,
+    telegram_chat_id: i64,
+    thread_id: Option<i32>,
+    id_str: String,
+) -> ResponseResult<()> {
+    let id_str = id_str.trim();
+    let id_filter = if id_str.is_empty() {
+        None
+    } else {
+        match id_str.parse::<i32>() {
+            Ok(id) => Some(id),
+            Err(_) => {
+                send_message_simple(bot, telegram_chat_id, thread_id, "Usage: /incidents [id]")
+                    .await?;
+                return Ok(());
+            }
+        }
+    };
+
+    let chat = match find_chat_by_telegram_chat_id(pool, &telegram_chat_id.to_string()) {
+        Ok(Some(c)) => c,
+        Ok(None) => {
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                "You should call /start command first to initialize the bot.",
+            )
+            .await?;
+            return Ok(());
+        }
+        Err(e) => {
+            tracing::error!("Database error: {:?}", e);
+            METRICS.increment_errors();
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                "Database error occurred. Please try again later.",
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let now = chrono::Utc::now();
+    let since = now - chrono::Duration::days(INCIDENT_LIST_DAYS);
+    let message = match get_incidents_by_chat_id_since(pool, chat.id, since) {
+        Ok(incidents) => {
+            let incidents: Vec<_> = incidents
+                .into_iter()
+                .filter(|(incident, _)| id_filter.is_none_or(|id| incident.health_url_id == id))
+                .collect();
+            format_incident_list(&incidents, since, now)
+        }
+        Err(e) => {
+            tracing::error!("Failed to load incidents: {:?}", e);
+            METRICS.increment_errors();
+            "Database error occurred. Please try again later.".to_string()
+        }
+    };
+
+    send_message_simple(bot, telegram_chat_id, thread_id, &message).await?;
+
+    Ok(())
+}
+
+async fn handle_pause_endpoint(
+    pool: &DbPool,
+    bot: &Bot,
+    telegram_chat_id: i64,
+    thread_id: Option<i32>,
+    args: String,
+) -> ResponseResult<()> {
+    let args: Vec<&str> = args.split_whitespace().collect();
+    let (id, duration) = match args.as_slice() {
+        [id] => (id.parse::<i32>().ok(), None),
+        [id, duration] => (id.parse::<i32>().ok(), Some(*duration)),
+        _ => (None, None),
+    };
+    let Some(id) = id else {
+        send_message_simple(bot, telegram_chat_id, thread_id, PAUSE_USAGE).await?;
+        return Ok(());
+    };
+
+    let duration = match duration.map(parse_pause_duration).transpose() {
+        Ok(duration) => duration,
+        Err(e) => {
+            let message = format!("{}\n\n{}", e, PAUSE_USAGE);
+            send_message_simple(bot, telegram_chat_id, thread_id, &message).await?;
+            return Ok(());
+        }
+    };
+
+    let Some(health_url) = find_chat_health_url(pool, bot, telegram_chat_id, thread_id, id).await?
+    else {
+        return Ok(());
+    };
+
+    let until = duration.map(|duration| {
+        chrono::Utc::now() + chrono::Duration::from_std(duration).unwrap_or_default()
+    });
+    let message = match pause_health_url(pool, health_url.id, health_url.chat_id, until) {
+        Ok(Some(_)) => match (until, duration) {
+            (Some(until), Some(duration)) => format!(
+                "⏸ Alerts for {} are paused for {} (until {} UTC).",
+                health_url.display_name(),
+                format_duration(duration),
+                until.format("%Y-%m-%d %H:%M")
+            ),
+            _ => format!(
+                "⏸ Alerts for {} are paused until you /resume {}.",
+                health_url.display_name(),
+                health_url.id
+            ),
+        },
+        Ok(None) => "Endpoint not found. Use /list to see available endpoints.".to_string(),
+        Err(e) => {
+            tracing::error!("Failed to pause health URL: {:?}", e);
+            METRICS.increment_errors();
+            "Database error occurred while pausing the endpoint.".to_string()
+        }
+    };
+
+    send_message_simple(bot, telegram_chat_id, thread_id, &message).await?;
+
+    Ok(())
+}
+
+async fn handle_resume_endpoint(
+    pool: &DbPool,
+    bot: &Bot,
+    telegram_chat_id: i64,
+    thread_id: Option<i32>,
+    id_str: String,
+) -> ResponseResult<()> {
+    let Ok(id) = id_str.trim().parse::<i32>() else {
+        send_message_simple(bot, telegram_chat_id, thread_id, "Usage: /resume <id>").await?;
+        return Ok(());
+    };
+
+    let Some(health_url) = find_chat_health_url(pool, bot, telegram_chat_id, thread_id, id).await?
+    else {
+        return Ok(());
+    };
+
+    let message = if !health_url.is_paused(chrono::Utc::now()) {
+        format!("Alerts for {} are not paused.", health_url.display_name())
+    } else {
+        match resume_health_url(pool, health_url.id, health_url.chat_id) {
+            Ok(Some(_)) => format!(
+                "▶️ Alerts for {} are active again. You'll get a summary of the paused period after the next check.",
+                health_url.display_name()
+            ),
+            Ok(None) => "Endpoint not found. Use /list to see available endpoints.".to_string(),
+            Err(e) => {
+                tracing::error!("Failed to resume health URL: {:?}", e);
+                METRICS.increment_errors();
+                "Database error occurred while resuming the endpoint.".to_string()
+            }
+        }
+    };
+
+    send_message_simple(bot, telegram_chat_id, thread_id, &message).await?;
+
+    Ok(())
+}
+
+async fn handle_maintenance_endpoint(
+    pool: &DbPool,
+    bot: &Bot,
+    telegram_chat_id: i64,
+    thread_id: Option<i32>,
+    args: String,
+) -> ResponseResult<()> {
+    let args = args.trim();
+    let (id_str, rest) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+    let rest = rest.trim();
+
+    let Ok(id) = id_str.parse::<i32>() else {
+        send_message_simple(bot, telegram_chat_id, thread_id, MAINTENANCE_USAGE).await?;
+        return Ok(());
+    };
+
+    let Some(health_url) = find_chat_health_url(pool, bot, telegram_chat_id, thread_id, id).await?
+    else {
+        return Ok(());
+    };
+
+    let windows = match get_maintenance_windows_by_health_url_id(pool, health_url.id) {
+        Ok(windows) => windows,
+        Err(e) => {
+            tracing::error!("Failed to load maintenance windows: {:?}", e);
+            METRICS.increment_errors();
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                "Database error occurred. Please try again later.",
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let message = if rest.is_empty() {
+        if windows.is_empty() {
+            format!(
+                "No maintenance windows for {}.\n\n{}",
+                health_url.url, MAINTENANCE_USAGE
+            )
+        } else {
+            let lines: Vec<String> = windows
+                .iter()
+                .map(|window| format!("• {}", window.describe()))
+                .collect();
+            format!(
+                "Maintenance windows for {}:\n{}",
+                health_url.url,
+                lines.join("\n")
+            )
+        }
+    } else if rest.eq_ignore_ascii_case("clear") {
+        match delete_maintenance_windows_by_health_url_id(pool, health_url.id) {
+            Ok(count) => format!(
+                "Removed {} maintenance windows from {}.",
+                count, health_url.url
+            ),
+            Err(e) => {
+                tracing::error!("Failed to delete maintenance windows: {:?}", e);
+                METRICS.increment_errors();
+                "Database error occurred while removing maintenance windows.".to_string()
+            }
+        }
+    } else if windows.len() >= MAX_WINDOWS_PER_MONITOR {
+        format!(
+            "An endpoint can have at most {} maintenance windows. Use /maintenance {} clear to start over.",
+            MAX_WINDOWS_PER_MONITOR, health_url.id
+        )
+    } else {
+        match parse_maintenance_window(rest) {
+            Ok(input) => match create_maintenance_window(pool, health_url.id, &input) {
+                Ok(window) => format!(
+                    "✅ Maintenance window added to {}: {}. Alerts are paused during the window.",
+                    health_url.url,
+                    window.describe()
+                ),
+                Err(e) => {
+                    tracing::error!("Failed to create maintenance window: {:?}", e);
+                    METRICS.increment_errors();
+                    "Database error occurred while saving the maintenance window.".to_string()
+                }
+            },
+            Err(e) => format!("{}\n\n{}", e, MAINTENANCE_USAGE),
+        }
+    };
+
+    send_message_simple(bot, telegram_chat_id, thread_id, &message).await?;
+
+    Ok(())
+}
+
+async fn handle_heartbeat_endpoint(
+    pool: &DbPool,
+    bot: &Bot,
+    telegram_chat_id: i64,
+    thread_id: Option<i32>,
+    args: String,
+    webhook_base_url: &str,
+) -> ResponseResult<()> {
+    let command = match parse_heartbeat_command(&args) {
+        Ok(command) => command,
+        Err(e) => {
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                &format!("{}\n\n{}", e, HEARTBEAT_USAGE),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let chat = match find_chat_by_telegram_chat_id(pool, &telegram_chat_id.to_string()) {
+        Ok(Some(c)) => c,
+        Ok(None) => {
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                "You should call /start command first to initialize the bot.",
+            )
+            .await?;
+            return Ok(());
+        }
+        Err(e) => {
+            tracing::error!("Database error: {:?}", e);
+            METRICS.increment_errors();
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                "Database error occurred. Please try again later.",
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let message = match command {
+        HeartbeatCommand::List => match get_heartbeats_by_chat_id(pool, chat.id) {
+            Ok(heartbeats) if heartbeats.is_empty() => {
+                send_message_simple(bot, telegram_chat_id, thread_id, HEARTBEAT_USAGE).await?;
+                return Ok(());
+            }
+            Ok(heartbeats) => build_heartbeat_list(&heartbeats, webhook_base_url),
+            Err(e) => {
+                tracing::error!("Failed to get heartbeats: {:?}", e);
+                METRICS.increment_errors();
+                "Database error occurred. Please try again later.".to_string()
+            }
+        },
+        HeartbeatCommand::Upsert(input) => match upsert_heartbeat(pool, chat.id, &input) {
+            Ok(heartbeat) => {
+                let url = encode_text(&ping_url(webhook_base_url, &heartbeat.token)).to_string();
+                format!(
+                    "Heartbeat <b>{}</b> expects a ping {}.\n\n\
+                    Request this URL when the job succeeds:\n<code>{}</code>\n\n\
+                    Append /start when the job starts to track run durations, or /fail to report a failure:\n\
+                    <code>curl -fsS {}/start; ./backup.sh &amp;&amp; curl -fsS {} || curl -fsS {}/fail</code>",
+                    encode_text(&heartbeat.name),
+                    heartbeat.describe(),
+                    url,
+                    url,
+                    url,
+                    url
+                )
+            }
+            Err(e) => {
+                tracing::error!("Failed to save heartbeat: {:?}", e);
+                METRICS.increment_errors();
+                "Failed to save the heartbeat.".to_string()
+            }
+        },
+        HeartbeatCommand::Delete(name) => match delete_heartbeat(pool, chat.id, &name) {
+            Ok(0) => format!("Heartbeat '{}' not found.", encode_text(&name)),
+            Ok(_) => format!("Heartbeat '{}' deleted.", encode_text(&name)),
+            Err(e) => {
+                tracing::error!("Failed to delete heartbeat: {:?}", e);
+                METRICS.increment_errors();
+                "Database error occurred. Please try again later.".to_string()
+            }
+        },
+    };
+
+    send_telegram_message(
+        bot,
+        TelegramMessage {
+            chat_id: telegram_chat_id,
+            thread_id,
+            message,
+        },
+    )
+    .await
+}
+
+async fn handle_status_page_endpoint(
+    pool: &DbPool,
+    bot: &Bot,
+    telegram_chat_id: i64,
+    thread_id: Option<i32>,
+    args: String,
+    webhook_base_url: &str,
+) -> ResponseResult<()> {
+    let command = match parse_status_page_command(&args) {
+        Ok(command) => command,
+        Err(e) => {
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                &format!("{}\n\n{}", e, STATUS_PAGE_USAGE),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let chat = match find_chat_by_telegram_chat_id(pool, &telegram_chat_id.to_string()) {
+        Ok(Some(c)) => c,
+        Ok(None) => {
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                "You should call /start command first to initialize the bot.",
+            )
+            .await?;
+            return Ok(());
+        }
+        Err(e) => {
+            tracing::error!("Database error: {:?}", e);
+            METRICS.increment_errors();
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                "Database error occurred. Please try again later.",
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let page = match get_status_page_by_chat_id(pool, chat.id) {
+        Ok(page) => page,
+        Err(e) => {
+            tracing::error!("Failed to get status page: {:?}", e);
+            METRICS.increment_errors();
+            send_message_simple(
+                bot,
+                telegram_chat_id,
+                thread_id,
+                "Database error occurred. Please try again later.",
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let message = match command {
+        StatusPageCommand::Show => {
+            let Some(page) = page else {
+                send_message_simple(bot, telegram_chat_id, thread_id, STATUS_PAGE_USAGE).await?;
+                return Ok(());
+            };
+            let shown: Vec<String> = get_health_urls_by_chat_id(pool, chat.id as i64)
+                .unwrap_or_default()
+                .iter()
+                .filter(|health_url| health_url.show_on_status_page)
+                .map(|health_url| {
+                    format!(
+                        "• ID {}: {}",
+                        health_url.id,
+                        encode_text(&public_name(&health_url.url))
+                    )
+                })
+                .collect();
+            let shown = if shown.is_empty() {
+                "No endpoints yet. Add one with /statuspage add &lt;id&gt;.".to_string()
+            } else {
+                shown.join("\n")
+            };
+            format!(
+                "Status page <b>{}</b> is published at:\n{}\n\n{}\n\nJSON for embedding: {}.json",
+                encode_text(&page.title),
+                status_page_url(webhook_base_url, &page.slug),
+                shown,
+                status_page_url(webhook_base_url, &page.slug)
+            )
+        }
+        StatusPageCommand::Enable(slug) => {
+            let slug = slug
+                .or_else(|| page.as_ref().map(|page| page.slug.clone()))
+                .unwrap_or_else(|| uuid::Uuid::new_v4().simple().to_string()[..12].to_string());
+            match get_status_page_by_slug(pool, &slug) {
+                Ok(Some(existing)) if existing.chat_id != chat.id => format!(
+                    "The address '{}' is already taken. Try another one.",
+                    encode_text(&slug)
+                ),
+                Ok(_) => match upsert_status_page(pool, chat.id, &slug) {
+                    Ok(page) => format!(
+                        "✅ Your status page is live at:\n{}\n\nChoose the endpoints to show with /statuspage add &lt;id&gt;.",
+                        status_page_url(webhook_base_url, &page.slug)
+                    ),
+                    Err(e) => {
+                        tracing::error!("Failed to save status page: {:?}", e);
+                        METRICS.increment_errors();
+                        "Failed to save the status page.".to_string()
+                    }
+                },
+                Err(e) => {
+                    tracing::error!("Failed to find status page: {:?}", e);
+                    METRICS.increment_errors();
+                    "Database error occurred. Please try again later.".to_string()
+                }
+            }
+        }
+        StatusPageCommand::Disable => match delete_status_page(pool, chat.id) {
+            Ok(0) => "This chat has no status page.".to_string(),
+            Ok(_) => "Status page removed. Its address no longer works.".to_string(),
+            Err(e) => {
+                tracing::error!("Failed to delete status page: {:?}", e);
+                METRICS.increment_errors();
+                "Database error occurred. Please try again later.".to_string()
+            }
+        },
+        StatusPageCommand::Title(title) => match update_status_page_title(pool, chat.id, &title) {
+            Ok(Some(page)) => format!(
+                "Status page title changed to <b>{}</b>.",
+                encode_text(&page.title)
+            ),
+            Ok(None) => "Publish the status page first with /statuspage on.".to_string(),
+            Err(e) => {
+                tracing::error!("Failed to update status page title: {:?}", e);
+                METRICS.increment_errors();
+                "Database error occurred. Please try again later.".to_string()
+            }
+        },
+        StatusPageCommand::Add(id) | StatusPageCommand::Remove(id) => {
+            let show = matches!(command, StatusPageCommand::Add(_));
+            match set_health_url_on_status_page(pool, id, chat.id, show) {
+                Ok(Some(health_url)) => {
+                    let name = encode_text(&public_name(&health_url.url)).to_string();
+                    match (show, page.is_some()) {
+                        (true, true) => format!("{} is now shown on the status page.", name),
+                        (true, false) => format!(
+                            "{} will be shown on the status page. Publish it with /statuspage on.",
+                            name
+                        ),
+                        (false, _) => format!("{} is no longer shown on the status page.", name),
+                    }
+                }
+                Ok(None) => "Endpoint not found. Use /list to see available endpoints.".to_string(),
+                Err(e) => {
+                    tracing::error!("Failed to update status page endpoints: {:?}", e);
+                    METRICS.increment_errors();
+                    "Database error occurred. Please try again later.".to_string()
+                }
+            }
+        }
+    };
+
+    send_telegram_message(
+        bot,
+        TelegramMessage {
+            chat_id: telegram_chat_id,
+            thread_id,
+            message,
+        },
+    )
+    .await
+}
+
+async fn callback_handler(bot: Bot, q: CallbackQuery, pool: DbPool) -> ResponseResult<()> {
+    let (Some(data), Some(msg)) = (q.data.as_deref(), q.message.as_ref()) else {
+        return Ok(());
+    };
+    let Some((action, id_str)) = data.split_once(':') else {
+        return Ok(());
+    };
+    let Ok(health_url_id) = id_str.parse::<i32>() else {
+        return Ok(());
+    };
+
+    let telegram_chat_id = msg.chat.id.0;
+    let chat = match find_chat_by_telegram_chat_id(&pool, &telegram_chat_id.to_string()) {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Database error: {:?}", e);
+            METRICS.increment_errors();
+            ALERTS
+                .send_alert(
+                    &bot,
+                    Severity::Error,
+                    "Database",
+                    &format!("Failed to find chat in callback handler: {}", e),
+                )
+                .await;
+            bot.answer_callback_query(&q.id)
+                .text("Database error occurred")
+                .await?;
+            return Ok(());
+        }
+    };
+    let Some(chat) = chat else {
+        bot.answer_callback_query(&q.id).await?;
+        return Ok(());
+    };
+
+    match action {
+        "delete" => {
+            let deleted = match delete_health_url_by_id(&pool, health_url_id, chat.id) {
+                Ok(d) => d,
+                Err(e) => {
+                    tracing::error!("Database error: {:?}", e);
+                    METRICS.increment_errors();
+                    ALERTS
+                        .send_alert(
+                            &bot,
+                            Severity::Error,
+                            "Database",
+                            &format!(
+                                "Failed to delete health URL {} in callback: {}",
+                                health_url_id, e
+                            ),
+                        )
+                        .await;
+                    bot.answer_callback_query(&q.id)
+                        .text("Database error while deleting")
+                        .await?;
+                    return Ok(());
+                }
+            };
+
+            if deleted {
+                bot.answer_callback_query(&q.id).await?;
+                refresh_endpoint_list(&bot, &pool, msg, chat.id).await?;
+            } else {
+                bot.answer_callback_query(&q.id)
+                    .text("Endpoint not found")
+                    .await?;
+            }
+        }
+        "pause" | "resume" => {
+            let (result, notice) = if action == "pause" {
+                (
+                    pause_health_url(&pool, health_url_id, chat.id, None),
+                    "Alerts paused until resumed",
+                )
+            } else {
+                (
+                    resume_health_url(&pool, health_url_id, chat.id),
+                    "Alerts resumed",
+                )
+            };
+
+            match result {
+                Ok(Some(_)) => {
+                    bot.answer_callback_query(&q.id).text(notice).await?;
+                    refresh_endpoint_list(&bot, &pool, msg, chat.id).await?;
+                }
+                Ok(None) => {
+                    bot.answer_callback_query(&q.id)
+                        .text("Endpoint not found")
+                        .await?;
+                }
+                Err(e) => {
+                    tracing::error!("Failed to {} health URL {}: {:?}", action, health_url_id, e);
+                    METRICS.increment_errors();
+                    bot.answer_callback_query(&q.id)
+                        .text("Database error occurred")
+                        .await?;
+                }
+            }
+        }
+        "edit" => {
+            bot.answer_callback_query(&q.id).await?;
+            let message = format!(
+                "To change endpoint {}, send /edit {} <url> [options] with the new URL. The options are the same as for /new.",
+                health_url_id, health_url_id
+            );
+            send_message_simple(&bot, telegram_chat_id, msg.thread_id, &message).await?;
+        }
+        "stats" => {
+            bot.answer_callback_query(&q.id).await?;
+            handle_stats_endpoint(
+                &pool,
+                &bot,
+                telegram_chat_id,
+                msg.thread_id,
+                health_url_id.to_string(),
+            )
+            .await?;
+        }
+        _ => {
+            bot.answer_callback_query(&q.id).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Redraws the `/list` message a button was pressed on.
+async fn refresh_endpoint_list(
+    bot: &Bot,
+    pool: &DbPool,
+    msg: &Message,
+    chat_id: i32,
+) -> ResponseResult<()> {
+    let health_urls = match get_health_urls_by_chat_id(pool, chat_id as i64) {
+        Ok(urls) => urls,
+        Err(e) => {
+            tracing::error!("Database error: {:?}", e);
+            METRICS.increment_errors();
+            ALERTS
+                .send_alert(
+                    bot,
+                    Severity::Error,
+                    "Database",
+                    &format!("Failed to get health URLs in callback: {}", e),
+                )
+                .await;
+            bot.edit_message_text(
+                msg.chat.id,
+                msg.id,
+                "Endpoints changed but failed to refresh list. Use /list to see current endpoints.",
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    if health_urls.is_empty() {
+        bot.edit_message_text(
+            msg.chat.id,
+            msg.id,
+            "All endpoints deleted. Use /new <url> to add one.",
+        )
+        .await?;
+    } else {
+        let (new_message, keyboard) = build_endpoint_list(&health_urls);
+        bot.edit_message_text(msg.chat.id, msg.id, new_message)
+            .parse_mode(ParseMode::Html)
+            .reply_markup(InlineKeyboardMarkup::new(keyboard))
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn chat_member_handler(
+    bot: Bot,
+    update: ChatMemberUpdated,
+    pool: DbPool,
+) -> ResponseResult<()> {
+    let chat_id = update.chat.id.0;
+    let bot_name = "Uptime";
+    let chat_title = update.chat.title().map(|t| t.to_string());
+
+    tracing::info!(
+        "Received chat member update from {}: {:#?} {:#?}",
+        chat_id,
+        update.old_chat_member,
+        update.new_chat_member
+    );
+
+    let old_kind = &update.old_chat_member.kind;
+    let new_kind = &update.new_chat_member.kind;
+
+    if *old_kind == ChatMemberKind::Left && *new_kind == ChatMemberKind::Member {
+        handle_new_chat_and_start_command(
+            &pool,
+            &bot,
+            StartCommand {
+                chat_id,
+                thread_id: None,
+                inviter_username: update.from.username,
+                chat_title,
+            },
+        )
+        .await?
+    } else if matches!(
+        old_kind,
+        ChatMemberKind::Member | ChatMemberKind::Administrator { .. }
+    ) && matches!(
+        new_kind,
+        ChatMemberKind::Left | ChatMemberKind::Banned { .. }
+    ) {
+        tracing::info!("Bot removed from chat {}", chat_id);
+        METRICS.increment_churn();
+
+        if let Err(e) = record_churn_event(&pool, chat_id, bot_name, chat_title.as_deref()) {
+            tracing::warn!("Failed to record churn event: {:?}", e);
+        }
+
+        match handle_bot_removed(&pool, chat_id, BotType::Uptime) {
+            Ok(was_deactivated) => {
+                let message = if was_deactivated {
+                    format!(
+                        "{bot_name} bot removed from chat {chat_id} - chat deactivated (no reachable bots)"
+                    )
+                } else {
+                    format!(
+                        "{bot_name} bot removed from chat {chat_id} but other bots still reachable"
+                    )
+                };
+                send_message_to_admin(&bot, message, 10).await?;
+            }
+            Err(e) => {
+                tracing::error!("Failed to handle bot removal for chat {}: {:?}", chat_id, e);
+                METRICS.increment_errors();
+                ALERTS
+                    .send_alert(
+                        &bot,
+                        Severity::Warning,
+                        "Database",
+                        &format!("Failed to handle bot removal for chat {}: {}", chat_id, e),
+                    )
+                    .await;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn send_telegram_message(bot: &Bot, message: TelegramMessage) -> ResponseResult<()> {
+    let TelegramMessage {
+        chat_id,
+        thread_id,
+        message,
+    } = message;
+
+    let chat_id = ChatId(chat_id);
+
+    let mut request = bot
+        .send_message(chat_id, &message)
+        .parse_mode(ParseMode::Html);
 
     if let Some(tid) = thread_id {
         request = request.message_thread_id(tid);
@@ -886,6 +2379,9 @@ async fn handle_new_chat_and_start_command(
             \n/new - Add a new health check endpoint\
             \n/list - List all health check endpoints\
             \n/delete - Delete a health check endpoint\
+            \n/stats - Show uptime and response times of an endpoint\
+            \n/settings - Change check interval, timeout and alerting of an endpoint\
+            \n/assert - Check the response body of an endpoint\
             \n/help - Show this help message",
         bot_name = bot_name
     );
@@ -933,7 +2429,12 @@ async fn handle_new_chat_and_start_command(
     Ok(())
 }
 
-pub async fn run_bot(pool: DbPool, token: String, admin_chat_id: Option<i64>) {
+pub async fn run_bot(
+    pool: DbPool,
+    token: String,
+    admin_chat_id: Option<i64>,
+    webhook_base_url: String,
+) {
     tracing::info!("Starting bot...");
 
     let bot = Bot::new(token);
@@ -946,7 +2447,11 @@ pub async fn run_bot(pool: DbPool, token: String, admin_chat_id: Option<i64>) {
                     let pool = pool.clone();
                     move |bot: Bot, msg: Message, cmd: Command| {
                         let pool = pool.clone();
-                        async move { command_handler(bot, msg, cmd, pool, admin_chat_id).await }
+                        let webhook_base_url = webhook_base_url.clone();
+                        async move {
+                            command_handler(bot, msg, cmd, pool, admin_chat_id, webhook_base_url)
+                                .await
+                        }
                     }
                 }),
         )
@@ -1,4 +1,9 @@
+use crate::services::heartbeats::http_server::{
+    handle_heartbeat_fail, handle_heartbeat_ping, handle_heartbeat_start,
+};
+use crate::services::status_page::http_server::{handle_status_page, handle_status_page_json};
 use crate::webhooks::beep::http_server::handle_beep_webhook;
+use crate::webhooks::dockerhub::http_server::handle_dockerhub_webhook;
 use crate::webhooks::github::http_server::handle_github_webhook;
 use crate::webhooks::gitlab::http_server::handle_gitlab_webhook;
 use actix_web::{get, middleware, web, App, HttpServer, Responder};
@@ -23,6 +28,12 @@ pub async fn run_http_server(pool: DbPool) -> std::io::Result<()> {
             .service(handle_gitlab_webhook)
             .service(handle_github_webhook)
             .service(handle_beep_webhook)
+            .service(handle_dockerhub_webhook)
+            .service(handle_heartbeat_start)
+            .service(handle_heartbeat_fail)
+            .service(handle_heartbeat_ping)
+            .service(handle_status_page_json)
+            .service(handle_status_page)
     })
     .bind(("0.0.0.0", port))?
     .run()
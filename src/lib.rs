@@ -218,6 +218,7 @@ pub fn create_health_url(
     new_url: &str,
     chat_id: i32,
     status_code: i32,
+    request: &HealthUrlRequest,
 ) -> Result<HealthUrl, DbError> {
     use self::schema::health_urls;
 
@@ -230,7 +231,7 @@ pub fn create_health_url(
     };
 
     Ok(diesel::insert_into(health_urls::table)
-        .values(&new_health_endpoint)
+        .values((&new_health_endpoint, request))
         .get_result(conn)?)
 }
 
@@ -261,20 +262,239 @@ pub fn get_all_health_urls(pool: &PgPool) -> Result<Vec<HealthUrl>, DbError> {
     Ok(health_urls.load::<HealthUrl>(conn)?)
 }
 
-pub fn update_health_url_status(
+/// Stores the outcome of a check: the last status code and the alerting state.
+pub fn update_health_url_check_state(
     pool: &PgPool,
     id_to_update: i32,
     new_status_code: i32,
+    state: &HealthUrlCheckState,
 ) -> Result<HealthUrl, DbError> {
     use self::schema::health_urls::dsl::*;
 
     let conn = &mut pool.get()?;
 
     Ok(diesel::update(health_urls.filter(id.eq(id_to_update)))
-        .set(status_code.eq(new_status_code))
+        .set((status_code.eq(new_status_code), state))
         .get_result(conn)?)
 }
 
+pub fn update_health_url_request(
+    pool: &PgPool,
+    id_to_update: i32,
+    request: &HealthUrlRequest,
+) -> Result<HealthUrl, DbError> {
+    use self::schema::health_urls::dsl::*;
+
+    let conn = &mut pool.get()?;
+
+    Ok(diesel::update(health_urls.filter(id.eq(id_to_update)))
+        .set(request)
+        .get_result(conn)?)
+}
+
+pub fn update_health_url_tls_state(
+    pool: &PgPool,
+    id_to_update: i32,
+    state: &HealthUrlTlsState,
+) -> Result<HealthUrl, DbError> {
+    use self::schema::health_urls::dsl::*;
+
+    let conn = &mut pool.get()?;
+
+    Ok(diesel::update(health_urls.filter(id.eq(id_to_update)))
+        .set(state)
+        .get_result(conn)?)
+}
+
+pub fn update_health_url_latency_state(
+    pool: &PgPool,
+    id_to_update: i32,
+    state: &HealthUrlLatencyState,
+) -> Result<HealthUrl, DbError> {
+    use self::schema::health_urls::dsl::*;
+
+    let conn = &mut pool.get()?;
+
+    Ok(diesel::update(health_urls.filter(id.eq(id_to_update)))
+        .set(state)
+        .get_result(conn)?)
+}
+
+/// Pauses alerts until `until`, or until resumed when `None`.
+pub fn pause_health_url(
+    pool: &PgPool,
+    health_url_id: i32,
+    chat_id_value: i32,
+    until: Option<DateTime<Utc>>,
+) -> Result<Option<HealthUrl>, DbError> {
+    use self::schema::health_urls::dsl::*;
+
+    let conn = &mut pool.get()?;
+
+    Ok(diesel::update(
+        health_urls
+            .filter(id.eq(health_url_id))
+            .filter(chat_id.eq(chat_id_value)),
+    )
+    .set((paused_at.eq(Some(Utc::now())), paused_until.eq(until)))
+    .get_result(conn)
+    .optional()?)
+}
+
+pub fn resume_health_url(
+    pool: &PgPool,
+    health_url_id: i32,
+    chat_id_value: i32,
+) -> Result<Option<HealthUrl>, DbError> {
+    use self::schema::health_urls::dsl::*;
+
+    let conn = &mut pool.get()?;
+
+    Ok(diesel::update(
+        health_urls
+            .filter(id.eq(health_url_id))
+            .filter(chat_id.eq(chat_id_value)),
+    )
+    .set((
+        paused_at.eq(None::<DateTime<Utc>>),
+        paused_until.eq(None::<DateTime<Utc>>),
+    ))
+    .get_result(conn)
+    .optional()?)
+}
+
+pub fn set_health_url_on_status_page(
+    pool: &PgPool,
+    health_url_id: i32,
+    chat_id_value: i32,
+    show: bool,
+) -> Result<Option<HealthUrl>, DbError> {
+    use self::schema::health_urls::dsl::*;
+
+    let conn = &mut pool.get()?;
+
+    Ok(diesel::update(
+        health_urls
+            .filter(id.eq(health_url_id))
+            .filter(chat_id.eq(chat_id_value)),
+    )
+    .set(show_on_status_page.eq(show))
+    .get_result(conn)
+    .optional()?)
+}
+
+/// Sets the name shown instead of the URL, or removes it with `None`.
+pub fn rename_health_url(
+    pool: &PgPool,
+    health_url_id: i32,
+    chat_id_value: i32,
+    new_label: Option<&str>,
+) -> Result<Option<HealthUrl>, DbError> {
+    use self::schema::health_urls::dsl::*;
+
+    let conn = &mut pool.get()?;
+
+    Ok(diesel::update(
+        health_urls
+            .filter(id.eq(health_url_id))
+            .filter(chat_id.eq(chat_id_value)),
+    )
+    .set((label.eq(new_label), updated_at.eq(Utc::now())))
+    .get_result(conn)
+    .optional()?)
+}
+
+/// Points a monitor at a new URL and request, keeping its ID, settings and history.
+/// The alerting, response time and certificate state start over, as they belong to the old target.
+pub fn update_health_url_target(
+    pool: &PgPool,
+    health_url_id: i32,
+    chat_id_value: i32,
+    new_url: &str,
+    new_status_code: i32,
+    request: &HealthUrlRequest,
+) -> Result<Option<HealthUrl>, DbError> {
+    use self::schema::health_urls::dsl::*;
+
+    let conn = &mut pool.get()?;
+    let now = Utc::now();
+
+    let tls_state = HealthUrlTlsState {
+        tls_expires_at: None,
+        tls_issuer: None,
+        tls_last_warning_days: None,
+    };
+
+    Ok(diesel::update(
+        health_urls
+            .filter(id.eq(health_url_id))
+            .filter(chat_id.eq(chat_id_value)),
+    )
+    .set((
+        url.eq(new_url),
+        status_code.eq(new_status_code),
+        request,
+        &HealthUrlCheckState::default(),
+        &HealthUrlLatencyState::default(),
+        &tls_state,
+        target_changed_at.eq(now),
+        updated_at.eq(now),
+    ))
+    .get_result(conn)
+    .optional()?)
+}
+
+/// Marks the start of a pause or maintenance window, or its end with `None`.
+pub fn update_health_url_maintenance_started(
+    pool: &PgPool,
+    id_to_update: i32,
+    started_at: Option<DateTime<Utc>>,
+) -> Result<(), DbError> {
+    use self::schema::health_urls::dsl::*;
+
+    let conn = &mut pool.get()?;
+
+    diesel::update(health_urls.filter(id.eq(id_to_update)))
+        .set(maintenance_started_at.eq(started_at))
+        .execute(conn)?;
+
+    Ok(())
+}
+
+pub fn update_health_url_settings(
+    pool: &PgPool,
+    health_url_id: i32,
+    chat_id_value: i32,
+    settings: &HealthUrlSettings,
+) -> Result<Option<HealthUrl>, DbError> {
+    use self::schema::health_urls::dsl::*;
+
+    let conn = &mut pool.get()?;
+
+    Ok(diesel::update(
+        health_urls
+            .filter(id.eq(health_url_id))
+            .filter(chat_id.eq(chat_id_value)),
+    )
+    .set((settings, updated_at.eq(Utc::now())))
+    .get_result(conn)
+    .optional()?)
+}
+
+pub fn get_health_url_by_id(
+    pool: &PgPool,
+    health_url_id: i32,
+) -> Result<Option<HealthUrl>, DbError> {
+    use self::schema::health_urls::dsl::*;
+
+    let conn = &mut pool.get()?;
+
+    Ok(health_urls
+        .filter(id.eq(health_url_id))
+        .first::<HealthUrl>(conn)
+        .optional()?)
+}
+
 pub fn get_health_url_by_chat_id_and_url(
     pool: &PgPool,
     chat_id_value: i64,
@@ -2,8 +2,11 @@ use crate::observability::startup::{
     alert_database_error, alert_http_server_error, alert_migration_error, alert_startup_success,
 };
 use crate::services::broadcast::BroadcastWorker;
+use crate::services::heartbeats::run_heartbeat_checker;
 use crate::services::reminder_scheduler::run_reminder_scheduler;
 use crate::services::statistics_scheduler::run_statistics_scheduler;
+use crate::services::uptime_history::run_uptime_history_maintenance;
+use crate::services::uptime_incidents::run_weekly_incident_report;
 use crate::{http_server::run_http_server, services::uptime_checker::run_uptime_checker};
 
 use dotenv::dotenv;
@@ -129,7 +132,13 @@ async fn main() {
     if let Some(token) = config.uptime_token.clone() {
         let pool = pool.clone();
         let admin_chat_id = config.admin_chat_id;
-        task::spawn(bots::uptime_bot::run_bot(pool, token, admin_chat_id));
+        let webhook_base_url = config.webhook_base_url.clone();
+        task::spawn(bots::uptime_bot::run_bot(
+            pool,
+            token,
+            admin_chat_id,
+            webhook_base_url,
+        ));
         tracing::info!("Uptime bot enabled");
     }
 
@@ -147,6 +156,27 @@ async fn main() {
         }
     });
 
+    task::spawn({
+        let pool = pool.clone();
+        async move {
+            run_uptime_history_maintenance(pool).await;
+        }
+    });
+
+    task::spawn({
+        let pool = pool.clone();
+        async move {
+            run_heartbeat_checker(pool).await;
+        }
+    });
+
+    task::spawn({
+        let pool = pool.clone();
+        async move {
+            run_weekly_incident_report(pool).await;
+        }
+    });
+
     task::spawn({
         let pool = pool.clone();
         async move {
@@ -66,6 +66,98 @@ pub struct HealthUrl {
     pub status_code: i32,
     pub created_at: PgTimestamp,
     pub updated_at: PgTimestamp,
+    pub check_interval_secs: i32,
+    pub timeout_secs: i32,
+    pub max_retries: i32,
+    pub confirmation_threshold: i32,
+    pub consecutive_failures: i32,
+    pub is_down: bool,
+    pub http_method: String,
+    pub request_headers: serde_json::Value,
+    pub request_body: Option<String>,
+    pub basic_auth: Option<String>,
+    pub expected_status: Option<String>,
+    pub tls_expires_at: Option<DateTime<Utc>>,
+    pub tls_issuer: Option<String>,
+    pub tls_warning_days: String,
+    pub tls_last_warning_days: Option<i32>,
+    pub expected_response: Option<String>,
+    pub recovery_threshold: i32,
+    pub consecutive_successes: i32,
+    pub is_flapping: bool,
+    pub flap_window_started_at: Option<DateTime<Utc>>,
+    pub flap_transitions: i32,
+    pub last_transition_at: Option<DateTime<Utc>>,
+    pub paused_at: Option<DateTime<Utc>>,
+    pub paused_until: Option<DateTime<Utc>>,
+    pub maintenance_started_at: Option<DateTime<Utc>>,
+    pub show_on_status_page: bool,
+    /// Name shown instead of the URL, set with `/rename`.
+    pub label: Option<String>,
+    pub latency_threshold: String,
+    pub is_degraded: bool,
+    pub latency_streak: i32,
+    pub degraded_since: Option<DateTime<Utc>>,
+    /// When `/edit` last pointed the monitor at a new URL.
+    pub target_changed_at: Option<DateTime<Utc>>,
+}
+
+impl HealthUrl {
+    /// Paused monitors are still checked, but don't alert. `paused_until` is
+    /// empty for monitors paused until `/resume`.
+    pub fn is_paused(&self, now: DateTime<Utc>) -> bool {
+        self.paused_at.is_some() && self.paused_until.is_none_or(|until| until > now)
+    }
+
+    /// The label of the monitor, or its URL when it has none.
+    pub fn display_name(&self) -> &str {
+        self.label.as_deref().unwrap_or(&self.url)
+    }
+
+    pub fn check_state(&self) -> HealthUrlCheckState {
+        HealthUrlCheckState {
+            consecutive_failures: self.consecutive_failures,
+            consecutive_successes: self.consecutive_successes,
+            is_down: self.is_down,
+            is_flapping: self.is_flapping,
+            flap_window_started_at: self.flap_window_started_at,
+            flap_transitions: self.flap_transitions,
+            last_transition_at: self.last_transition_at,
+        }
+    }
+
+    pub fn latency_state(&self) -> HealthUrlLatencyState {
+        HealthUrlLatencyState {
+            is_degraded: self.is_degraded,
+            latency_streak: self.latency_streak,
+            degraded_since: self.degraded_since,
+        }
+    }
+}
+
+/// Alerting state of a monitor, updated after every check.
+#[derive(AsChangeset, Debug, Clone, Default, PartialEq)]
+#[diesel(table_name = health_urls)]
+#[diesel(treat_none_as_null = true)]
+pub struct HealthUrlCheckState {
+    pub consecutive_failures: i32,
+    pub consecutive_successes: i32,
+    pub is_down: bool,
+    pub is_flapping: bool,
+    pub flap_window_started_at: Option<DateTime<Utc>>,
+    pub flap_transitions: i32,
+    pub last_transition_at: Option<DateTime<Utc>>,
+}
+
+/// Response time state of a monitor. `latency_streak` counts the checks in a row
+/// that were slow while it is not degraded, and fast again while it is.
+#[derive(AsChangeset, Debug, Clone, Default, PartialEq)]
+#[diesel(table_name = health_urls)]
+#[diesel(treat_none_as_null = true)]
+pub struct HealthUrlLatencyState {
+    pub is_degraded: bool,
+    pub latency_streak: i32,
+    pub degraded_since: Option<DateTime<Utc>>,
 }
 
 #[derive(Insertable)]
@@ -77,6 +169,45 @@ pub struct NewHealthUrl<'a> {
     pub status_code: i32,
 }
 
+/// How a monitor's request is made: method, headers (a JSON object), body,
+/// `user:password` basic auth and the accepted status codes, e.g. `200-299,401`.
+/// `expected_response` is the banner of TCP monitors or the addresses of DNS monitors.
+#[derive(Insertable, AsChangeset, Debug, Clone, PartialEq)]
+#[diesel(table_name = health_urls)]
+#[diesel(treat_none_as_null = true)]
+pub struct HealthUrlRequest {
+    pub http_method: String,
+    pub request_headers: serde_json::Value,
+    pub request_body: Option<String>,
+    pub basic_auth: Option<String>,
+    pub expected_status: Option<String>,
+    pub expected_response: Option<String>,
+}
+
+/// Per-monitor check settings; `None` fields are left unchanged.
+#[derive(AsChangeset, Debug, Default, PartialEq)]
+#[diesel(table_name = health_urls)]
+pub struct HealthUrlSettings {
+    pub check_interval_secs: Option<i32>,
+    pub timeout_secs: Option<i32>,
+    pub max_retries: Option<i32>,
+    pub confirmation_threshold: Option<i32>,
+    pub recovery_threshold: Option<i32>,
+    pub tls_warning_days: Option<String>,
+    pub latency_threshold: Option<String>,
+}
+
+/// The last seen certificate of an HTTPS monitor and the smallest expiry
+/// threshold (in days) the chat was already warned about.
+#[derive(AsChangeset, Debug, PartialEq)]
+#[diesel(table_name = health_urls)]
+#[diesel(treat_none_as_null = true)]
+pub struct HealthUrlTlsState {
+    pub tls_expires_at: Option<DateTime<Utc>>,
+    pub tls_issuer: Option<String>,
+    pub tls_last_warning_days: Option<i32>,
+}
+
 #[derive(Debug, Queryable, Identifiable)]
 #[diesel(table_name = daily_stats)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
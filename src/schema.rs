@@ -86,6 +86,18 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    chat_api_tokens (id) {
+        id -> Int4,
+        chat_id -> Int4,
+        #[max_length = 20]
+        provider -> Varchar,
+        encrypted_token -> Text,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     chat_bot_subscriptions (id) {
         id -> Int4,
@@ -120,6 +132,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    chat_repositories (id) {
+        id -> Int4,
+        chat_id -> Int4,
+        #[max_length = 20]
+        provider -> Varchar,
+        #[max_length = 255]
+        repository -> Varchar,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     chats (id) {
         id -> Int4,
@@ -140,6 +165,17 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    check_suite_runs (webhook_id, suite_id, name) {
+        webhook_id -> Int4,
+        suite_id -> Int8,
+        name -> Text,
+        #[max_length = 30]
+        conclusion -> Varchar,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     daily_stats (id) {
         id -> Int4,
@@ -158,6 +194,46 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    health_check_rollups (id) {
+        id -> Int4,
+        health_url_id -> Int4,
+        day -> Date,
+        total_checks -> Int4,
+        successful_checks -> Int4,
+        p50_duration_ms -> Nullable<Int4>,
+        p95_duration_ms -> Nullable<Int4>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    health_checks (id) {
+        id -> Int8,
+        health_url_id -> Int4,
+        checked_at -> Timestamptz,
+        success -> Bool,
+        status_code -> Int4,
+        duration_ms -> Int4,
+        #[max_length = 20]
+        failure_reason -> Nullable<Varchar>,
+        error_detail -> Nullable<Text>,
+        during_degradation -> Bool,
+    }
+}
+
+diesel::table! {
+    health_url_assertions (id) {
+        id -> Int4,
+        health_url_id -> Int4,
+        #[max_length = 20]
+        kind -> Varchar,
+        target -> Text,
+        expected -> Nullable<Text>,
+        created_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     health_urls (id) {
         id -> Int4,
@@ -166,6 +242,87 @@ diesel::table! {
         status_code -> Int4,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
+        check_interval_secs -> Int4,
+        timeout_secs -> Int4,
+        max_retries -> Int4,
+        confirmation_threshold -> Int4,
+        consecutive_failures -> Int4,
+        is_down -> Bool,
+        #[max_length = 10]
+        http_method -> Varchar,
+        request_headers -> Jsonb,
+        request_body -> Nullable<Text>,
+        basic_auth -> Nullable<Text>,
+        #[max_length = 100]
+        expected_status -> Nullable<Varchar>,
+        tls_expires_at -> Nullable<Timestamptz>,
+        tls_issuer -> Nullable<Text>,
+        #[max_length = 50]
+        tls_warning_days -> Varchar,
+        tls_last_warning_days -> Nullable<Int4>,
+        expected_response -> Nullable<Text>,
+        recovery_threshold -> Int4,
+        consecutive_successes -> Int4,
+        is_flapping -> Bool,
+        flap_window_started_at -> Nullable<Timestamptz>,
+        flap_transitions -> Int4,
+        last_transition_at -> Nullable<Timestamptz>,
+        paused_at -> Nullable<Timestamptz>,
+        paused_until -> Nullable<Timestamptz>,
+        maintenance_started_at -> Nullable<Timestamptz>,
+        show_on_status_page -> Bool,
+        #[max_length = 100]
+        label -> Nullable<Varchar>,
+        #[max_length = 20]
+        latency_threshold -> Varchar,
+        is_degraded -> Bool,
+        latency_streak -> Int4,
+        degraded_since -> Nullable<Timestamptz>,
+        target_changed_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    heartbeats (id) {
+        id -> Int4,
+        chat_id -> Int4,
+        #[max_length = 100]
+        name -> Varchar,
+        #[max_length = 64]
+        token -> Varchar,
+        period_secs -> Int4,
+        grace_secs -> Int4,
+        last_ping_at -> Nullable<Timestamptz>,
+        last_start_at -> Nullable<Timestamptz>,
+        last_duration_ms -> Nullable<Int8>,
+        is_down -> Bool,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    incidents (id) {
+        id -> Int4,
+        health_url_id -> Int4,
+        started_at -> Timestamptz,
+        resolved_at -> Nullable<Timestamptz>,
+        cause -> Text,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    maintenance_windows (id) {
+        id -> Int4,
+        health_url_id -> Int4,
+        #[max_length = 30]
+        days -> Varchar,
+        start_time -> Time,
+        duration_mins -> Int4,
+        #[max_length = 64]
+        timezone -> Varchar,
+        created_at -> Timestamptz,
     }
 }
 
@@ -206,6 +363,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    status_pages (id) {
+        id -> Int4,
+        chat_id -> Int4,
+        #[max_length = 64]
+        slug -> Varchar,
+        #[max_length = 100]
+        title -> Varchar,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     tesla_auth (id) {
         id -> Int4,
@@ -246,6 +416,47 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    user_link_verifications (provider, telegram_user_id) {
+        #[max_length = 20]
+        provider -> Varchar,
+        telegram_user_id -> Int8,
+        #[max_length = 255]
+        provider_username -> Varchar,
+        #[max_length = 32]
+        code -> Varchar,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    user_links (id) {
+        id -> Int4,
+        #[max_length = 20]
+        provider -> Varchar,
+        #[max_length = 255]
+        provider_username -> Varchar,
+        telegram_user_id -> Int8,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    user_mappings (id) {
+        id -> Int4,
+        chat_id -> Int4,
+        #[max_length = 20]
+        provider -> Varchar,
+        #[max_length = 255]
+        provider_username -> Varchar,
+        #[max_length = 255]
+        telegram_username -> Varchar,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     webhooks (id) {
         id -> Int4,
@@ -260,8 +471,19 @@ diesel::table! {
 }
 
 diesel::joinable!(agreements -> agreement_users (user_id));
+diesel::joinable!(chat_api_tokens -> chats (chat_id));
+diesel::joinable!(chat_repositories -> chats (chat_id));
+diesel::joinable!(check_suite_runs -> webhooks (webhook_id));
+diesel::joinable!(health_check_rollups -> health_urls (health_url_id));
+diesel::joinable!(health_checks -> health_urls (health_url_id));
+diesel::joinable!(health_url_assertions -> health_urls (health_url_id));
+diesel::joinable!(heartbeats -> chats (chat_id));
+diesel::joinable!(incidents -> health_urls (health_url_id));
+diesel::joinable!(maintenance_windows -> health_urls (health_url_id));
 diesel::joinable!(pending_deactivations -> broadcast_jobs (source_broadcast_job_id));
 diesel::joinable!(reminders -> agreements (agreement_id));
+diesel::joinable!(status_pages -> chats (chat_id));
+diesel::joinable!(user_mappings -> chats (chat_id));
 diesel::joinable!(webhooks -> chats (chat_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
@@ -269,15 +491,28 @@ diesel::allow_tables_to_appear_in_same_query!(
     agreement_users,
     agreements,
     broadcast_jobs,
+    chat_api_tokens,
     chat_bot_subscriptions,
     chat_events,
+    chat_repositories,
     chats,
+    check_suite_runs,
     daily_stats,
+    health_check_rollups,
+    health_checks,
+    health_url_assertions,
     health_urls,
+    heartbeats,
+    incidents,
+    maintenance_windows,
     pending_deactivations,
     reminders,
+    status_pages,
     tesla_auth,
     tesla_orders,
     trello_tokens,
+    user_link_verifications,
+    user_links,
+    user_mappings,
     webhooks,
 );
@@ -0,0 +1,147 @@
+use html_escape::encode_text;
+use serde_json::json;
+use teloxide::prelude::*;
+use teloxide::types::{CallbackQuery, ParseMode};
+
+use notifine::db::DbPool;
+use notifine::find_chat_by_telegram_chat_id;
+
+use super::db::find_chat_api_token;
+use super::types::Action;
+use crate::services::api_client::{ApiClient, ApiError};
+use crate::services::mentions::Provider;
+use crate::utils::chat_commands::is_chat_admin;
+use crate::utils::crypto::decrypt_secret;
+
+fn action_provider(action: Action) -> Provider {
+    match action {
+        Action::GithubApprove { .. } | Action::GithubRerun { .. } => Provider::Github,
+        Action::GitlabApprove { .. } | Action::GitlabRetryJob { .. } => Provider::Gitlab,
+    }
+}
+
+/// Performs the action and returns a short description of what was done.
+async fn perform_action(client: &ApiClient, action: Action) -> Result<String, ApiError> {
+    match action {
+        Action::GithubApprove {
+            repository_id,
+            number,
+        } => {
+            client
+                .post(
+                    &format!("/repositories/{repository_id}/pulls/{number}/reviews"),
+                    Some(json!({ "event": "APPROVE" })),
+                )
+                .await?;
+            Ok(format!("approved pull request #{number}"))
+        }
+        Action::GithubRerun {
+            repository_id,
+            run_id,
+        } => {
+            client
+                .post(
+                    &format!(
+                        "/repositories/{repository_id}/actions/runs/{run_id}/rerun-failed-jobs"
+                    ),
+                    None,
+                )
+                .await?;
+            Ok("re-ran the failed jobs of the workflow".to_string())
+        }
+        Action::GitlabApprove { project_id, iid } => {
+            client
+                .post(
+                    &format!("/projects/{project_id}/merge_requests/{iid}/approve"),
+                    None,
+                )
+                .await?;
+            Ok(format!("approved merge request !{iid}"))
+        }
+        Action::GitlabRetryJob { project_id, job_id } => {
+            client
+                .post(&format!("/projects/{project_id}/jobs/{job_id}/retry"), None)
+                .await?;
+            Ok(format!("retried job #{job_id}"))
+        }
+    }
+}
+
+fn load_token(pool: &DbPool, telegram_chat_id: i64, provider: Provider) -> Result<String, String> {
+    let chat = find_chat_by_telegram_chat_id(pool, &telegram_chat_id.to_string())
+        .map_err(|e| format!("Failed to load chat: {}", e))?
+        .ok_or_else(|| "This chat is not registered".to_string())?;
+
+    let encrypted = find_chat_api_token(pool, chat.id, provider)
+        .map_err(|e| format!("Failed to load token: {}", e))?
+        .ok_or_else(|| "No API token stored. An admin can add one with /token".to_string())?;
+
+    decrypt_secret(&encrypted).map_err(|e| e.to_string())
+}
+
+/// Handles presses on action buttons attached to notifications.
+pub async fn handle_action_callback(
+    bot: &Bot,
+    q: &CallbackQuery,
+    pool: &DbPool,
+    provider: Option<Provider>,
+) -> ResponseResult<()> {
+    let action = q.data.as_deref().and_then(Action::parse);
+    let (Some(action), Some(message)) = (action, q.message.as_ref()) else {
+        bot.answer_callback_query(&q.id)
+            .text("This button is no longer supported.")
+            .await?;
+        return Ok(());
+    };
+
+    if provider != Some(action_provider(action)) {
+        bot.answer_callback_query(&q.id)
+            .text("This button belongs to another bot.")
+            .await?;
+        return Ok(());
+    }
+
+    if !is_chat_admin(bot, &message.chat, q.from.id).await {
+        bot.answer_callback_query(&q.id)
+            .text("Only chat administrators can use action buttons.")
+            .show_alert(true)
+            .await?;
+        return Ok(());
+    }
+
+    let token = match load_token(pool, message.chat.id.0, action_provider(action)) {
+        Ok(token) => token,
+        Err(reason) => {
+            bot.answer_callback_query(&q.id)
+                .text(reason)
+                .show_alert(true)
+                .await?;
+            return Ok(());
+        }
+    };
+
+    bot.answer_callback_query(&q.id)
+        .text("Working on it...")
+        .await?;
+
+    let client = ApiClient::new(action_provider(action), Some(token));
+    let user_name = encode_text(&q.from.first_name);
+    let reply = match perform_action(&client, action).await {
+        Ok(outcome) => format!("✅ <b>{user_name}</b> {outcome}"),
+        Err(e) => {
+            tracing::warn!("Notification action {:?} failed: {}", action, e);
+            format!("❌ Action failed: {}", encode_text(&e.to_string()))
+        }
+    };
+
+    let mut request = bot
+        .send_message(message.chat.id, reply)
+        .parse_mode(ParseMode::Html)
+        .reply_to_message_id(message.id);
+    if let Some(thread_id) = message.thread_id {
+        request = request.message_thread_id(thread_id);
+    }
+    request.await?;
+
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use teloxide::prelude::*;
+use teloxide::types::Message;
+
+use notifine::db::DbPool;
+
+use super::db::{delete_chat_api_token, upsert_chat_api_token};
+use crate::services::mentions::Provider;
+use crate::utils::chat_commands::{ensure_chat_admin, find_chat_id};
+use crate::utils::crypto::encrypt_secret;
+
+const TOKEN_USAGE: &str = "Usage: /token <api_token>\n\n\
+    Stores an API token for this chat so the Approve, Rerun and Retry buttons \
+    on notifications can act on your behalf, and so /prs, /pipeline and /issues \
+    can read private repositories. The token is stored encrypted and \
+    your message is deleted right away.\n\n\
+    GitHub: a fine-grained token with Pull requests and Actions write access.\n\
+    GitLab: a project access token with the api scope.";
+
+async fn ensure_admin(
+    bot: &Bot,
+    msg: &Message,
+    provider: Option<Provider>,
+) -> ResponseResult<Option<Provider>> {
+    let Some(provider) = provider else {
+        bot.send_message(
+            msg.chat.id,
+            "Notification actions are only available for GitHub and GitLab.",
+        )
+        .await?;
+        return Ok(None);
+    };
+
+    if !ensure_chat_admin(
+        bot,
+        msg,
+        "Only chat administrators can manage the API token.",
+    )
+    .await?
+    {
+        return Ok(None);
+    }
+
+    Ok(Some(provider))
+}
+
+pub async fn handle_token(
+    bot: &Bot,
+    msg: &Message,
+    pool: &DbPool,
+    provider: Option<Provider>,
+) -> ResponseResult<()> {
+    let token = msg
+        .text()
+        .unwrap_or("")
+        .split_whitespace()
+        .nth(1)
+        .map(str::to_string);
+
+    // Never leave a token readable in the chat history, whatever happens next
+    if token.is_some() {
+        if let Err(e) = bot.delete_message(msg.chat.id, msg.id).await {
+            tracing::warn!("Failed to delete token message in {}: {}", msg.chat.id, e);
+        }
+    }
+
+    let Some(provider) = ensure_admin(bot, msg, provider).await? else {
+        return Ok(());
+    };
+
+    let Some(token) = token else {
+        bot.send_message(msg.chat.id, TOKEN_USAGE).await?;
+        return Ok(());
+    };
+
+    let Some(chat_id) = find_chat_id(bot, msg, pool).await? else {
+        return Ok(());
+    };
+
+    let encrypted = match encrypt_secret(&token) {
+        Ok(encrypted) => encrypted,
+        Err(e) => {
+            tracing::error!("Failed to encrypt API token: {}", e);
+            bot.send_message(
+                msg.chat.id,
+                "Storing tokens is not configured on this server.",
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let reply = match upsert_chat_api_token(pool, chat_id, provider, &encrypted) {
+        Ok(()) => "🔐 Token saved. Action buttons are now enabled for this chat.",
+        Err(e) => {
+            tracing::error!("Failed to save API token: {:?}", e);
+            "Failed to save the token."
+        }
+    };
+
+    bot.send_message(msg.chat.id, reply).await?;
+
+    Ok(())
+}
+
+pub async fn handle_remove_token(
+    bot: &Bot,
+    msg: &Message,
+    pool: &DbPool,
+    provider: Option<Provider>,
+) -> ResponseResult<()> {
+    let Some(provider) = ensure_admin(bot, msg, provider).await? else {
+        return Ok(());
+    };
+
+    let Some(chat_id) = find_chat_id(bot, msg, pool).await? else {
+        return Ok(());
+    };
+
+    let reply = match delete_chat_api_token(pool, chat_id, provider) {
+        Ok(true) => "🗑 Token removed. Action buttons are disabled for this chat.",
+        Ok(false) => "No token is stored for this chat.",
+        Err(e) => {
+            tracing::error!("Failed to delete API token: {:?}", e);
+            "Failed to remove the token."
+        }
+    };
+
+    bot.send_message(msg.chat.id, reply).await?;
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use chrono::Utc;
+use diesel::prelude::*;
+use notifine::db::{DbError, DbPool};
+use notifine::schema::chat_api_tokens;
+
+use crate::services::mentions::Provider;
+
+#[derive(Insertable)]
+#[diesel(table_name = chat_api_tokens)]
+struct NewChatApiToken<'a> {
+    chat_id: i32,
+    provider: &'a str,
+    encrypted_token: &'a str,
+}
+
+pub fn upsert_chat_api_token(
+    pool: &DbPool,
+    chat_id: i32,
+    provider: Provider,
+    encrypted_token: &str,
+) -> Result<(), DbError> {
+    let conn = &mut pool.get()?;
+
+    diesel::insert_into(chat_api_tokens::table)
+        .values(&NewChatApiToken {
+            chat_id,
+            provider: provider.as_str(),
+            encrypted_token,
+        })
+        .on_conflict((chat_api_tokens::chat_id, chat_api_tokens::provider))
+        .do_update()
+        .set((
+            chat_api_tokens::encrypted_token.eq(encrypted_token),
+            chat_api_tokens::updated_at.eq(Utc::now()),
+        ))
+        .execute(conn)?;
+
+    Ok(())
+}
+
+pub fn delete_chat_api_token(
+    pool: &DbPool,
+    chat_id: i32,
+    provider: Provider,
+) -> Result<bool, DbError> {
+    let conn = &mut pool.get()?;
+
+    let deleted = diesel::delete(
+        chat_api_tokens::table
+            .filter(chat_api_tokens::chat_id.eq(chat_id))
+            .filter(chat_api_tokens::provider.eq(provider.as_str())),
+    )
+    .execute(conn)?;
+
+    Ok(deleted > 0)
+}
+
+/// Returns the encrypted token stored for the chat, if any.
+pub fn find_chat_api_token(
+    pool: &DbPool,
+    chat_id: i32,
+    provider: Provider,
+) -> Result<Option<String>, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(chat_api_tokens::table
+        .filter(chat_api_tokens::chat_id.eq(chat_id))
+        .filter(chat_api_tokens::provider.eq(provider.as_str()))
+        .select(chat_api_tokens::encrypted_token)
+        .first::<String>(conn)
+        .optional()?)
+}
@@ -0,0 +1,8 @@
+pub mod callbacks;
+pub mod commands;
+pub mod db;
+pub mod types;
+
+pub use callbacks::handle_action_callback;
+pub use commands::{handle_remove_token, handle_token};
+pub use types::{build_keyboard, Action, NotificationButton};
@@ -0,0 +1,152 @@
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
+
+const CALLBACK_PREFIX: &str = "act:";
+
+/// An action triggered from a notification's inline keyboard. Actions are encoded in the
+/// callback data, which Telegram limits to 64 bytes, so only numeric ids are stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    GithubApprove { repository_id: i64, number: i64 },
+    GithubRerun { repository_id: i64, run_id: i64 },
+    GitlabApprove { project_id: i64, iid: i64 },
+    GitlabRetryJob { project_id: i64, job_id: i64 },
+}
+
+impl Action {
+    pub fn to_callback_data(self) -> String {
+        let (kind, first, second) = match self {
+            Action::GithubApprove {
+                repository_id,
+                number,
+            } => ("gh_approve", repository_id, number),
+            Action::GithubRerun {
+                repository_id,
+                run_id,
+            } => ("gh_rerun", repository_id, run_id),
+            Action::GitlabApprove { project_id, iid } => ("gl_approve", project_id, iid),
+            Action::GitlabRetryJob { project_id, job_id } => ("gl_retry", project_id, job_id),
+        };
+
+        format!("{CALLBACK_PREFIX}{kind}:{first}:{second}")
+    }
+
+    pub fn parse(data: &str) -> Option<Action> {
+        let mut parts = data.strip_prefix(CALLBACK_PREFIX)?.split(':');
+        let kind = parts.next()?;
+        let first = parts.next()?.parse::<i64>().ok()?;
+        let second = parts.next()?.parse::<i64>().ok()?;
+
+        if parts.next().is_some() {
+            return None;
+        }
+
+        match kind {
+            "gh_approve" => Some(Action::GithubApprove {
+                repository_id: first,
+                number: second,
+            }),
+            "gh_rerun" => Some(Action::GithubRerun {
+                repository_id: first,
+                run_id: second,
+            }),
+            "gl_approve" => Some(Action::GitlabApprove {
+                project_id: first,
+                iid: second,
+            }),
+            "gl_retry" => Some(Action::GitlabRetryJob {
+                project_id: first,
+                job_id: second,
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn is_callback_data(data: &str) -> bool {
+        data.starts_with(CALLBACK_PREFIX)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotificationButton {
+    Url { text: String, url: String },
+    Action { text: String, action: Action },
+}
+
+impl NotificationButton {
+    pub fn url(text: &str, url: &str) -> Self {
+        NotificationButton::Url {
+            text: text.to_string(),
+            url: url.to_string(),
+        }
+    }
+
+    pub fn action(text: &str, action: Action) -> Self {
+        NotificationButton::Action {
+            text: text.to_string(),
+            action,
+        }
+    }
+}
+
+/// Builds a single-row keyboard. Buttons with unparsable URLs are skipped.
+pub fn build_keyboard(buttons: &[NotificationButton]) -> Option<InlineKeyboardMarkup> {
+    let row: Vec<InlineKeyboardButton> = buttons
+        .iter()
+        .filter_map(|button| match button {
+            NotificationButton::Url { text, url } => url::Url::parse(url)
+                .ok()
+                .map(|url| InlineKeyboardButton::url(text.clone(), url)),
+            NotificationButton::Action { text, action } => Some(InlineKeyboardButton::callback(
+                text.clone(),
+                action.to_callback_data(),
+            )),
+        })
+        .collect();
+
+    if row.is_empty() {
+        None
+    } else {
+        Some(InlineKeyboardMarkup::new(vec![row]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_callback_data_roundtrip() {
+        let actions = [
+            Action::GithubApprove {
+                repository_id: 123456789,
+                number: 42,
+            },
+            Action::GithubRerun {
+                repository_id: 123456789,
+                run_id: 9876543210,
+            },
+            Action::GitlabApprove {
+                project_id: 15,
+                iid: 7,
+            },
+            Action::GitlabRetryJob {
+                project_id: 15,
+                job_id: 1977,
+            },
+        ];
+
+        for action in actions {
+            let data = action.to_callback_data();
+            assert!(data.len() <= 64);
+            assert_eq!(Action::parse(&data), Some(action));
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_data() {
+        assert_eq!(Action::parse("act:gh_merge:1:2"), None);
+        assert_eq!(Action::parse("act:gh_approve:1"), None);
+        assert_eq!(Action::parse("act:gh_approve:1:2:3"), None);
+        assert_eq!(Action::parse("rem:done:1"), None);
+    }
+}
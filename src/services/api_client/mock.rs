@@ -0,0 +1,111 @@
+//! Tiny HTTP server for exercising `ApiClient` callers in tests without network access.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    /// Path including the query string, e.g. `/repos/o/r/pulls?state=open`.
+    pub target: String,
+    pub headers: HashMap<String, String>,
+}
+
+type Routes = HashMap<(String, String), (u16, String)>;
+
+pub struct MockServer {
+    pub base_url: String,
+    routes: Arc<Mutex<Routes>>,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl MockServer {
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let routes: Arc<Mutex<Routes>> = Arc::default();
+        let requests: Arc<Mutex<Vec<RecordedRequest>>> = Arc::default();
+
+        let (thread_routes, thread_requests) = (routes.clone(), requests.clone());
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                serve(stream, &thread_routes, &thread_requests);
+            }
+        });
+
+        MockServer {
+            base_url,
+            routes,
+            requests,
+        }
+    }
+
+    /// Registers a canned response. `path` is matched without the query string.
+    pub fn respond(&self, method: &str, path: &str, status: u16, body: &str) {
+        self.routes.lock().unwrap().insert(
+            (method.to_string(), path.to_string()),
+            (status, body.to_string()),
+        );
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn serve(stream: TcpStream, routes: &Mutex<Routes>, requests: &Mutex<Vec<RecordedRequest>>) {
+    let mut reader = BufReader::new(stream.try_clone().expect("clone stream"));
+
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or_default().to_string();
+
+    let mut headers = HashMap::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).is_err() || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let content_length = headers
+        .get("content-length")
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; content_length];
+    let _ = reader.read_exact(&mut body);
+
+    let path = target.split('?').next().unwrap_or_default().to_string();
+    let (status, body) = routes
+        .lock()
+        .unwrap()
+        .get(&(method.clone(), path))
+        .cloned()
+        .unwrap_or((404, r#"{"message":"Not Found"}"#.to_string()));
+
+    requests.lock().unwrap().push(RecordedRequest {
+        method,
+        target,
+        headers,
+    });
+
+    let response = format!(
+        "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    let mut stream = stream;
+    let _ = stream.write_all(response.as_bytes());
+    let _ = stream.flush();
+}
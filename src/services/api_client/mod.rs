@@ -0,0 +1,146 @@
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde::de::DeserializeOwned;
+use std::env;
+use std::time::Duration;
+
+use crate::services::mentions::Provider;
+
+#[cfg(test)]
+pub mod mock;
+
+const DEFAULT_GITHUB_API_URL: &str = "https://api.github.com";
+const DEFAULT_GITLAB_API_URL: &str = "https://gitlab.com/api/v4";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Debug)]
+pub enum ApiError {
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    Status(StatusCode, String),
+    Request(reqwest::Error),
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiError::Unauthorized => write!(f, "the stored token is invalid or expired"),
+            ApiError::Forbidden => write!(f, "the stored token lacks permission for this"),
+            ApiError::NotFound => write!(f, "not found or not visible to the stored token"),
+            ApiError::Status(status, body) => write!(f, "API returned {}: {}", status, body),
+            ApiError::Request(e) => write!(f, "request failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl From<reqwest::Error> for ApiError {
+    fn from(err: reqwest::Error) -> Self {
+        ApiError::Request(err)
+    }
+}
+
+/// Minimal GitHub/GitLab REST client, authenticated with a chat's stored token when
+/// one is available. Public repositories can be queried without a token.
+pub struct ApiClient {
+    http: Client,
+    provider: Provider,
+    base_url: String,
+    token: Option<String>,
+}
+
+impl ApiClient {
+    /// Uses `GITHUB_API_URL` / `GITLAB_API_URL` when set, e.g. for GitHub Enterprise
+    /// or self-managed GitLab.
+    pub fn new(provider: Provider, token: Option<String>) -> Self {
+        let base_url = match provider {
+            Provider::Github => {
+                env::var("GITHUB_API_URL").unwrap_or_else(|_| DEFAULT_GITHUB_API_URL.into())
+            }
+            Provider::Gitlab => {
+                env::var("GITLAB_API_URL").unwrap_or_else(|_| DEFAULT_GITLAB_API_URL.into())
+            }
+        };
+
+        Self::with_base_url(provider, token, &base_url)
+    }
+
+    pub fn with_base_url(provider: Provider, token: Option<String>, base_url: &str) -> Self {
+        let http = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .user_agent("notifine")
+            .build()
+            .unwrap_or_else(|_| Client::new());
+
+        ApiClient {
+            http,
+            provider,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            token,
+        }
+    }
+
+    pub fn provider(&self) -> Provider {
+        self.provider
+    }
+
+    fn authorize(&self, request: RequestBuilder) -> RequestBuilder {
+        let request = match self.provider {
+            Provider::Github => request.header("Accept", "application/vnd.github+json"),
+            Provider::Gitlab => request,
+        };
+
+        match (&self.token, self.provider) {
+            (Some(token), Provider::Github) => request.bearer_auth(token),
+            (Some(token), Provider::Gitlab) => request.header("PRIVATE-TOKEN", token),
+            (None, _) => request,
+        }
+    }
+
+    async fn check_status(response: reqwest::Response) -> Result<reqwest::Response, ApiError> {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        match status {
+            StatusCode::UNAUTHORIZED => Err(ApiError::Unauthorized),
+            StatusCode::FORBIDDEN => Err(ApiError::Forbidden),
+            StatusCode::NOT_FOUND => Err(ApiError::NotFound),
+            _ => {
+                let body = response.text().await.unwrap_or_default();
+                let body: String = body.chars().take(200).collect();
+                Err(ApiError::Status(status, body))
+            }
+        }
+    }
+
+    pub async fn post(
+        &self,
+        path: &str,
+        body: Option<serde_json::Value>,
+    ) -> Result<reqwest::Response, ApiError> {
+        let mut request = self.authorize(self.http.post(format!("{}{}", self.base_url, path)));
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+
+        Self::check_status(request.send().await?).await
+    }
+
+    pub async fn get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T, ApiError> {
+        let request = self
+            .authorize(self.http.get(format!("{}{}", self.base_url, path)))
+            .query(query);
+
+        Ok(Self::check_status(request.send().await?)
+            .await?
+            .json()
+            .await?)
+    }
+}
@@ -0,0 +1,136 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use notifine::db::{DbError, DbPool};
+use notifine::schema::heartbeats;
+
+use super::types::{Heartbeat, HeartbeatInput};
+
+#[derive(Insertable)]
+#[diesel(table_name = heartbeats)]
+struct NewHeartbeat<'a> {
+    chat_id: i32,
+    name: &'a str,
+    token: &'a str,
+    period_secs: i32,
+    grace_secs: i32,
+}
+
+/// Creates the heartbeat, or changes the period and grace of an existing one with
+/// the same name. The ping URL of an existing heartbeat stays the same.
+pub fn upsert_heartbeat(
+    pool: &DbPool,
+    chat_id: i32,
+    input: &HeartbeatInput,
+) -> Result<Heartbeat, DbError> {
+    let conn = &mut pool.get()?;
+    let token = uuid::Uuid::new_v4().simple().to_string();
+    let period_secs = input.period.as_secs() as i32;
+    let grace_secs = input.grace.as_secs() as i32;
+
+    Ok(diesel::insert_into(heartbeats::table)
+        .values(&NewHeartbeat {
+            chat_id,
+            name: &input.name,
+            token: &token,
+            period_secs,
+            grace_secs,
+        })
+        .on_conflict((heartbeats::chat_id, heartbeats::name))
+        .do_update()
+        .set((
+            heartbeats::period_secs.eq(period_secs),
+            heartbeats::grace_secs.eq(grace_secs),
+            heartbeats::updated_at.eq(Utc::now()),
+        ))
+        .returning(Heartbeat::as_returning())
+        .get_result(conn)?)
+}
+
+pub fn get_heartbeats_by_chat_id(pool: &DbPool, chat_id: i32) -> Result<Vec<Heartbeat>, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(heartbeats::table
+        .filter(heartbeats::chat_id.eq(chat_id))
+        .order(heartbeats::name.asc())
+        .select(Heartbeat::as_select())
+        .load(conn)?)
+}
+
+pub fn delete_heartbeat(pool: &DbPool, chat_id: i32, name: &str) -> Result<usize, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(diesel::delete(
+        heartbeats::table
+            .filter(heartbeats::chat_id.eq(chat_id))
+            .filter(heartbeats::name.eq(name)),
+    )
+    .execute(conn)?)
+}
+
+pub fn find_heartbeat_by_token(pool: &DbPool, token: &str) -> Result<Option<Heartbeat>, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(heartbeats::table
+        .filter(heartbeats::token.eq(token))
+        .select(Heartbeat::as_select())
+        .first(conn)
+        .optional()?)
+}
+
+/// Heartbeats that haven't been reported late yet; the caller checks their deadlines.
+pub fn get_active_heartbeats(pool: &DbPool) -> Result<Vec<Heartbeat>, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(heartbeats::table
+        .filter(heartbeats::is_down.eq(false))
+        .select(Heartbeat::as_select())
+        .load(conn)?)
+}
+
+/// Records a finished run: a successful ping, or an explicit failure when `failed`.
+pub fn record_run(
+    pool: &DbPool,
+    id: i32,
+    at: DateTime<Utc>,
+    duration_ms: Option<i64>,
+    failed: bool,
+) -> Result<Heartbeat, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(diesel::update(heartbeats::table.find(id))
+        .set((
+            heartbeats::last_ping_at.eq(at),
+            heartbeats::last_start_at.eq(None::<DateTime<Utc>>),
+            heartbeats::last_duration_ms.eq(duration_ms),
+            heartbeats::is_down.eq(failed),
+            heartbeats::updated_at.eq(Utc::now()),
+        ))
+        .returning(Heartbeat::as_returning())
+        .get_result(conn)?)
+}
+
+pub fn record_start(pool: &DbPool, id: i32, at: DateTime<Utc>) -> Result<(), DbError> {
+    let conn = &mut pool.get()?;
+
+    diesel::update(heartbeats::table.find(id))
+        .set(heartbeats::last_start_at.eq(at))
+        .execute(conn)?;
+
+    Ok(())
+}
+
+/// Marks the heartbeat as late. Returns false when a ping arrived in the meantime.
+pub fn mark_heartbeat_late(pool: &DbPool, heartbeat: &Heartbeat) -> Result<bool, DbError> {
+    let conn = &mut pool.get()?;
+
+    let updated = diesel::update(
+        heartbeats::table
+            .find(heartbeat.id)
+            .filter(heartbeats::is_down.eq(false))
+            .filter(heartbeats::last_ping_at.is_not_distinct_from(heartbeat.last_ping_at)),
+    )
+    .set(heartbeats::is_down.eq(true))
+    .execute(conn)?;
+
+    Ok(updated > 0)
+}
@@ -0,0 +1,63 @@
+use actix_web::{route, web, HttpResponse, Responder};
+use notifine::db::DbPool;
+
+use super::{find_heartbeat_by_token, handle_signal, Signal};
+use crate::observability::METRICS;
+
+async fn receive(pool: &DbPool, token: &str, signal: Signal) -> HttpResponse {
+    let heartbeat = match find_heartbeat_by_token(pool, token) {
+        Ok(Some(heartbeat)) => heartbeat,
+        Ok(None) => return HttpResponse::NotFound().body("Unknown heartbeat"),
+        Err(e) => {
+            tracing::error!("Failed to find heartbeat: {:?}", e);
+            METRICS.increment_errors();
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    match handle_signal(pool, &heartbeat, signal).await {
+        Ok(()) => HttpResponse::Ok().body("OK"),
+        Err(e) => {
+            tracing::error!("Failed to record heartbeat {}: {:?}", heartbeat.id, e);
+            METRICS.increment_errors();
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+#[route("/heartbeat/{token}", method = "GET", method = "POST", method = "HEAD")]
+pub async fn handle_heartbeat_ping(
+    pool: web::Data<DbPool>,
+    token: web::Path<String>,
+) -> impl Responder {
+    receive(&pool, &token, Signal::Success).await
+}
+
+#[route(
+    "/heartbeat/{token}/start",
+    method = "GET",
+    method = "POST",
+    method = "HEAD"
+)]
+pub async fn handle_heartbeat_start(
+    pool: web::Data<DbPool>,
+    token: web::Path<String>,
+) -> impl Responder {
+    receive(&pool, &token, Signal::Start).await
+}
+
+/// The request body, e.g. the tail of the job's log, is included in the alert.
+#[route(
+    "/heartbeat/{token}/fail",
+    method = "GET",
+    method = "POST",
+    method = "HEAD"
+)]
+pub async fn handle_heartbeat_fail(
+    pool: web::Data<DbPool>,
+    token: web::Path<String>,
+    body: web::Bytes,
+) -> impl Responder {
+    let reason = String::from_utf8_lossy(&body).into_owned();
+    receive(&pool, &token, Signal::Fail(reason)).await
+}
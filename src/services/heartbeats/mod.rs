@@ -0,0 +1,189 @@
+pub mod db;
+pub mod http_server;
+pub mod types;
+
+use chrono::{DateTime, Utc};
+use html_escape::encode_text;
+use notifine::db::DbPool;
+use notifine::find_chat_by_id;
+use std::env;
+use std::time::Duration;
+use teloxide::prelude::*;
+
+use crate::observability::METRICS;
+use crate::services::uptime_notifications::send_uptime_notification;
+use crate::utils::duration::format_duration;
+pub use db::{
+    delete_heartbeat, find_heartbeat_by_token, get_heartbeats_by_chat_id, upsert_heartbeat,
+};
+use db::{get_active_heartbeats, mark_heartbeat_late, record_run, record_start};
+pub use types::{parse_heartbeat_command, ping_url, Heartbeat, HeartbeatCommand, HEARTBEAT_USAGE};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+/// Failure reasons sent to `/fail` are shown in the alert, up to this length.
+const MAX_REASON_CHARS: usize = 500;
+
+/// What a job reported by requesting its heartbeat URL.
+#[derive(Debug)]
+pub enum Signal {
+    Success,
+    Start,
+    Fail(String),
+}
+
+fn uptime_bot() -> Option<Bot> {
+    env::var("UPTIME_TELOXIDE_TOKEN").ok().map(Bot::new)
+}
+
+/// Sends an HTML message to the chat that owns the heartbeat, in its thread if it has one.
+async fn notify_chat(pool: &DbPool, bot: &Bot, heartbeat: &Heartbeat, message: String) {
+    let chat = match find_chat_by_id(pool, heartbeat.chat_id) {
+        Ok(Some(chat)) => chat,
+        Ok(None) => {
+            tracing::error!("Chat not found for heartbeat {}", heartbeat.id);
+            return;
+        }
+        Err(e) => {
+            tracing::error!(
+                "Failed to find chat for heartbeat {}: {:?}",
+                heartbeat.id,
+                e
+            );
+            METRICS.increment_errors();
+            return;
+        }
+    };
+
+    let Ok(telegram_chat_id) = chat.telegram_id.parse::<i64>() else {
+        tracing::error!("Invalid telegram_id '{}'", chat.telegram_id);
+        return;
+    };
+
+    let thread_id = chat.thread_id.and_then(|tid| tid.parse::<i32>().ok());
+    if let Err(e) = send_uptime_notification(pool, bot, telegram_chat_id, thread_id, &message).await
+    {
+        tracing::error!("Failed to send heartbeat message: {}", e);
+    }
+}
+
+fn format_run_duration(duration_ms: Option<i64>) -> String {
+    duration_ms
+        .map(|ms| {
+            format!(
+                " (run took {})",
+                format_duration(Duration::from_millis(ms.max(0) as u64))
+            )
+        })
+        .unwrap_or_default()
+}
+
+/// Applies a signal from the job and tells the chat about failures and recoveries.
+pub async fn handle_signal(
+    pool: &DbPool,
+    heartbeat: &Heartbeat,
+    signal: Signal,
+) -> Result<(), notifine::db::DbError> {
+    let now = Utc::now();
+    let duration_ms = heartbeat
+        .run_duration(now)
+        .map(|duration| duration.as_millis() as i64);
+
+    let message = match signal {
+        Signal::Start => {
+            record_start(pool, heartbeat.id, now)?;
+            None
+        }
+        Signal::Success => {
+            record_run(pool, heartbeat.id, now, duration_ms, false)?;
+            heartbeat.is_down.then(|| {
+                format!(
+                    "<b>[FIXED]</b> Heartbeat '{}' received again{}. Expecting the next one {}.",
+                    encode_text(&heartbeat.name),
+                    format_run_duration(duration_ms),
+                    heartbeat.describe()
+                )
+            })
+        }
+        Signal::Fail(reason) => {
+            record_run(pool, heartbeat.id, now, duration_ms, true)?;
+            let reason: String = reason.trim().chars().take(MAX_REASON_CHARS).collect();
+            let reason = if reason.is_empty() {
+                String::new()
+            } else {
+                format!("\n<b>Reason:</b> {}", encode_text(&reason))
+            };
+            Some(format!(
+                "<b>[ALARM]</b> Job '{}' reported a failure{}.{}",
+                encode_text(&heartbeat.name),
+                format_run_duration(duration_ms),
+                reason
+            ))
+        }
+    };
+
+    if let (Some(message), Some(bot)) = (message, uptime_bot()) {
+        notify_chat(pool, &bot, heartbeat, message).await;
+    }
+
+    Ok(())
+}
+
+fn format_late_message(heartbeat: &Heartbeat, now: DateTime<Utc>) -> String {
+    let last = match heartbeat.last_ping_at {
+        Some(at) => format!(
+            "the last one was {} ago",
+            format_duration((now - at).to_std().unwrap_or_default())
+        ),
+        None => "it has never pinged".to_string(),
+    };
+    let running = heartbeat
+        .run_duration(now)
+        .map(|duration| format!(" A run started {} ago.", format_duration(duration)))
+        .unwrap_or_default();
+
+    format!(
+        "<b>[ALARM]</b> Heartbeat '{}' is late: expected {}, {}.{} You will get a message when it pings again.",
+        encode_text(&heartbeat.name),
+        heartbeat.describe(),
+        last,
+        running
+    )
+}
+
+async fn check_overdue_heartbeats(pool: &DbPool, bot: &Bot) {
+    let heartbeats = match get_active_heartbeats(pool) {
+        Ok(heartbeats) => heartbeats,
+        Err(e) => {
+            tracing::error!("Failed to load heartbeats: {:?}", e);
+            METRICS.increment_errors();
+            return;
+        }
+    };
+    let now = Utc::now();
+
+    for heartbeat in heartbeats.iter().filter(|h| h.is_overdue(now)) {
+        match mark_heartbeat_late(pool, heartbeat) {
+            Ok(true) => {
+                notify_chat(pool, bot, heartbeat, format_late_message(heartbeat, now)).await
+            }
+            Ok(false) => {}
+            Err(e) => {
+                tracing::error!("Failed to mark heartbeat {} late: {:?}", heartbeat.id, e);
+                METRICS.increment_errors();
+            }
+        }
+    }
+}
+
+pub async fn run_heartbeat_checker(pool: DbPool) {
+    let Some(bot) = uptime_bot() else {
+        tracing::error!("UPTIME_TELOXIDE_TOKEN not set, heartbeat checker disabled");
+        return;
+    };
+
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    loop {
+        interval.tick().await;
+        check_overdue_heartbeats(&pool, &bot).await;
+    }
+}
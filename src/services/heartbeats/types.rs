@@ -0,0 +1,248 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+use crate::utils::duration::{format_duration, parse_duration};
+
+const PERIOD_RANGE: RangeInclusive<u64> = 60..=30 * 86400;
+const GRACE_RANGE: RangeInclusive<u64> = 0..=7 * 86400;
+const MAX_NAME_CHARS: usize = 100;
+
+pub const HEARTBEAT_USAGE: &str = "Usage: /heartbeat <name> <period> [grace]\n\n\
+    Creates a push monitor for a cron job. The job requests the returned URL when it \
+    succeeds, and the chat is alerted if no request arrives within the period plus grace \
+    (default: a tenth of the period, between 1m and 1h).\n\n\
+    /heartbeat - list heartbeats\n\
+    /heartbeat backup 25h - create or change a heartbeat\n\
+    /heartbeat backup 1d 2h - with 2 hours of grace\n\
+    /heartbeat delete backup - remove a heartbeat\n\n\
+    Append /start to the URL when the job starts to get run durations, or /fail to report \
+    a failure right away.";
+
+#[derive(Debug, Clone, Queryable, Selectable)]
+#[diesel(table_name = notifine::schema::heartbeats)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Heartbeat {
+    pub id: i32,
+    pub chat_id: i32,
+    pub name: String,
+    pub token: String,
+    pub period_secs: i32,
+    pub grace_secs: i32,
+    pub last_ping_at: Option<DateTime<Utc>>,
+    pub last_start_at: Option<DateTime<Utc>>,
+    pub last_duration_ms: Option<i64>,
+    pub is_down: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct HeartbeatInput {
+    pub name: String,
+    pub period: Duration,
+    pub grace: Duration,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum HeartbeatCommand {
+    List,
+    Upsert(HeartbeatInput),
+    Delete(String),
+}
+
+fn parse_secs(value: &str, range: RangeInclusive<u64>, name: &str) -> Result<Duration, String> {
+    let duration = parse_duration(value).ok_or_else(|| {
+        format!(
+            "Invalid {} '{}'. Use values like 30m, 25h or 7d.",
+            name, value
+        )
+    })?;
+
+    if !range.contains(&duration.as_secs()) {
+        return Err(format!(
+            "{} must be between {} and {}.",
+            name,
+            format_duration(Duration::from_secs(*range.start())),
+            format_duration(Duration::from_secs(*range.end()))
+        ));
+    }
+
+    Ok(duration)
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().count() <= MAX_NAME_CHARS
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// A tenth of the period, so daily jobs get more slack than ones running every few minutes.
+pub fn default_grace(period: Duration) -> Duration {
+    (period / 10).clamp(Duration::from_secs(60), Duration::from_secs(3600))
+}
+
+pub fn parse_heartbeat_command(args: &str) -> Result<HeartbeatCommand, String> {
+    let args: Vec<&str> = args.split_whitespace().collect();
+
+    match args.as_slice() {
+        [] => Ok(HeartbeatCommand::List),
+        ["delete", name] => Ok(HeartbeatCommand::Delete(name.to_string())),
+        [name, period, rest @ ..] if rest.len() <= 1 => {
+            if !is_valid_name(name) {
+                return Err(format!(
+                    "Invalid name '{}'. Use letters, digits, '-', '_' and '.'.",
+                    name
+                ));
+            }
+            let period = parse_secs(period, PERIOD_RANGE, "period")?;
+            let grace = match rest.first() {
+                Some(grace) => parse_secs(grace, GRACE_RANGE, "grace")?,
+                None => default_grace(period),
+            };
+
+            Ok(HeartbeatCommand::Upsert(HeartbeatInput {
+                name: name.to_string(),
+                period,
+                grace,
+            }))
+        }
+        _ => Err("Expected a name and a period.".to_string()),
+    }
+}
+
+pub fn ping_url(base_url: &str, token: &str) -> String {
+    format!("{}/heartbeat/{}", base_url.trim_end_matches('/'), token)
+}
+
+impl Heartbeat {
+    pub fn period(&self) -> Duration {
+        Duration::from_secs(self.period_secs.max(0) as u64)
+    }
+
+    pub fn grace(&self) -> Duration {
+        Duration::from_secs(self.grace_secs.max(0) as u64)
+    }
+
+    /// When the next ping is due at the latest. A heartbeat that never pinged counts
+    /// from its creation, so a job that never runs is reported too.
+    pub fn deadline(&self) -> DateTime<Utc> {
+        let since = self.last_ping_at.unwrap_or(self.created_at);
+        let allowed = self.period() + self.grace();
+
+        since + chrono::Duration::from_std(allowed).unwrap_or(chrono::Duration::MAX)
+    }
+
+    /// Whether the chat should be alerted that the job is late. Only fires once,
+    /// until the next ping recovers the heartbeat.
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        !self.is_down && now > self.deadline()
+    }
+
+    /// Duration of the current run, if the job signalled its start.
+    pub fn run_duration(&self, now: DateTime<Utc>) -> Option<Duration> {
+        self.last_start_at
+            .and_then(|started| (now - started).to_std().ok())
+    }
+
+    pub fn describe(&self) -> String {
+        format!(
+            "every {} (grace {})",
+            format_duration(self.period()),
+            format_duration(self.grace())
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn heartbeat(last_ping_at: Option<DateTime<Utc>>, is_down: bool) -> Heartbeat {
+        let created_at = Utc.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap();
+        Heartbeat {
+            id: 1,
+            chat_id: 1,
+            name: "backup".to_string(),
+            token: "abc".to_string(),
+            period_secs: 25 * 3600,
+            grace_secs: 3600,
+            last_ping_at,
+            last_start_at: None,
+            last_duration_ms: None,
+            is_down,
+            created_at,
+            updated_at: created_at,
+        }
+    }
+
+    #[test]
+    fn test_parse_heartbeat_command() {
+        assert_eq!(parse_heartbeat_command(""), Ok(HeartbeatCommand::List));
+        assert_eq!(
+            parse_heartbeat_command("delete backup"),
+            Ok(HeartbeatCommand::Delete("backup".to_string()))
+        );
+        assert_eq!(
+            parse_heartbeat_command("backup 25h"),
+            Ok(HeartbeatCommand::Upsert(HeartbeatInput {
+                name: "backup".to_string(),
+                period: Duration::from_secs(25 * 3600),
+                grace: Duration::from_secs(3600),
+            }))
+        );
+        assert_eq!(
+            parse_heartbeat_command("db-vacuum 5m 30s"),
+            Ok(HeartbeatCommand::Upsert(HeartbeatInput {
+                name: "db-vacuum".to_string(),
+                period: Duration::from_secs(300),
+                grace: Duration::from_secs(30),
+            }))
+        );
+        assert!(parse_heartbeat_command("backup").is_err());
+        assert!(parse_heartbeat_command("backup 10s").is_err());
+        assert!(parse_heartbeat_command("back/up 1h").is_err());
+        assert!(parse_heartbeat_command("backup 1h 1m extra").is_err());
+    }
+
+    #[test]
+    fn test_default_grace() {
+        assert_eq!(
+            default_grace(Duration::from_secs(300)),
+            Duration::from_secs(60)
+        );
+        assert_eq!(
+            default_grace(Duration::from_secs(5 * 3600)),
+            Duration::from_secs(1800)
+        );
+        assert_eq!(
+            default_grace(Duration::from_secs(86400)),
+            Duration::from_secs(3600)
+        );
+    }
+
+    #[test]
+    fn test_is_overdue() {
+        let pinged = Utc.with_ymd_and_hms(2026, 10, 10, 2, 0, 0).unwrap();
+        let hours = |h| pinged + chrono::Duration::hours(h);
+
+        assert!(!heartbeat(Some(pinged), false).is_overdue(hours(26)));
+        assert!(heartbeat(Some(pinged), false).is_overdue(hours(27)));
+        // Already alerted, wait for the next ping
+        assert!(!heartbeat(Some(pinged), true).is_overdue(hours(27)));
+        // Never pinged: counted from creation
+        assert!(heartbeat(None, false).is_overdue(hours(0)));
+    }
+
+    #[test]
+    fn test_ping_url() {
+        assert_eq!(
+            ping_url("https://webhook.notifine.com/", "abc"),
+            "https://webhook.notifine.com/heartbeat/abc"
+        );
+    }
+}
@@ -0,0 +1,424 @@
+use chrono::Utc;
+use teloxide::prelude::*;
+use teloxide::types::Message;
+
+use notifine::db::DbPool;
+
+use super::db::{
+    delete_link_verification, delete_user_links, delete_user_mapping, find_link_verification,
+    find_user_link, get_user_links_by_telegram_user_id, get_user_mappings_by_chat_id,
+    insert_user_link, upsert_link_verification, upsert_user_mapping,
+};
+use super::types::Provider;
+use super::verification::{generate_code, profile_contains_code, VERIFICATION_TTL};
+use crate::services::api_client::{ApiClient, ApiError};
+use crate::utils::chat_commands::{ensure_chat_admin, find_chat_id};
+use crate::utils::mentions::normalize_telegram_username;
+
+const MAP_USAGE: &str = "Usage: /map <provider>:<username> @telegram_username\n\n\
+    Example:\n\
+    /map github:alice @alice_tg\n\
+    /map gitlab:bob @bob_tg";
+
+const UNMAP_USAGE: &str = "Usage: /unmap <provider>:<username>\n\n\
+    Example:\n\
+    /unmap github:alice";
+
+const LINK_USAGE: &str = "Usage: /link <username>\n\n\
+    Links your account to this Telegram user so you get a direct message \
+    when you are requested as a reviewer, assigned or mentioned. To prove \
+    the account is yours, you will be asked to add a code to your profile bio.\n\n\
+    Example:\n\
+    /link alice";
+
+/// Returns the command arguments, dropping the `/command` or `/command@BotName` token.
+fn command_args(msg: &Message) -> Vec<&str> {
+    msg.text()
+        .unwrap_or("")
+        .split_whitespace()
+        .skip(1)
+        .collect()
+}
+
+fn parse_provider_username(input: &str) -> Option<(Provider, &str)> {
+    let (provider, username) = input.split_once(':')?;
+    let provider = Provider::parse(provider)?;
+    let username = username.trim_start_matches('@');
+
+    if username.is_empty() {
+        return None;
+    }
+
+    Some((provider, username))
+}
+
+pub async fn handle_map(bot: &Bot, msg: &Message, pool: &DbPool) -> ResponseResult<()> {
+    let args = command_args(msg);
+
+    let parsed = match args.as_slice() {
+        [account, telegram_username] => {
+            parse_provider_username(account).zip(normalize_telegram_username(telegram_username))
+        }
+        _ => None,
+    };
+
+    let Some(((provider, provider_username), telegram_username)) = parsed else {
+        bot.send_message(msg.chat.id, MAP_USAGE).await?;
+        return Ok(());
+    };
+
+    if !ensure_chat_admin(
+        bot,
+        msg,
+        "Only chat administrators can change user mappings.",
+    )
+    .await?
+    {
+        return Ok(());
+    }
+
+    let Some(chat_id) = find_chat_id(bot, msg, pool).await? else {
+        return Ok(());
+    };
+
+    match upsert_user_mapping(
+        pool,
+        chat_id,
+        provider,
+        provider_username,
+        &telegram_username,
+    ) {
+        Ok(mapping) => {
+            bot.send_message(
+                msg.chat.id,
+                format!(
+                    "✅ {}:{} will be mentioned as @{}",
+                    mapping.provider, mapping.provider_username, mapping.telegram_username
+                ),
+            )
+            .await?;
+        }
+        Err(e) => {
+            tracing::error!("Failed to save user mapping: {:?}", e);
+            bot.send_message(msg.chat.id, "Failed to save the mapping.")
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn handle_unmap(bot: &Bot, msg: &Message, pool: &DbPool) -> ResponseResult<()> {
+    let args = command_args(msg);
+
+    let parsed = match args.as_slice() {
+        [account] => parse_provider_username(account),
+        _ => None,
+    };
+
+    let Some((provider, provider_username)) = parsed else {
+        bot.send_message(msg.chat.id, UNMAP_USAGE).await?;
+        return Ok(());
+    };
+
+    if !ensure_chat_admin(
+        bot,
+        msg,
+        "Only chat administrators can change user mappings.",
+    )
+    .await?
+    {
+        return Ok(());
+    }
+
+    let Some(chat_id) = find_chat_id(bot, msg, pool).await? else {
+        return Ok(());
+    };
+
+    let reply = match delete_user_mapping(pool, chat_id, provider, provider_username) {
+        Ok(true) => format!(
+            "🗑 Removed mapping for {}:{}",
+            provider.as_str(),
+            provider_username
+        ),
+        Ok(false) => format!(
+            "No mapping found for {}:{}",
+            provider.as_str(),
+            provider_username
+        ),
+        Err(e) => {
+            tracing::error!("Failed to delete user mapping: {:?}", e);
+            "Failed to remove the mapping.".to_string()
+        }
+    };
+
+    bot.send_message(msg.chat.id, reply).await?;
+
+    Ok(())
+}
+
+pub async fn handle_mappings(bot: &Bot, msg: &Message, pool: &DbPool) -> ResponseResult<()> {
+    let Some(chat_id) = find_chat_id(bot, msg, pool).await? else {
+        return Ok(());
+    };
+
+    let reply = match get_user_mappings_by_chat_id(pool, chat_id) {
+        Ok(mappings) if mappings.is_empty() => {
+            format!("No user mappings yet.\n\n{MAP_USAGE}")
+        }
+        Ok(mappings) => {
+            let lines: Vec<String> = mappings
+                .iter()
+                .map(|m| {
+                    format!(
+                        "• {}:{} → @{}",
+                        m.provider, m.provider_username, m.telegram_username
+                    )
+                })
+                .collect();
+            format!("👥 User mappings:\n{}", lines.join("\n"))
+        }
+        Err(e) => {
+            tracing::error!("Failed to load user mappings: {:?}", e);
+            "Failed to load user mappings.".to_string()
+        }
+    };
+
+    bot.send_message(msg.chat.id, reply).await?;
+
+    Ok(())
+}
+
+/// Checks that personal links are managed in a private chat with a bot that supports them.
+async fn ensure_private_link_chat(
+    bot: &Bot,
+    msg: &Message,
+    provider: Option<Provider>,
+) -> ResponseResult<Option<Provider>> {
+    if !msg.chat.is_private() {
+        bot.send_message(
+            msg.chat.id,
+            "Please send this command in a private chat with the bot.",
+        )
+        .await?;
+        return Ok(None);
+    }
+
+    if provider.is_none() {
+        bot.send_message(
+            msg.chat.id,
+            "Personal notifications are only available for GitHub and GitLab.",
+        )
+        .await?;
+    }
+
+    Ok(provider)
+}
+
+pub async fn handle_link(
+    bot: &Bot,
+    msg: &Message,
+    pool: &DbPool,
+    provider: Option<Provider>,
+) -> ResponseResult<()> {
+    let Some(provider) = ensure_private_link_chat(bot, msg, provider).await? else {
+        return Ok(());
+    };
+    let Some(user) = msg.from() else {
+        return Ok(());
+    };
+    let telegram_user_id = user.id.0 as i64;
+
+    let args = command_args(msg);
+
+    let reply = match args.as_slice() {
+        [] => match get_user_links_by_telegram_user_id(pool, provider, telegram_user_id) {
+            Ok(links) if links.is_empty() => LINK_USAGE.to_string(),
+            Ok(links) => {
+                let usernames: Vec<&str> = links
+                    .iter()
+                    .map(|link| link.provider_username.as_str())
+                    .collect();
+                format!(
+                    "🔗 Linked {} accounts: {}\n\nUse /unlink to stop personal notifications.",
+                    provider.as_str(),
+                    usernames.join(", ")
+                )
+            }
+            Err(e) => {
+                tracing::error!("Failed to load user links: {:?}", e);
+                "Failed to load your linked accounts.".to_string()
+            }
+        },
+        [username] => {
+            link_account(
+                pool,
+                provider,
+                telegram_user_id,
+                username.trim_start_matches('@'),
+            )
+            .await
+        }
+        _ => LINK_USAGE.to_string(),
+    };
+
+    bot.send_message(msg.chat.id, reply).await?;
+
+    Ok(())
+}
+
+/// Links the account once the user proved they own it: the first `/link` hands out a
+/// code for their profile bio, and sending `/link` again checks that it is there.
+async fn link_account(
+    pool: &DbPool,
+    provider: Provider,
+    telegram_user_id: i64,
+    username: &str,
+) -> String {
+    match find_user_link(pool, provider, username) {
+        Ok(Some(link)) if link.telegram_user_id == telegram_user_id => {
+            return format!(
+                "{}:{} is already linked to you.",
+                link.provider, link.provider_username
+            );
+        }
+        Ok(Some(link)) => {
+            return format!(
+                "{}:{} is already linked to another Telegram user. It has to be unlinked \
+                 there with /unlink first.",
+                link.provider, link.provider_username
+            );
+        }
+        Ok(None) => {}
+        Err(e) => {
+            tracing::error!("Failed to load user link: {:?}", e);
+            return "Failed to link your account.".to_string();
+        }
+    }
+
+    let pending = match find_link_verification(pool, provider, telegram_user_id) {
+        Ok(pending) => pending.filter(|pending| {
+            pending.provider_username == username.to_lowercase()
+                && pending.created_at + VERIFICATION_TTL > Utc::now()
+        }),
+        Err(e) => {
+            tracing::error!("Failed to load link verification: {:?}", e);
+            return "Failed to link your account.".to_string();
+        }
+    };
+
+    let Some(pending) = pending else {
+        let code = generate_code();
+        return match upsert_link_verification(pool, provider, telegram_user_id, username, &code) {
+            Ok(_) => format!(
+                "To confirm that {} is your account, add {} to the bio of your public {} \
+                 profile, then send /link {} again within the next hour.",
+                username,
+                code,
+                provider.as_str(),
+                username
+            ),
+            Err(e) => {
+                tracing::error!("Failed to save link verification: {:?}", e);
+                "Failed to link your account.".to_string()
+            }
+        };
+    };
+
+    let client = ApiClient::new(provider, None);
+    match profile_contains_code(&client, username, &pending.code).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return format!(
+                "{} was not found in the bio of {}. Save it in your {} profile and send /link {} again.",
+                pending.code,
+                username,
+                provider.as_str(),
+                username
+            );
+        }
+        Err(ApiError::NotFound) => {
+            return format!(
+                "No {} account named {} was found.",
+                provider.as_str(),
+                username
+            );
+        }
+        Err(e) => {
+            tracing::warn!("Failed to load profile of {}: {}", username, e);
+            return "Failed to check your profile. Please try again later.".to_string();
+        }
+    }
+
+    if let Err(e) = delete_link_verification(pool, provider, telegram_user_id) {
+        tracing::warn!("Failed to delete link verification: {:?}", e);
+    }
+
+    match insert_user_link(pool, provider, username, telegram_user_id) {
+        Ok(Some(link)) => format!(
+            "✅ Linked {}:{}. You will get a direct message when you are requested \
+             as a reviewer, assigned or mentioned in chats you are a member of. \
+             You can remove the code from your bio now.",
+            link.provider, link.provider_username
+        ),
+        Ok(None) => format!(
+            "{}:{} is already linked to another Telegram user.",
+            provider.as_str(),
+            username
+        ),
+        Err(e) => {
+            tracing::error!("Failed to save user link: {:?}", e);
+            "Failed to link your account.".to_string()
+        }
+    }
+}
+
+pub async fn handle_unlink(
+    bot: &Bot,
+    msg: &Message,
+    pool: &DbPool,
+    provider: Option<Provider>,
+) -> ResponseResult<()> {
+    let Some(provider) = ensure_private_link_chat(bot, msg, provider).await? else {
+        return Ok(());
+    };
+    let Some(user) = msg.from() else {
+        return Ok(());
+    };
+
+    let reply = match delete_user_links(pool, provider, user.id.0 as i64) {
+        Ok(0) => "No linked accounts found.".to_string(),
+        Ok(_) => format!(
+            "🗑 Unlinked your {} accounts. You will no longer get personal notifications.",
+            provider.as_str()
+        ),
+        Err(e) => {
+            tracing::error!("Failed to delete user links: {:?}", e);
+            "Failed to unlink your account.".to_string()
+        }
+    };
+
+    bot.send_message(msg.chat.id, reply).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_provider_username() {
+        assert_eq!(
+            parse_provider_username("github:alice"),
+            Some((Provider::Github, "alice"))
+        );
+        assert_eq!(
+            parse_provider_username("GitLab:@bob"),
+            Some((Provider::Gitlab, "bob"))
+        );
+        assert_eq!(parse_provider_username("bitbucket:alice"), None);
+        assert_eq!(parse_provider_username("github:"), None);
+        assert_eq!(parse_provider_username("alice"), None);
+    }
+}
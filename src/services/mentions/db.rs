@@ -0,0 +1,274 @@
+use chrono::Utc;
+use diesel::prelude::*;
+use notifine::db::{DbError, DbPool};
+use notifine::schema::{user_link_verifications, user_links, user_mappings};
+
+use super::types::{Provider, UserLink, UserLinkVerification, UserMapping};
+
+#[derive(Insertable)]
+#[diesel(table_name = user_mappings)]
+struct NewUserMapping<'a> {
+    chat_id: i32,
+    provider: &'a str,
+    provider_username: &'a str,
+    telegram_username: &'a str,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = user_links)]
+struct NewUserLink<'a> {
+    provider: &'a str,
+    provider_username: &'a str,
+    telegram_user_id: i64,
+}
+
+pub fn upsert_user_mapping(
+    pool: &DbPool,
+    chat_id: i32,
+    provider: Provider,
+    provider_username: &str,
+    telegram_username: &str,
+) -> Result<UserMapping, DbError> {
+    let conn = &mut pool.get()?;
+    let provider_username = provider_username.to_lowercase();
+
+    Ok(diesel::insert_into(user_mappings::table)
+        .values(&NewUserMapping {
+            chat_id,
+            provider: provider.as_str(),
+            provider_username: &provider_username,
+            telegram_username,
+        })
+        .on_conflict((
+            user_mappings::chat_id,
+            user_mappings::provider,
+            user_mappings::provider_username,
+        ))
+        .do_update()
+        .set((
+            user_mappings::telegram_username.eq(telegram_username),
+            user_mappings::updated_at.eq(Utc::now()),
+        ))
+        .get_result(conn)?)
+}
+
+pub fn delete_user_mapping(
+    pool: &DbPool,
+    chat_id: i32,
+    provider: Provider,
+    provider_username: &str,
+) -> Result<bool, DbError> {
+    let conn = &mut pool.get()?;
+
+    let deleted = diesel::delete(
+        user_mappings::table
+            .filter(user_mappings::chat_id.eq(chat_id))
+            .filter(user_mappings::provider.eq(provider.as_str()))
+            .filter(user_mappings::provider_username.eq(provider_username.to_lowercase())),
+    )
+    .execute(conn)?;
+
+    Ok(deleted > 0)
+}
+
+pub fn get_user_mappings_by_chat_id(
+    pool: &DbPool,
+    chat_id: i32,
+) -> Result<Vec<UserMapping>, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(user_mappings::table
+        .filter(user_mappings::chat_id.eq(chat_id))
+        .order((
+            user_mappings::provider.asc(),
+            user_mappings::provider_username.asc(),
+        ))
+        .select(UserMapping::as_select())
+        .load(conn)?)
+}
+
+/// Resolves provider usernames to Telegram usernames, keeping the order of `provider_usernames`.
+/// Usernames without a mapping are skipped.
+pub fn find_telegram_usernames(
+    pool: &DbPool,
+    chat_id: i32,
+    provider: Provider,
+    provider_usernames: &[String],
+) -> Result<Vec<String>, DbError> {
+    if provider_usernames.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let conn = &mut pool.get()?;
+    let lowercased: Vec<String> = provider_usernames
+        .iter()
+        .map(|username| username.to_lowercase())
+        .collect();
+
+    let mappings: Vec<UserMapping> = user_mappings::table
+        .filter(user_mappings::chat_id.eq(chat_id))
+        .filter(user_mappings::provider.eq(provider.as_str()))
+        .filter(user_mappings::provider_username.eq_any(&lowercased))
+        .select(UserMapping::as_select())
+        .load(conn)?;
+
+    let mut telegram_usernames: Vec<String> = Vec::new();
+    for username in &lowercased {
+        if let Some(mapping) = mappings.iter().find(|m| &m.provider_username == username) {
+            if !telegram_usernames.contains(&mapping.telegram_username) {
+                telegram_usernames.push(mapping.telegram_username.clone());
+            }
+        }
+    }
+
+    Ok(telegram_usernames)
+}
+
+/// Links a provider username to a Telegram user. A username can only be linked to one
+/// Telegram user, so `None` is returned when it is already linked.
+pub fn insert_user_link(
+    pool: &DbPool,
+    provider: Provider,
+    provider_username: &str,
+    telegram_user_id: i64,
+) -> Result<Option<UserLink>, DbError> {
+    let conn = &mut pool.get()?;
+    let provider_username = provider_username.to_lowercase();
+
+    Ok(diesel::insert_into(user_links::table)
+        .values(&NewUserLink {
+            provider: provider.as_str(),
+            provider_username: &provider_username,
+            telegram_user_id,
+        })
+        .on_conflict((user_links::provider, user_links::provider_username))
+        .do_nothing()
+        .get_result(conn)
+        .optional()?)
+}
+
+pub fn find_user_link(
+    pool: &DbPool,
+    provider: Provider,
+    provider_username: &str,
+) -> Result<Option<UserLink>, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(user_links::table
+        .filter(user_links::provider.eq(provider.as_str()))
+        .filter(user_links::provider_username.eq(provider_username.to_lowercase()))
+        .select(UserLink::as_select())
+        .first(conn)
+        .optional()?)
+}
+
+/// Starts verifying a username for the Telegram user, replacing any earlier pending one.
+pub fn upsert_link_verification(
+    pool: &DbPool,
+    provider: Provider,
+    telegram_user_id: i64,
+    provider_username: &str,
+    code: &str,
+) -> Result<UserLinkVerification, DbError> {
+    let conn = &mut pool.get()?;
+    let provider_username = provider_username.to_lowercase();
+
+    Ok(diesel::insert_into(user_link_verifications::table)
+        .values((
+            user_link_verifications::provider.eq(provider.as_str()),
+            user_link_verifications::telegram_user_id.eq(telegram_user_id),
+            user_link_verifications::provider_username.eq(&provider_username),
+            user_link_verifications::code.eq(code),
+        ))
+        .on_conflict((
+            user_link_verifications::provider,
+            user_link_verifications::telegram_user_id,
+        ))
+        .do_update()
+        .set((
+            user_link_verifications::provider_username.eq(&provider_username),
+            user_link_verifications::code.eq(code),
+            user_link_verifications::created_at.eq(Utc::now()),
+        ))
+        .get_result(conn)?)
+}
+
+pub fn find_link_verification(
+    pool: &DbPool,
+    provider: Provider,
+    telegram_user_id: i64,
+) -> Result<Option<UserLinkVerification>, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(user_link_verifications::table
+        .find((provider.as_str(), telegram_user_id))
+        .select(UserLinkVerification::as_select())
+        .first(conn)
+        .optional()?)
+}
+
+pub fn delete_link_verification(
+    pool: &DbPool,
+    provider: Provider,
+    telegram_user_id: i64,
+) -> Result<(), DbError> {
+    let conn = &mut pool.get()?;
+
+    diesel::delete(user_link_verifications::table.find((provider.as_str(), telegram_user_id)))
+        .execute(conn)?;
+
+    Ok(())
+}
+
+pub fn delete_user_links(
+    pool: &DbPool,
+    provider: Provider,
+    telegram_user_id: i64,
+) -> Result<usize, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(diesel::delete(
+        user_links::table
+            .filter(user_links::provider.eq(provider.as_str()))
+            .filter(user_links::telegram_user_id.eq(telegram_user_id)),
+    )
+    .execute(conn)?)
+}
+
+pub fn get_user_links_by_telegram_user_id(
+    pool: &DbPool,
+    provider: Provider,
+    telegram_user_id: i64,
+) -> Result<Vec<UserLink>, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(user_links::table
+        .filter(user_links::provider.eq(provider.as_str()))
+        .filter(user_links::telegram_user_id.eq(telegram_user_id))
+        .order(user_links::provider_username.asc())
+        .select(UserLink::as_select())
+        .load(conn)?)
+}
+
+pub fn find_linked_telegram_user_ids(
+    pool: &DbPool,
+    provider: Provider,
+    provider_usernames: &[String],
+) -> Result<Vec<i64>, DbError> {
+    if provider_usernames.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let conn = &mut pool.get()?;
+    let lowercased: Vec<String> = provider_usernames
+        .iter()
+        .map(|username| username.to_lowercase())
+        .collect();
+
+    Ok(user_links::table
+        .filter(user_links::provider.eq(provider.as_str()))
+        .filter(user_links::provider_username.eq_any(&lowercased))
+        .select(user_links::telegram_user_id)
+        .distinct()
+        .load(conn)?)
+}
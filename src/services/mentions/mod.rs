@@ -0,0 +1,7 @@
+pub mod commands;
+pub mod db;
+pub mod types;
+pub mod verification;
+
+pub use commands::{handle_link, handle_map, handle_mappings, handle_unlink, handle_unmap};
+pub use types::Provider;
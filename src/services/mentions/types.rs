@@ -0,0 +1,62 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    Github,
+    Gitlab,
+}
+
+impl Provider {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Provider::Github => "github",
+            Provider::Gitlab => "gitlab",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Provider> {
+        match s.to_lowercase().as_str() {
+            "github" => Some(Provider::Github),
+            "gitlab" => Some(Provider::Gitlab),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = notifine::schema::user_mappings)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UserMapping {
+    pub id: i32,
+    pub chat_id: i32,
+    pub provider: String,
+    pub provider_username: String,
+    pub telegram_username: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = notifine::schema::user_links)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UserLink {
+    pub id: i32,
+    pub provider: String,
+    pub provider_username: String,
+    pub telegram_user_id: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A pending `/link` waiting for the user to prove they own the account.
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = notifine::schema::user_link_verifications)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UserLinkVerification {
+    pub provider: String,
+    pub telegram_user_id: i64,
+    pub provider_username: String,
+    pub code: String,
+    pub created_at: DateTime<Utc>,
+}
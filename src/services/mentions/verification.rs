@@ -0,0 +1,108 @@
+use chrono::Duration;
+use rand::distributions::Alphanumeric;
+use rand::{thread_rng, Rng};
+use serde::Deserialize;
+
+use super::types::Provider;
+use crate::services::api_client::{ApiClient, ApiError};
+
+/// A pending `/link` has to be confirmed within this long.
+pub const VERIFICATION_TTL: Duration = Duration::hours(1);
+
+#[derive(Deserialize)]
+struct Profile {
+    bio: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GitlabUser {
+    id: u64,
+}
+
+/// A code the user adds to their public profile bio to prove they own the account.
+pub fn generate_code() -> String {
+    let suffix: String = thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(10)
+        .map(|c| char::from(c).to_ascii_lowercase())
+        .collect();
+
+    format!("notifine-{}", suffix)
+}
+
+/// Checks the account's public profile bio for the verification code.
+pub async fn profile_contains_code(
+    client: &ApiClient,
+    username: &str,
+    code: &str,
+) -> Result<bool, ApiError> {
+    let profile: Profile = match client.provider() {
+        Provider::Github => {
+            client
+                .get(&format!("/users/{}", urlencoding::encode(username)), &[])
+                .await?
+        }
+        Provider::Gitlab => {
+            let users: Vec<GitlabUser> = client.get("/users", &[("username", username)]).await?;
+            let Some(user) = users.first() else {
+                return Err(ApiError::NotFound);
+            };
+            // The user search leaves out the bio
+            client.get(&format!("/users/{}", user.id), &[]).await?
+        }
+    };
+
+    Ok(profile.bio.is_some_and(|bio| bio.contains(code)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::api_client::mock::MockServer;
+
+    #[test]
+    fn test_generate_code() {
+        let code = generate_code();
+        assert!(code.starts_with("notifine-"));
+        assert_eq!(code.len(), "notifine-".len() + 10);
+        assert_ne!(code, generate_code());
+    }
+
+    #[tokio::test]
+    async fn test_profile_contains_code() {
+        let server = MockServer::start();
+        server.respond(
+            "GET",
+            "/users/alice",
+            200,
+            r#"{"login": "alice", "bio": "Rust dev notifine-abc123"}"#,
+        );
+        server.respond("GET", "/users/bob", 200, r#"{"login": "bob", "bio": null}"#);
+        let client = ApiClient::with_base_url(Provider::Github, None, &server.base_url);
+
+        assert!(profile_contains_code(&client, "alice", "notifine-abc123")
+            .await
+            .unwrap());
+        assert!(!profile_contains_code(&client, "bob", "notifine-abc123")
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_gitlab_profile_is_looked_up_by_id() {
+        let server = MockServer::start();
+        server.respond("GET", "/users", 200, r#"[{"id": 42, "username": "carol"}]"#);
+        server.respond(
+            "GET",
+            "/users/42",
+            200,
+            r#"{"id": 42, "bio": "notifine-xyz"}"#,
+        );
+        let client = ApiClient::with_base_url(Provider::Gitlab, None, &server.base_url);
+
+        assert!(profile_contains_code(&client, "carol", "notifine-xyz")
+            .await
+            .unwrap());
+        assert!(server.requests()[0].target.contains("username=carol"));
+    }
+}
@@ -1,5 +1,22 @@
+pub mod actions;
+pub mod api_client;
 pub mod broadcast;
+pub mod heartbeats;
+pub mod mentions;
+pub mod queries;
 pub mod reminder_scheduler;
 pub mod statistics_scheduler;
 pub mod stats;
+pub mod status_page;
+pub mod uptime_assertions;
 pub mod uptime_checker;
+pub mod uptime_history;
+pub mod uptime_incidents;
+pub mod uptime_latency;
+pub mod uptime_maintenance;
+pub mod uptime_notifications;
+pub mod uptime_probes;
+pub mod uptime_request;
+pub mod uptime_settings;
+pub mod uptime_state;
+pub mod uptime_tls;
@@ -0,0 +1,376 @@
+use html_escape::encode_text;
+use serde::Deserialize;
+
+use crate::services::api_client::{ApiClient, ApiError};
+use crate::services::mentions::Provider;
+
+const MAX_ITEMS: usize = 10;
+const PER_PAGE: &str = "10";
+
+#[derive(Deserialize)]
+struct Account {
+    #[serde(alias = "username")]
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct GithubPull {
+    number: u64,
+    title: String,
+    html_url: String,
+    user: Account,
+    #[serde(default)]
+    draft: bool,
+}
+
+#[derive(Deserialize)]
+struct GithubRuns {
+    workflow_runs: Vec<GithubRun>,
+}
+
+#[derive(Deserialize)]
+struct GithubRun {
+    name: Option<String>,
+    status: String,
+    conclusion: Option<String>,
+    head_branch: Option<String>,
+    html_url: String,
+}
+
+#[derive(Deserialize)]
+struct GithubIssue {
+    number: u64,
+    title: String,
+    html_url: String,
+    pull_request: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct GitlabMergeRequest {
+    iid: u64,
+    title: String,
+    web_url: String,
+    author: Account,
+    #[serde(default)]
+    draft: bool,
+}
+
+#[derive(Deserialize)]
+struct GitlabPipeline {
+    id: u64,
+    status: String,
+    #[serde(rename = "ref")]
+    ref_name: String,
+    web_url: String,
+}
+
+#[derive(Deserialize)]
+struct GitlabIssue {
+    iid: u64,
+    title: String,
+    web_url: String,
+}
+
+fn project_path(repository: &str) -> String {
+    urlencoding::encode(repository).into_owned()
+}
+
+fn status_emoji(status: &str) -> &'static str {
+    match status {
+        "success" => "✅",
+        "failure" | "failed" | "timed_out" | "startup_failure" => "❌",
+        "cancelled" | "canceled" | "skipped" => "⚪",
+        "in_progress" | "running" => "🔄",
+        "queued" | "pending" | "waiting" | "requested" | "created" => "⏳",
+        _ => "❔",
+    }
+}
+
+fn format_list(header: String, lines: Vec<String>, empty: &str) -> String {
+    if lines.is_empty() {
+        return format!("{}\n\n{}", header, empty);
+    }
+
+    format!("{}\n\n{}", header, lines.join("\n"))
+}
+
+fn format_item(prefix: &str, number: u64, title: &str, url: &str, suffix: &str) -> String {
+    format!(
+        "• <a href=\"{}\">{}{}</a> {}{}",
+        url,
+        prefix,
+        number,
+        encode_text(title),
+        suffix
+    )
+}
+
+/// Lists open pull requests (GitHub) or merge requests (GitLab).
+pub async fn open_pull_requests(client: &ApiClient, repository: &str) -> Result<String, ApiError> {
+    let repo = encode_text(repository);
+
+    let lines: Vec<String> = match client.provider() {
+        Provider::Github => {
+            let pulls: Vec<GithubPull> = client
+                .get(
+                    &format!("/repos/{}/pulls", repository),
+                    &[("state", "open"), ("per_page", PER_PAGE)],
+                )
+                .await?;
+
+            pulls
+                .iter()
+                .take(MAX_ITEMS)
+                .map(|pr| {
+                    let draft = if pr.draft { " (draft)" } else { "" };
+                    let suffix = format!(" by {}{}", encode_text(&pr.user.login), draft);
+                    format_item("#", pr.number, &pr.title, &pr.html_url, &suffix)
+                })
+                .collect()
+        }
+        Provider::Gitlab => {
+            let merge_requests: Vec<GitlabMergeRequest> = client
+                .get(
+                    &format!("/projects/{}/merge_requests", project_path(repository)),
+                    &[("state", "opened"), ("per_page", PER_PAGE)],
+                )
+                .await?;
+
+            merge_requests
+                .iter()
+                .take(MAX_ITEMS)
+                .map(|mr| {
+                    let draft = if mr.draft { " (draft)" } else { "" };
+                    let suffix = format!(" by {}{}", encode_text(&mr.author.login), draft);
+                    format_item("!", mr.iid, &mr.title, &mr.web_url, &suffix)
+                })
+                .collect()
+        }
+    };
+
+    Ok(format_list(
+        format!("🔀 <b>Open pull requests in {}</b>", repo),
+        lines,
+        "No open pull requests.",
+    ))
+}
+
+/// Shows the most recent workflow run (GitHub) or pipeline (GitLab), optionally for a branch.
+pub async fn latest_pipeline(
+    client: &ApiClient,
+    repository: &str,
+    branch: Option<&str>,
+) -> Result<String, ApiError> {
+    let repo = encode_text(repository);
+    let mut query = vec![("per_page", "1")];
+
+    let line = match client.provider() {
+        Provider::Github => {
+            if let Some(branch) = branch {
+                query.push(("branch", branch));
+            }
+            let runs: GithubRuns = client
+                .get(&format!("/repos/{}/actions/runs", repository), &query)
+                .await?;
+
+            runs.workflow_runs.first().map(|run| {
+                let state = run.conclusion.as_deref().unwrap_or(&run.status);
+                format!(
+                    "{} <a href=\"{}\">{}</a> on {}: {}",
+                    status_emoji(state),
+                    run.html_url,
+                    encode_text(run.name.as_deref().unwrap_or("Workflow")),
+                    encode_text(run.head_branch.as_deref().unwrap_or("unknown")),
+                    state
+                )
+            })
+        }
+        Provider::Gitlab => {
+            if let Some(branch) = branch {
+                query.push(("ref", branch));
+            }
+            let pipelines: Vec<GitlabPipeline> = client
+                .get(
+                    &format!("/projects/{}/pipelines", project_path(repository)),
+                    &query,
+                )
+                .await?;
+
+            pipelines.first().map(|pipeline| {
+                format!(
+                    "{} <a href=\"{}\">Pipeline #{}</a> on {}: {}",
+                    status_emoji(&pipeline.status),
+                    pipeline.web_url,
+                    pipeline.id,
+                    encode_text(&pipeline.ref_name),
+                    pipeline.status
+                )
+            })
+        }
+    };
+
+    let header = match branch {
+        Some(branch) => format!(
+            "🚦 <b>Latest pipeline in {} ({})</b>",
+            repo,
+            encode_text(branch)
+        ),
+        None => format!("🚦 <b>Latest pipeline in {}</b>", repo),
+    };
+
+    Ok(format_list(
+        header,
+        line.into_iter().collect(),
+        "No pipelines found.",
+    ))
+}
+
+/// Lists open issues, optionally restricted to a label.
+pub async fn open_issues(
+    client: &ApiClient,
+    repository: &str,
+    label: Option<&str>,
+) -> Result<String, ApiError> {
+    let repo = encode_text(repository);
+
+    let lines: Vec<String> = match client.provider() {
+        Provider::Github => {
+            let mut query = vec![("state", "open"), ("per_page", PER_PAGE)];
+            if let Some(label) = label {
+                query.push(("labels", label));
+            }
+            let issues: Vec<GithubIssue> = client
+                .get(&format!("/repos/{}/issues", repository), &query)
+                .await?;
+
+            // The issues endpoint also returns pull requests
+            issues
+                .iter()
+                .filter(|issue| issue.pull_request.is_none())
+                .take(MAX_ITEMS)
+                .map(|issue| format_item("#", issue.number, &issue.title, &issue.html_url, ""))
+                .collect()
+        }
+        Provider::Gitlab => {
+            let mut query = vec![("state", "opened"), ("per_page", PER_PAGE)];
+            if let Some(label) = label {
+                query.push(("labels", label));
+            }
+            let issues: Vec<GitlabIssue> = client
+                .get(
+                    &format!("/projects/{}/issues", project_path(repository)),
+                    &query,
+                )
+                .await?;
+
+            issues
+                .iter()
+                .take(MAX_ITEMS)
+                .map(|issue| format_item("#", issue.iid, &issue.title, &issue.web_url, ""))
+                .collect()
+        }
+    };
+
+    let header = match label {
+        Some(label) => format!(
+            "🐛 <b>Open issues in {} labeled {}</b>",
+            repo,
+            encode_text(label)
+        ),
+        None => format!("🐛 <b>Open issues in {}</b>", repo),
+    };
+
+    Ok(format_list(header, lines, "No open issues."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::api_client::mock::MockServer;
+
+    #[tokio::test]
+    async fn test_github_open_pull_requests() {
+        let server = MockServer::start();
+        server.respond(
+            "GET",
+            "/repos/acme/app/pulls",
+            200,
+            r#"[
+                {"number": 12, "title": "Add <cache>", "html_url": "https://github.com/acme/app/pull/12", "user": {"login": "alice"}, "draft": false},
+                {"number": 13, "title": "WIP", "html_url": "https://github.com/acme/app/pull/13", "user": {"login": "bob"}, "draft": true}
+            ]"#,
+        );
+        let client =
+            ApiClient::with_base_url(Provider::Github, Some("secret".into()), &server.base_url);
+
+        let text = open_pull_requests(&client, "acme/app").await.unwrap();
+
+        assert!(text.contains("Open pull requests in acme/app"));
+        assert!(text.contains(">#12</a> Add &lt;cache&gt; by alice"));
+        assert!(text.contains("#13</a> WIP by bob (draft)"));
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].target.contains("state=open"));
+        assert_eq!(
+            requests[0].headers.get("authorization").map(String::as_str),
+            Some("Bearer secret")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gitlab_latest_pipeline_for_branch() {
+        let server = MockServer::start();
+        server.respond(
+            "GET",
+            "/projects/group%2Fapp/pipelines",
+            200,
+            r#"[{"id": 77, "status": "failed", "ref": "main", "web_url": "https://gitlab.com/group/app/-/pipelines/77"}]"#,
+        );
+        let client = ApiClient::with_base_url(Provider::Gitlab, None, &server.base_url);
+
+        let text = latest_pipeline(&client, "group/app", Some("main"))
+            .await
+            .unwrap();
+
+        assert!(text.contains("Latest pipeline in group/app (main)"));
+        assert!(text.contains("❌"));
+        assert!(text.contains("Pipeline #77</a> on main: failed"));
+
+        let requests = server.requests();
+        assert!(requests[0].target.contains("ref=main"));
+        assert!(!requests[0].headers.contains_key("private-token"));
+    }
+
+    #[tokio::test]
+    async fn test_github_open_issues_skips_pull_requests() {
+        let server = MockServer::start();
+        server.respond(
+            "GET",
+            "/repos/acme/app/issues",
+            200,
+            r#"[
+                {"number": 1, "title": "Crash on start", "html_url": "https://github.com/acme/app/issues/1"},
+                {"number": 2, "title": "A PR", "html_url": "https://github.com/acme/app/pull/2", "pull_request": {}}
+            ]"#,
+        );
+        let client = ApiClient::with_base_url(Provider::Github, None, &server.base_url);
+
+        let text = open_issues(&client, "acme/app", Some("bug")).await.unwrap();
+
+        assert!(text.contains("labeled bug"));
+        assert!(text.contains("Crash on start"));
+        assert!(!text.contains("A PR"));
+        assert!(server.requests()[0].target.contains("labels=bug"));
+    }
+
+    #[tokio::test]
+    async fn test_not_found_is_reported() {
+        let server = MockServer::start();
+        let client = ApiClient::with_base_url(Provider::Gitlab, None, &server.base_url);
+
+        let result = open_issues(&client, "group/missing", None).await;
+
+        assert!(matches!(result, Err(ApiError::NotFound)));
+    }
+}
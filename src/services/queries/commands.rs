@@ -0,0 +1,303 @@
+use teloxide::prelude::*;
+use teloxide::types::{Message, ParseMode};
+
+use notifine::db::DbPool;
+
+use super::api::{latest_pipeline, open_issues, open_pull_requests};
+use super::db::{find_chat_repository, upsert_chat_repository};
+use crate::services::actions::db::find_chat_api_token;
+use crate::services::api_client::{ApiClient, ApiError};
+use crate::services::mentions::Provider;
+use crate::utils::chat_commands::{ensure_chat_admin, find_chat_id};
+use crate::utils::crypto::decrypt_secret;
+
+const REPO_USAGE: &str = "Usage: /repo <owner>/<name>\n\n\
+    Sets the repository that /prs, /pipeline and /issues query for this chat \
+    (admins only). \
+    Private repositories also need an API token, see /token.\n\n\
+    Example:\n\
+    /repo octocat/hello-world";
+
+const ISSUES_USAGE: &str = "Usage: /issues [label:<name>]\n\n\
+    Example:\n\
+    /issues label:bug";
+
+/// Everything a query command needs: the chat's default repository and an API client.
+struct QueryTarget {
+    repository: String,
+    client: ApiClient,
+}
+
+fn command_args(msg: &Message) -> Vec<&str> {
+    msg.text()
+        .unwrap_or("")
+        .split_whitespace()
+        .skip(1)
+        .collect()
+}
+
+/// GitHub repositories are exactly `owner/name`; GitLab projects may sit in nested
+/// groups. The path ends up in API URLs, so only plain path characters are allowed.
+fn is_valid_repository(provider: Provider, repository: &str) -> bool {
+    let parts: Vec<&str> = repository.split('/').collect();
+    let valid_part = |part: &&str| {
+        !part.is_empty()
+            && *part != "."
+            && *part != ".."
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    };
+
+    let expected_parts = match provider {
+        Provider::Github => parts.len() == 2,
+        Provider::Gitlab => parts.len() >= 2,
+    };
+    expected_parts && parts.iter().all(valid_part)
+}
+
+async fn reply(bot: &Bot, msg: &Message, text: String) -> ResponseResult<()> {
+    let mut request = bot
+        .send_message(msg.chat.id, text)
+        .parse_mode(ParseMode::Html)
+        .disable_web_page_preview(true);
+    if let Some(thread_id) = msg.thread_id {
+        request = request.message_thread_id(thread_id);
+    }
+    request.await?;
+
+    Ok(())
+}
+
+async fn ensure_provider(
+    bot: &Bot,
+    msg: &Message,
+    provider: Option<Provider>,
+) -> ResponseResult<Option<Provider>> {
+    if provider.is_none() {
+        bot.send_message(
+            msg.chat.id,
+            "Repository queries are only available for GitHub and GitLab.",
+        )
+        .await?;
+    }
+
+    Ok(provider)
+}
+
+/// The stored token is optional: public repositories can be queried without one.
+fn load_optional_token(pool: &DbPool, chat_id: i32, provider: Provider) -> Option<String> {
+    let encrypted = match find_chat_api_token(pool, chat_id, provider) {
+        Ok(encrypted) => encrypted?,
+        Err(e) => {
+            tracing::error!("Failed to load API token for chat {}: {:?}", chat_id, e);
+            return None;
+        }
+    };
+
+    match decrypt_secret(&encrypted) {
+        Ok(token) => Some(token),
+        Err(e) => {
+            tracing::warn!("Failed to decrypt API token for chat {}: {}", chat_id, e);
+            None
+        }
+    }
+}
+
+async fn load_target(
+    bot: &Bot,
+    msg: &Message,
+    pool: &DbPool,
+    provider: Option<Provider>,
+) -> ResponseResult<Option<QueryTarget>> {
+    let Some(provider) = ensure_provider(bot, msg, provider).await? else {
+        return Ok(None);
+    };
+    let Some(chat_id) = find_chat_id(bot, msg, pool).await? else {
+        return Ok(None);
+    };
+
+    let repository = match find_chat_repository(pool, chat_id, provider) {
+        Ok(Some(repository)) => repository,
+        Ok(None) => {
+            bot.send_message(
+                msg.chat.id,
+                "No repository is set for this chat. Set one with /repo <owner>/<name>.",
+            )
+            .await?;
+            return Ok(None);
+        }
+        Err(e) => {
+            tracing::error!("Failed to load chat repository: {:?}", e);
+            bot.send_message(msg.chat.id, "Failed to load chat settings.")
+                .await?;
+            return Ok(None);
+        }
+    };
+
+    let token = load_optional_token(pool, chat_id, provider);
+
+    Ok(Some(QueryTarget {
+        repository,
+        client: ApiClient::new(provider, token),
+    }))
+}
+
+async fn reply_with_result(
+    bot: &Bot,
+    msg: &Message,
+    result: Result<String, ApiError>,
+) -> ResponseResult<()> {
+    let text = match result {
+        Ok(text) => text,
+        Err(e) => {
+            tracing::warn!("Repository query failed in {}: {}", msg.chat.id, e);
+            format!(
+                "❌ Query failed: {}",
+                html_escape::encode_text(&e.to_string())
+            )
+        }
+    };
+
+    reply(bot, msg, text).await
+}
+
+pub async fn handle_repo(
+    bot: &Bot,
+    msg: &Message,
+    pool: &DbPool,
+    provider: Option<Provider>,
+) -> ResponseResult<()> {
+    let Some(provider) = ensure_provider(bot, msg, provider).await? else {
+        return Ok(());
+    };
+    let Some(chat_id) = find_chat_id(bot, msg, pool).await? else {
+        return Ok(());
+    };
+
+    let args = command_args(msg);
+
+    let text = match args.as_slice() {
+        [] => match find_chat_repository(pool, chat_id, provider) {
+            Ok(Some(repository)) => format!("📦 Repository for this chat: {}", repository),
+            Ok(None) => REPO_USAGE.to_string(),
+            Err(e) => {
+                tracing::error!("Failed to load chat repository: {:?}", e);
+                "Failed to load chat settings.".to_string()
+            }
+        },
+        [repository] if is_valid_repository(provider, repository) => {
+            if !ensure_chat_admin(
+                bot,
+                msg,
+                "Only chat administrators can change the repository.",
+            )
+            .await?
+            {
+                return Ok(());
+            }
+
+            match upsert_chat_repository(pool, chat_id, provider, repository) {
+                Ok(()) => format!(
+                    "✅ Repository set to {}. Try /prs, /pipeline or /issues.",
+                    repository
+                ),
+                Err(e) => {
+                    tracing::error!("Failed to save chat repository: {:?}", e);
+                    "Failed to save the repository.".to_string()
+                }
+            }
+        }
+        _ => REPO_USAGE.to_string(),
+    };
+
+    bot.send_message(msg.chat.id, text).await?;
+
+    Ok(())
+}
+
+pub async fn handle_prs(
+    bot: &Bot,
+    msg: &Message,
+    pool: &DbPool,
+    provider: Option<Provider>,
+) -> ResponseResult<()> {
+    let Some(target) = load_target(bot, msg, pool, provider).await? else {
+        return Ok(());
+    };
+
+    let result = open_pull_requests(&target.client, &target.repository).await;
+    reply_with_result(bot, msg, result).await
+}
+
+pub async fn handle_pipeline(
+    bot: &Bot,
+    msg: &Message,
+    pool: &DbPool,
+    provider: Option<Provider>,
+) -> ResponseResult<()> {
+    let Some(target) = load_target(bot, msg, pool, provider).await? else {
+        return Ok(());
+    };
+
+    let branch = command_args(msg).first().copied();
+    let result = latest_pipeline(&target.client, &target.repository, branch).await;
+    reply_with_result(bot, msg, result).await
+}
+
+pub async fn handle_issues(
+    bot: &Bot,
+    msg: &Message,
+    pool: &DbPool,
+    provider: Option<Provider>,
+) -> ResponseResult<()> {
+    let label = match command_args(msg).as_slice() {
+        [] => None,
+        [filter] => match filter.strip_prefix("label:") {
+            Some(label) if !label.is_empty() => Some(label.to_string()),
+            _ => {
+                bot.send_message(msg.chat.id, ISSUES_USAGE).await?;
+                return Ok(());
+            }
+        },
+        _ => {
+            bot.send_message(msg.chat.id, ISSUES_USAGE).await?;
+            return Ok(());
+        }
+    };
+
+    let Some(target) = load_target(bot, msg, pool, provider).await? else {
+        return Ok(());
+    };
+
+    let result = open_issues(&target.client, &target.repository, label.as_deref()).await;
+    reply_with_result(bot, msg, result).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_repository() {
+        assert!(is_valid_repository(Provider::Github, "octocat/hello-world"));
+        assert!(is_valid_repository(Provider::Github, "octocat/hello.rs"));
+        assert!(!is_valid_repository(
+            Provider::Github,
+            "group/subgroup/project"
+        ));
+        assert!(!is_valid_repository(Provider::Github, "octocat/../pulls"));
+        assert!(!is_valid_repository(
+            Provider::Github,
+            "octocat/app?per_page=1"
+        ));
+        assert!(!is_valid_repository(Provider::Github, "hello-world"));
+        assert!(!is_valid_repository(Provider::Github, "octocat/"));
+        assert!(!is_valid_repository(Provider::Github, "/hello-world"));
+        assert!(is_valid_repository(
+            Provider::Gitlab,
+            "group/subgroup/project"
+        ));
+        assert!(!is_valid_repository(Provider::Gitlab, "group/../project"));
+    }
+}
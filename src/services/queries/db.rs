@@ -0,0 +1,55 @@
+use chrono::Utc;
+use diesel::prelude::*;
+use notifine::db::{DbError, DbPool};
+use notifine::schema::chat_repositories;
+
+use crate::services::mentions::Provider;
+
+#[derive(Insertable)]
+#[diesel(table_name = chat_repositories)]
+struct NewChatRepository<'a> {
+    chat_id: i32,
+    provider: &'a str,
+    repository: &'a str,
+}
+
+pub fn upsert_chat_repository(
+    pool: &DbPool,
+    chat_id: i32,
+    provider: Provider,
+    repository: &str,
+) -> Result<(), DbError> {
+    let conn = &mut pool.get()?;
+
+    diesel::insert_into(chat_repositories::table)
+        .values(&NewChatRepository {
+            chat_id,
+            provider: provider.as_str(),
+            repository,
+        })
+        .on_conflict((chat_repositories::chat_id, chat_repositories::provider))
+        .do_update()
+        .set((
+            chat_repositories::repository.eq(repository),
+            chat_repositories::updated_at.eq(Utc::now()),
+        ))
+        .execute(conn)?;
+
+    Ok(())
+}
+
+/// Returns the default repository queried by `/prs`, `/pipeline` and `/issues`.
+pub fn find_chat_repository(
+    pool: &DbPool,
+    chat_id: i32,
+    provider: Provider,
+) -> Result<Option<String>, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(chat_repositories::table
+        .filter(chat_repositories::chat_id.eq(chat_id))
+        .filter(chat_repositories::provider.eq(provider.as_str()))
+        .select(chat_repositories::repository)
+        .first::<String>(conn)
+        .optional()?)
+}
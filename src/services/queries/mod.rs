@@ -0,0 +1,5 @@
+pub mod api;
+pub mod commands;
+pub mod db;
+
+pub use commands::{handle_issues, handle_pipeline, handle_prs, handle_repo};
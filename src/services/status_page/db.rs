@@ -0,0 +1,87 @@
+use chrono::Utc;
+use diesel::prelude::*;
+use notifine::db::{DbError, DbPool};
+use notifine::schema::status_pages;
+
+use super::types::StatusPage;
+
+const DEFAULT_TITLE: &str = "Service Status";
+
+#[derive(Insertable)]
+#[diesel(table_name = status_pages)]
+struct NewStatusPage<'a> {
+    chat_id: i32,
+    slug: &'a str,
+    title: &'a str,
+}
+
+/// Publishes the chat's status page at `slug`, or moves an existing page there.
+pub fn upsert_status_page(pool: &DbPool, chat_id: i32, slug: &str) -> Result<StatusPage, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(diesel::insert_into(status_pages::table)
+        .values(&NewStatusPage {
+            chat_id,
+            slug,
+            title: DEFAULT_TITLE,
+        })
+        .on_conflict(status_pages::chat_id)
+        .do_update()
+        .set((
+            status_pages::slug.eq(slug),
+            status_pages::updated_at.eq(Utc::now()),
+        ))
+        .returning(StatusPage::as_returning())
+        .get_result(conn)?)
+}
+
+pub fn get_status_page_by_chat_id(
+    pool: &DbPool,
+    chat_id: i32,
+) -> Result<Option<StatusPage>, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(status_pages::table
+        .filter(status_pages::chat_id.eq(chat_id))
+        .select(StatusPage::as_select())
+        .first(conn)
+        .optional()?)
+}
+
+pub fn get_status_page_by_slug(pool: &DbPool, slug: &str) -> Result<Option<StatusPage>, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(status_pages::table
+        .filter(status_pages::slug.eq(slug))
+        .select(StatusPage::as_select())
+        .first(conn)
+        .optional()?)
+}
+
+pub fn update_status_page_title(
+    pool: &DbPool,
+    chat_id: i32,
+    title: &str,
+) -> Result<Option<StatusPage>, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(
+        diesel::update(status_pages::table.filter(status_pages::chat_id.eq(chat_id)))
+            .set((
+                status_pages::title.eq(title),
+                status_pages::updated_at.eq(Utc::now()),
+            ))
+            .returning(StatusPage::as_returning())
+            .get_result(conn)
+            .optional()?,
+    )
+}
+
+pub fn delete_status_page(pool: &DbPool, chat_id: i32) -> Result<usize, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(
+        diesel::delete(status_pages::table.filter(status_pages::chat_id.eq(chat_id)))
+            .execute(conn)?,
+    )
+}
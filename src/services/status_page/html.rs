@@ -0,0 +1,160 @@
+use html_escape::{encode_double_quoted_attribute, encode_text};
+use std::fmt::Write;
+
+use super::types::{DayBar, MonitorState, MonitorStatus, StatusPageIncident, StatusPageView};
+use crate::utils::duration::format_duration;
+
+const STYLE: &str = "body{font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',sans-serif;\
+    max-width:860px;margin:0 auto;padding:24px;color:#1f2328;background:#f6f8fa}\
+    h1{margin-bottom:8px}.banner{padding:16px;border-radius:8px;color:#fff;font-weight:600}\
+    .up{background:#1a7f37}.down{background:#cf222e}.maintenance{background:#0969da}\
+    .monitor{background:#fff;border:1px solid #d0d7de;border-radius:8px;padding:16px;margin:16px 0}\
+    .row{display:flex;justify-content:space-between;margin-bottom:8px}\
+    .bars{display:flex;gap:2px;height:32px}.bar{flex:1;border-radius:2px;background:#d0d7de}\
+    .bar.good{background:#2da44e}.bar.degraded{background:#d4a72c}.bar.bad{background:#cf222e}\
+    .muted{color:#656d76;font-size:14px}li{margin-bottom:8px}";
+
+fn bar_class(day: &DayBar) -> &'static str {
+    match day.uptime {
+        None => "bar",
+        Some(uptime) if uptime >= 99.9 => "bar good",
+        Some(uptime) if uptime >= 95.0 => "bar degraded",
+        Some(_) => "bar bad",
+    }
+}
+
+fn state_class(state: MonitorState) -> &'static str {
+    match state {
+        MonitorState::Up => "up",
+        MonitorState::Down => "down",
+        MonitorState::Maintenance => "maintenance",
+    }
+}
+
+fn render_monitor(html: &mut String, monitor: &MonitorStatus) {
+    let uptime = monitor
+        .uptime
+        .map(|uptime| format!("{:.2}% uptime", uptime))
+        .unwrap_or_else(|| "no data yet".to_string());
+
+    let _ = write!(
+        html,
+        "<div class=\"monitor\"><div class=\"row\"><strong>{}</strong><span>{}</span></div><div class=\"bars\">",
+        encode_text(&monitor.name),
+        monitor.state.label()
+    );
+    for day in &monitor.days {
+        let title = match day.uptime {
+            Some(uptime) => format!("{}: {:.2}%", day.date, uptime),
+            None => format!("{}: no data", day.date),
+        };
+        let _ = write!(
+            html,
+            "<div class=\"{}\" title=\"{}\"></div>",
+            bar_class(day),
+            encode_double_quoted_attribute(&title)
+        );
+    }
+    let _ = write!(
+        html,
+        "</div><div class=\"row muted\"><span>90 days ago</span><span>{}</span><span>Today</span></div></div>",
+        uptime
+    );
+}
+
+fn render_incident(html: &mut String, entry: &StatusPageIncident) {
+    let incident = &entry.incident;
+    let started = incident.started_at.format("%Y-%m-%d %H:%M UTC");
+    let status = match incident.resolved_at {
+        Some(resolved_at) => format!(
+            "resolved after {}",
+            format_duration(
+                (resolved_at - incident.started_at)
+                    .to_std()
+                    .unwrap_or_default()
+            )
+        ),
+        None => "ongoing".to_string(),
+    };
+
+    let _ = write!(
+        html,
+        "<li><strong>{}</strong> was down <span class=\"muted\">{}, {}</span></li>",
+        encode_text(&entry.monitor),
+        started,
+        status
+    );
+}
+
+pub fn render_status_page(view: &StatusPageView) -> String {
+    let has_state = |state| view.monitors.iter().any(|monitor| monitor.state == state);
+    let (banner_state, banner) = if has_state(MonitorState::Down) {
+        (MonitorState::Down, "Some systems are down")
+    } else if has_state(MonitorState::Maintenance) {
+        (MonitorState::Maintenance, "Maintenance in progress")
+    } else {
+        (MonitorState::Up, "All systems operational")
+    };
+    let banner_class = state_class(banner_state);
+
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html><html lang=\"en\"><head><meta charset=\"utf-8\">\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+         <meta http-equiv=\"refresh\" content=\"60\"><title>{title}</title><style>{STYLE}</style></head>\
+         <body><h1>{title}</h1><div class=\"banner {banner_class}\">{banner}</div>",
+        title = encode_text(&view.title),
+    );
+
+    for monitor in &view.monitors {
+        render_monitor(&mut html, monitor);
+    }
+
+    html.push_str("<h2>Recent incidents</h2>");
+    if view.incidents.is_empty() {
+        html.push_str("<p class=\"muted\">No incidents in the last 7 days.</p>");
+    } else {
+        html.push_str("<ul>");
+        for incident in &view.incidents {
+            render_incident(&mut html, incident);
+        }
+        html.push_str("</ul>");
+    }
+
+    let _ = write!(
+        html,
+        "<p class=\"muted\">Updated {} · Powered by Notifine</p></body></html>",
+        view.generated_at.format("%Y-%m-%d %H:%M UTC")
+    );
+
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_render_status_page_escapes_user_input() {
+        let view = StatusPageView {
+            title: "<script>alert(1)</script>".to_string(),
+            generated_at: Utc.with_ymd_and_hms(2026, 10, 22, 12, 0, 0).unwrap(),
+            monitors: vec![MonitorStatus {
+                name: "example.com/<b>".to_string(),
+                state: MonitorState::Down,
+                uptime: Some(99.5),
+                days: vec![],
+            }],
+            incidents: vec![],
+        };
+
+        let html = render_status_page(&view);
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(html.contains("example.com/&lt;b&gt;"));
+        assert!(html.contains("Some systems are down"));
+        assert!(html.contains("99.50% uptime"));
+    }
+}
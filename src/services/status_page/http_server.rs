@@ -0,0 +1,161 @@
+use actix_web::{get, http::header, web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use notifine::db::DbPool;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::html::render_status_page;
+use super::types::{StatusPage, StatusPageView};
+use super::{build_status_page, get_status_page_by_slug};
+use crate::observability::METRICS;
+
+/// Public pages are rebuilt at most once per `CACHE_TTL`, however often they are
+/// requested. Matches the `max-age` the responses are sent with.
+const CACHE_TTL: Duration = Duration::from_secs(60);
+
+struct CachedPage {
+    built_at: Instant,
+    /// Changing the page, e.g. its title, shows up right away instead of after the TTL.
+    page_updated_at: DateTime<Utc>,
+    view: Arc<StatusPageView>,
+}
+
+/// Built pages by slug.
+#[derive(Default)]
+struct PageCache {
+    pages: HashMap<String, CachedPage>,
+}
+
+impl PageCache {
+    fn get(&self, page: &StatusPage, now: Instant) -> Option<Arc<StatusPageView>> {
+        self.pages
+            .get(&page.slug)
+            .filter(|cached| {
+                cached.page_updated_at == page.updated_at
+                    && now.duration_since(cached.built_at) < CACHE_TTL
+            })
+            .map(|cached| cached.view.clone())
+    }
+
+    fn insert(&mut self, page: &StatusPage, view: Arc<StatusPageView>, now: Instant) {
+        self.pages
+            .retain(|_, cached| now.duration_since(cached.built_at) < CACHE_TTL);
+        self.pages.insert(
+            page.slug.clone(),
+            CachedPage {
+                built_at: now,
+                page_updated_at: page.updated_at,
+                view,
+            },
+        );
+    }
+}
+
+lazy_static! {
+    static ref PAGE_CACHE: Mutex<PageCache> = Mutex::new(PageCache::default());
+}
+
+fn load(pool: &DbPool, slug: &str) -> Result<Arc<StatusPageView>, HttpResponse> {
+    let page = match get_status_page_by_slug(pool, slug) {
+        Ok(Some(page)) => page,
+        Ok(None) => return Err(HttpResponse::NotFound().body("Unknown status page")),
+        Err(e) => {
+            tracing::error!("Failed to find status page: {:?}", e);
+            METRICS.increment_errors();
+            return Err(HttpResponse::InternalServerError().finish());
+        }
+    };
+
+    let now = Instant::now();
+    if let Some(view) = PAGE_CACHE.lock().unwrap().get(&page, now) {
+        return Ok(view);
+    }
+
+    let view = build_status_page(pool, &page, Utc::now()).map_err(|e| {
+        tracing::error!("Failed to build status page {}: {:?}", page.id, e);
+        METRICS.increment_errors();
+        HttpResponse::InternalServerError().finish()
+    })?;
+    let view = Arc::new(view);
+    PAGE_CACHE.lock().unwrap().insert(&page, view.clone(), now);
+
+    Ok(view)
+}
+
+/// The same data as the page, for embedding the status in other sites.
+#[get("/status/{slug}.json")]
+pub async fn handle_status_page_json(
+    pool: web::Data<DbPool>,
+    slug: web::Path<String>,
+) -> impl Responder {
+    match load(&pool, &slug) {
+        Ok(view) => HttpResponse::Ok()
+            .insert_header((header::ACCESS_CONTROL_ALLOW_ORIGIN, "*"))
+            .insert_header((header::CACHE_CONTROL, "public, max-age=60"))
+            .json(&*view),
+        Err(response) => response,
+    }
+}
+
+#[get("/status/{slug}")]
+pub async fn handle_status_page(
+    pool: web::Data<DbPool>,
+    slug: web::Path<String>,
+) -> impl Responder {
+    match load(&pool, &slug) {
+        Ok(view) => HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .insert_header((header::CACHE_CONTROL, "public, max-age=60"))
+            .body(render_status_page(&view)),
+        Err(response) => response,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(slug: &str, updated_at: DateTime<Utc>) -> StatusPage {
+        StatusPage {
+            id: 1,
+            chat_id: 1,
+            slug: slug.to_string(),
+            title: "Acme".to_string(),
+            created_at: updated_at,
+            updated_at,
+        }
+    }
+
+    fn view() -> Arc<StatusPageView> {
+        Arc::new(StatusPageView {
+            title: "Acme".to_string(),
+            generated_at: Utc::now(),
+            monitors: Vec::new(),
+            incidents: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn test_page_cache() {
+        let mut cache = PageCache::default();
+        let now = Instant::now();
+        let updated_at = Utc::now();
+        let acme = page("acme", updated_at);
+
+        assert!(cache.get(&acme, now).is_none());
+        cache.insert(&acme, view(), now);
+        assert!(cache.get(&acme, now + Duration::from_secs(30)).is_some());
+        assert!(cache.get(&acme, now + CACHE_TTL).is_none());
+        assert!(cache.get(&page("other", updated_at), now).is_none());
+
+        // A changed page is rebuilt before the TTL runs out
+        let renamed = page("acme", updated_at + chrono::Duration::seconds(1));
+        assert!(cache.get(&renamed, now).is_none());
+
+        // Expired pages are dropped when another one is stored
+        cache.insert(&page("other", updated_at), view(), now + CACHE_TTL);
+        assert_eq!(cache.pages.len(), 1);
+    }
+}
@@ -0,0 +1,104 @@
+pub mod db;
+pub mod html;
+pub mod http_server;
+pub mod types;
+
+use chrono::{DateTime, Duration, Utc};
+use notifine::db::{DbError, DbPool};
+use notifine::get_health_urls_by_chat_id;
+use std::collections::HashMap;
+
+use crate::services::uptime_history::db::{get_check_samples_since, get_rollups_since};
+use crate::services::uptime_history::WindowStats;
+use crate::services::uptime_incidents::get_incidents_by_health_url_ids_since;
+use crate::services::uptime_maintenance::{
+    get_maintenance_windows_by_health_url_id, is_in_maintenance,
+};
+pub use db::{
+    delete_status_page, get_status_page_by_chat_id, get_status_page_by_slug,
+    update_status_page_title, upsert_status_page,
+};
+use types::{
+    build_day_bars, overall_uptime, MonitorState, MonitorStatus, StatusPageIncident,
+    StatusPageView, STATUS_PAGE_DAYS,
+};
+pub use types::{
+    parse_status_page_command, public_name, status_page_url, StatusPage, StatusPageCommand,
+    STATUS_PAGE_USAGE,
+};
+
+const INCIDENT_DAYS: i64 = 7;
+const MAX_INCIDENTS: i64 = 10;
+
+/// Collects everything shown on a status page from the monitors' check history and incidents.
+pub fn build_status_page(
+    pool: &DbPool,
+    page: &StatusPage,
+    now: DateTime<Utc>,
+) -> Result<StatusPageView, DbError> {
+    let today = now.date_naive();
+    let today_start = today.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+
+    let mut health_urls: Vec<_> = get_health_urls_by_chat_id(pool, page.chat_id as i64)?
+        .into_iter()
+        .filter(|health_url| health_url.show_on_status_page)
+        .collect();
+    health_urls.sort_by_key(|health_url| health_url.id);
+
+    let mut monitors = Vec::new();
+    let mut names = HashMap::new();
+    for health_url in &health_urls {
+        let name = health_url
+            .label
+            .clone()
+            .unwrap_or_else(|| public_name(&health_url.url));
+
+        let windows = get_maintenance_windows_by_health_url_id(pool, health_url.id)?;
+        let state = if is_in_maintenance(health_url, &windows, now) {
+            MonitorState::Maintenance
+        } else if health_url.is_down {
+            MonitorState::Down
+        } else {
+            MonitorState::Up
+        };
+
+        let rollups = get_rollups_since(
+            pool,
+            health_url.id,
+            today - Duration::days(STATUS_PAGE_DAYS),
+        )?;
+        let today_samples = get_check_samples_since(pool, health_url.id, today_start)?;
+        let days = build_day_bars(today, &rollups, &WindowStats::from_samples(&today_samples));
+
+        names.insert(health_url.id, name.clone());
+        monitors.push(MonitorStatus {
+            name,
+            state,
+            uptime: overall_uptime(&days),
+            days,
+        });
+    }
+
+    let health_url_ids: Vec<i32> = health_urls.iter().map(|health_url| health_url.id).collect();
+    let incidents = get_incidents_by_health_url_ids_since(
+        pool,
+        &health_url_ids,
+        now - Duration::days(INCIDENT_DAYS),
+        MAX_INCIDENTS,
+    )?
+    .into_iter()
+    .filter_map(|incident| {
+        Some(StatusPageIncident {
+            monitor: names.get(&incident.health_url_id)?.clone(),
+            incident,
+        })
+    })
+    .collect();
+
+    Ok(StatusPageView {
+        title: page.title.clone(),
+        generated_at: now,
+        monitors,
+        incidents,
+    })
+}
@@ -0,0 +1,57 @@
+use diesel::prelude::*;
+use notifine::db::{DbError, DbPool};
+use notifine::schema::health_url_assertions;
+
+use super::types::{Assertion, AssertionInput};
+
+#[derive(Insertable)]
+#[diesel(table_name = health_url_assertions)]
+struct NewAssertion<'a> {
+    health_url_id: i32,
+    kind: &'a str,
+    target: &'a str,
+    expected: Option<&'a str>,
+}
+
+pub fn create_assertion(
+    pool: &DbPool,
+    health_url_id: i32,
+    input: &AssertionInput,
+) -> Result<Assertion, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(diesel::insert_into(health_url_assertions::table)
+        .values(&NewAssertion {
+            health_url_id,
+            kind: input.kind.as_str(),
+            target: &input.target,
+            expected: input.expected.as_deref(),
+        })
+        .returning(Assertion::as_returning())
+        .get_result(conn)?)
+}
+
+pub fn get_assertions_by_health_url_id(
+    pool: &DbPool,
+    health_url_id: i32,
+) -> Result<Vec<Assertion>, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(health_url_assertions::table
+        .filter(health_url_assertions::health_url_id.eq(health_url_id))
+        .order(health_url_assertions::id.asc())
+        .select(Assertion::as_select())
+        .load(conn)?)
+}
+
+pub fn delete_assertions_by_health_url_id(
+    pool: &DbPool,
+    health_url_id: i32,
+) -> Result<usize, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(diesel::delete(
+        health_url_assertions::table.filter(health_url_assertions::health_url_id.eq(health_url_id)),
+    )
+    .execute(conn)?)
+}
@@ -0,0 +1,21 @@
+pub mod db;
+pub mod types;
+
+pub use db::{
+    create_assertion, delete_assertions_by_health_url_id, get_assertions_by_health_url_id,
+};
+pub use types::{evaluate_assertions, parse_assertion, Assertion};
+
+/// Upper bound so a monitor can't turn every check into a long list of scans.
+pub const MAX_ASSERTIONS_PER_MONITOR: usize = 5;
+
+pub const ASSERT_USAGE: &str = "Usage: /assert <id> <type> <value>\n\n\
+    Types:\n\
+    contains <text> - the response body must contain the text\n\
+    notcontains <text> - the response body must not contain the text\n\
+    regex <pattern> - the response body must match the regular expression\n\
+    json <path> <value> - the JSON value at the path must equal the value\n\n\
+    Examples:\n\
+    /assert 12 contains All systems operational\n\
+    /assert 12 json $.db.status up\n\n\
+    /assert <id> lists the assertions, /assert <id> clear removes them.";
@@ -0,0 +1,316 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use regex::Regex;
+use serde_json::Value;
+
+const MAX_SHOWN_CHARS: usize = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssertionKind {
+    Contains,
+    NotContains,
+    Regex,
+    JsonPath,
+}
+
+impl AssertionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AssertionKind::Contains => "contains",
+            AssertionKind::NotContains => "notcontains",
+            AssertionKind::Regex => "regex",
+            AssertionKind::JsonPath => "json",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<AssertionKind> {
+        match s.to_lowercase().as_str() {
+            "contains" => Some(AssertionKind::Contains),
+            "notcontains" | "not_contains" => Some(AssertionKind::NotContains),
+            "regex" => Some(AssertionKind::Regex),
+            "json" => Some(AssertionKind::JsonPath),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Queryable, Selectable)]
+#[diesel(table_name = notifine::schema::health_url_assertions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Assertion {
+    pub id: i32,
+    pub health_url_id: i32,
+    pub kind: String,
+    pub target: String,
+    pub expected: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A validated assertion as entered with `/assert`, before it is stored.
+#[derive(Debug, PartialEq)]
+pub struct AssertionInput {
+    pub kind: AssertionKind,
+    pub target: String,
+    pub expected: Option<String>,
+}
+
+fn shorten(text: &str) -> String {
+    if text.chars().count() <= MAX_SHOWN_CHARS {
+        return text.to_string();
+    }
+
+    let short: String = text.chars().take(MAX_SHOWN_CHARS).collect();
+    format!("{}…", short)
+}
+
+/// Parses the arguments after the monitor id, e.g. `contains "status":"ok"`,
+/// `regex ^OK$` or `json $.db.status up`.
+pub fn parse_assertion(args: &str) -> Result<AssertionInput, String> {
+    let args = args.trim();
+    let (kind, rest) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+    let kind =
+        AssertionKind::parse(kind).ok_or_else(|| format!("Unknown assertion type '{}'.", kind))?;
+    let rest = rest.trim();
+
+    if rest.is_empty() {
+        return Err("The assertion needs a value.".to_string());
+    }
+
+    match kind {
+        AssertionKind::Contains | AssertionKind::NotContains => Ok(AssertionInput {
+            kind,
+            target: rest.to_string(),
+            expected: None,
+        }),
+        AssertionKind::Regex => {
+            Regex::new(rest).map_err(|e| format!("Invalid regex: {}", e))?;
+            Ok(AssertionInput {
+                kind,
+                target: rest.to_string(),
+                expected: None,
+            })
+        }
+        AssertionKind::JsonPath => {
+            let (path, expected) = rest
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| "Usage: json <path> <expected value>".to_string())?;
+            parse_json_path(path)?;
+            Ok(AssertionInput {
+                kind,
+                target: path.to_string(),
+                expected: Some(expected.trim().to_string()),
+            })
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// Parses simple JSON paths such as `$.data.items[0].state` or `status`.
+fn parse_json_path(path: &str) -> Result<Vec<PathSegment>, String> {
+    let invalid = || format!("Invalid JSON path '{}'. Use paths like $.data.status", path);
+    let path = path.strip_prefix('$').unwrap_or(path);
+    let path = path.strip_prefix('.').unwrap_or(path);
+    let mut segments = Vec::new();
+
+    for part in path.split('.').filter(|part| !part.is_empty()) {
+        let (key, indexes) = part.split_once('[').unwrap_or((part, ""));
+        if !key.is_empty() {
+            segments.push(PathSegment::Key(key.to_string()));
+        }
+
+        if !indexes.is_empty() {
+            for index in format!("[{}", indexes).split('[').skip(1) {
+                let index = index.strip_suffix(']').ok_or_else(invalid)?;
+                segments.push(PathSegment::Index(index.parse().map_err(|_| invalid())?));
+            }
+        }
+    }
+
+    if segments.is_empty() {
+        return Err(invalid());
+    }
+
+    Ok(segments)
+}
+
+fn lookup<'a>(value: &'a Value, segments: &[PathSegment]) -> Option<&'a Value> {
+    segments
+        .iter()
+        .try_fold(value, |current, segment| match segment {
+            PathSegment::Key(key) => current.get(key),
+            PathSegment::Index(index) => current.get(index),
+        })
+}
+
+fn render_json_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+impl Assertion {
+    pub fn kind(&self) -> Option<AssertionKind> {
+        AssertionKind::parse(&self.kind)
+    }
+
+    pub fn describe(&self) -> String {
+        match (self.kind(), &self.expected) {
+            (Some(AssertionKind::JsonPath), Some(expected)) => {
+                format!("json {} = {}", self.target, shorten(expected))
+            }
+            _ => format!("{} {}", self.kind, shorten(&self.target)),
+        }
+    }
+
+    /// Checks the response body, returning a human readable reason on failure.
+    pub fn evaluate(&self, body: &str) -> Result<(), String> {
+        match self.kind() {
+            Some(AssertionKind::Contains) => {
+                if body.contains(&self.target) {
+                    Ok(())
+                } else {
+                    Err(format!("body does not contain '{}'", shorten(&self.target)))
+                }
+            }
+            Some(AssertionKind::NotContains) => {
+                if body.contains(&self.target) {
+                    Err(format!("body contains '{}'", shorten(&self.target)))
+                } else {
+                    Ok(())
+                }
+            }
+            Some(AssertionKind::Regex) => {
+                let regex =
+                    Regex::new(&self.target).map_err(|e| format!("invalid regex: {}", e))?;
+                if regex.is_match(body) {
+                    Ok(())
+                } else {
+                    Err(format!("body does not match /{}/", shorten(&self.target)))
+                }
+            }
+            Some(AssertionKind::JsonPath) => {
+                let expected = self.expected.as_deref().unwrap_or_default();
+                let segments = parse_json_path(&self.target)?;
+                let json: Value = serde_json::from_str(body)
+                    .map_err(|_| "response body is not valid JSON".to_string())?;
+
+                match lookup(&json, &segments) {
+                    Some(value) if render_json_value(value) == expected => Ok(()),
+                    // The actual value stays out of alerts, it may be sensitive
+                    Some(_) => Err(format!(
+                        "{} does not equal {}",
+                        self.target,
+                        shorten(expected)
+                    )),
+                    None => Err(format!("{} not found in response", self.target)),
+                }
+            }
+            None => Err(format!("unknown assertion type '{}'", self.kind)),
+        }
+    }
+}
+
+/// Runs all assertions and returns the first failure.
+pub fn evaluate_assertions(assertions: &[Assertion], body: &str) -> Result<(), String> {
+    assertions
+        .iter()
+        .try_for_each(|assertion| assertion.evaluate(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assertion(input: &str) -> Assertion {
+        let parsed = parse_assertion(input).unwrap();
+        Assertion {
+            id: 1,
+            health_url_id: 1,
+            kind: parsed.kind.as_str().to_string(),
+            target: parsed.target,
+            expected: parsed.expected,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_parse_assertion() {
+        assert_eq!(
+            parse_assertion("json $.db.status up").unwrap(),
+            AssertionInput {
+                kind: AssertionKind::JsonPath,
+                target: "$.db.status".to_string(),
+                expected: Some("up".to_string()),
+            }
+        );
+        assert_eq!(
+            parse_assertion("contains All systems go").unwrap().target,
+            "All systems go"
+        );
+        assert!(parse_assertion("regex (unclosed").is_err());
+        assert!(parse_assertion("json $.status").is_err());
+        assert!(parse_assertion("contains").is_err());
+        assert!(parse_assertion("equals ok").is_err());
+    }
+
+    #[test]
+    fn test_keyword_assertions() {
+        let body = "<html>Database connection failed</html>";
+
+        assert!(assertion("contains failed").evaluate(body).is_ok());
+        assert_eq!(
+            assertion("notcontains connection failed").evaluate(body),
+            Err("body contains 'connection failed'".to_string())
+        );
+        assert!(assertion("contains healthy").evaluate(body).is_err());
+    }
+
+    #[test]
+    fn test_regex_assertion() {
+        assert!(assertion(r"regex version \d+\.\d+")
+            .evaluate("version 1.2")
+            .is_ok());
+        assert!(assertion(r"regex ^OK$").evaluate("NOT OK").is_err());
+    }
+
+    #[test]
+    fn test_json_path_assertion() {
+        let body = r#"{"status": "ok", "checks": [{"name": "db", "healthy": true}], "count": 3}"#;
+
+        assert!(assertion("json $.status ok").evaluate(body).is_ok());
+        assert!(assertion("json checks[0].healthy true")
+            .evaluate(body)
+            .is_ok());
+        assert!(assertion("json $.count 3").evaluate(body).is_ok());
+        assert_eq!(
+            assertion("json $.checks[0].name cache").evaluate(body),
+            Err("$.checks[0].name does not equal cache".to_string())
+        );
+        assert_eq!(
+            assertion("json $.missing x").evaluate(body),
+            Err("$.missing not found in response".to_string())
+        );
+        assert_eq!(
+            assertion("json $.status ok").evaluate("<html>"),
+            Err("response body is not valid JSON".to_string())
+        );
+    }
+
+    #[test]
+    fn test_evaluate_assertions_reports_first_failure() {
+        let assertions = vec![assertion("contains ok"), assertion("notcontains error")];
+
+        assert!(evaluate_assertions(&assertions, "ok").is_ok());
+        assert_eq!(
+            evaluate_assertions(&assertions, "ok, error"),
+            Err("body contains 'error'".to_string())
+        );
+        assert!(evaluate_assertions(&[], "anything").is_ok());
+    }
+}
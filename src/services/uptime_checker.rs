@@ -1,25 +1,70 @@
 use crate::observability::alerts::Severity;
 use crate::observability::{ALERTS, METRICS};
+use crate::services::uptime_assertions::{
+    evaluate_assertions, get_assertions_by_health_url_id, Assertion,
+};
+use crate::services::uptime_history::db::{
+    get_check_samples_since, get_failure_streak_start, get_latency_baseline,
+};
+use crate::services::uptime_history::{record_health_check, NewHealthCheck, WindowStats};
+use crate::services::uptime_incidents::{open_incident, resolve_incident, Incident};
+use crate::services::uptime_latency::{
+    baseline_since, format_degraded_message, format_latency_recovered_message, next_latency_state,
+    LatencyThreshold, LatencyTransition, MIN_BASELINE_CHECKS,
+};
+use crate::services::uptime_maintenance::{
+    format_maintenance_summary, get_maintenance_windows_by_health_url_id, is_in_maintenance,
+    MaintenanceWindow,
+};
+use crate::services::uptime_notifications::send_uptime_notification;
+use crate::services::uptime_probes::{
+    check_dns, check_public_url, check_tcp, public_redirect_policy, MonitorKind, PublicResolver,
+};
+use crate::services::uptime_request::{has_plaintext_secrets, MonitorRequest};
+use crate::services::uptime_state::{next_check_state, Transition, FLAP_STABLE_AFTER};
+use crate::services::uptime_tls::{
+    format_certificate_warning, next_tls_state, parse_certificate, CertificateInfo,
+};
+use crate::utils::duration::format_duration;
+use chrono::{DateTime, Utc};
+use html_escape::encode_text;
 use notifine::db::DbPool;
-use notifine::models::HealthUrl;
-use notifine::{find_chat_by_chat_id, get_all_health_urls, update_health_url_status};
+use notifine::models::{HealthUrl, HealthUrlCheckState, HealthUrlLatencyState};
+use notifine::{
+    find_chat_by_chat_id, get_all_health_urls, get_health_url_by_id, update_health_url_check_state,
+    update_health_url_latency_state, update_health_url_maintenance_started,
+    update_health_url_request, update_health_url_tls_state,
+};
+use reqwest::tls::TlsInfo;
 use reqwest::Client;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::env;
 use std::fmt;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use teloxide::prelude::*;
-use tokio::sync::{Mutex, Semaphore};
+use tokio::sync::{mpsc, Mutex, Semaphore};
 use tokio::time::timeout;
+use url::Url;
 
+/// How many check attempts run at once.
 const BATCH_SIZE: usize = 10;
-const TIMEOUT_DURATION: Duration = Duration::from_secs(10);
+/// Timeout for checks outside of a monitor's own settings, e.g. when adding an endpoint.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
 const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
 const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
 const SLOW_REQUEST_THRESHOLD: Duration = Duration::from_secs(5);
 const MASS_TIMEOUT_THRESHOLD_PERCENT: f64 = 50.0;
-const MAX_RETRIES: u32 = 2;
+/// Response bodies are only read for assertions, and never beyond this size.
+const MAX_BODY_BYTES: usize = 1024 * 1024;
 const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+/// How often the schedule picks up added, removed and reconfigured monitors.
+const SCHEDULE_SYNC_INTERVAL: Duration = Duration::from_secs(30);
+/// How often check statistics are logged and checked for mass timeouts.
+const STATS_REPORT_INTERVAL: Duration = Duration::from_secs(60);
+/// First checks after startup are spread over this window to avoid a burst.
+const INITIAL_SPREAD: Duration = Duration::from_secs(60);
 
 #[derive(Debug)]
 pub enum HealthCheckError {
@@ -55,12 +100,25 @@ impl From<reqwest::Error> for HealthCheckError {
 /// Categorizes why a health check failed for debugging purposes.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum FailureReason {
-    /// Request completed but returned non-2xx status
+    /// Request completed but returned a status the monitor doesn't accept (default: non-2xx)
     HttpError,
-    /// Request timed out (exceeded TIMEOUT_DURATION)
+    /// Request timed out (exceeded the monitor's timeout)
     Timeout,
     /// Request failed (connection, SSL, redirect, etc.)
     RequestError,
+    /// Request returned an accepted status but the body failed one of the monitor's assertions
+    AssertionFailed,
+}
+
+impl FailureReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FailureReason::HttpError => "http_error",
+            FailureReason::Timeout => "timeout",
+            FailureReason::RequestError => "request_error",
+            FailureReason::AssertionFailed => "assertion_failed",
+        }
+    }
 }
 
 pub struct HealthResult {
@@ -69,6 +127,8 @@ pub struct HealthResult {
     pub duration: Duration,
     pub failure_reason: Option<FailureReason>,
     pub error_message: Option<String>,
+    /// Peer certificate of HTTPS endpoints, when the request got a response.
+    pub certificate: Option<CertificateInfo>,
 }
 
 fn format_failure_reason(result: &HealthResult) -> String {
@@ -78,14 +138,18 @@ fn format_failure_reason(result: &HealthResult) -> String {
 
     match result.failure_reason {
         Some(FailureReason::Timeout) => {
-            format!("Timeout (no response in {}s)", TIMEOUT_DURATION.as_secs())
+            format!(
+                "Timeout (no response in {}s)",
+                result.duration.as_secs_f64().round()
+            )
         }
         Some(FailureReason::HttpError) => format!("HTTP Error (status {})", result.status_code),
+        Some(FailureReason::AssertionFailed) => "Assertion failed".to_string(),
         _ => format!("Unknown (status {})", result.status_code),
     }
 }
 
-/// Statistics collected during a single check cycle for debugging.
+/// Statistics collected during a reporting window for debugging.
 #[derive(Debug, Default)]
 struct CycleStats {
     total: u32,
@@ -93,6 +157,7 @@ struct CycleStats {
     timeout: u32,
     http_error: u32,
     request_error: u32,
+    assertion_failed: u32,
     slow_requests: u32,
     max_duration_ms: u64,
 }
@@ -117,6 +182,7 @@ impl CycleStats {
                 Some(FailureReason::Timeout) => self.timeout += 1,
                 Some(FailureReason::HttpError) => self.http_error += 1,
                 Some(FailureReason::RequestError) => self.request_error += 1,
+                Some(FailureReason::AssertionFailed) => self.assertion_failed += 1,
                 None => {}
             }
         }
@@ -135,97 +201,179 @@ impl CycleStats {
     }
 }
 
-async fn check_health_urls(
-    pool: &DbPool,
-    client: &Client,
-) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let cycle_start = std::time::Instant::now();
+/// Min-heap of next-due times, so each monitor is checked on its own interval
+/// instead of sweeping every monitor at once.
+#[derive(Default)]
+struct Schedule {
+    queue: BinaryHeap<Reverse<(Instant, i32)>>,
+    intervals: HashMap<i32, Duration>,
+    in_flight: HashSet<i32>,
+}
 
-    let token = match env::var("UPTIME_TELOXIDE_TOKEN") {
-        Ok(token) => token,
-        Err(_) => {
-            tracing::error!("UPTIME_TELOXIDE_TOKEN not set, skipping health check");
-            return Ok(());
+impl Schedule {
+    /// Queues newly seen monitors and forgets removed ones. Interval changes apply
+    /// from the next check on.
+    fn sync(&mut self, monitors: impl IntoIterator<Item = (i32, Duration)>, now: Instant) {
+        let mut intervals = HashMap::new();
+
+        for (id, interval) in monitors {
+            if !self.intervals.contains_key(&id) {
+                let spread = INITIAL_SPREAD.min(interval).as_millis().max(1) as u64;
+                let offset = Duration::from_millis((id as u64).wrapping_mul(7919) % spread);
+                self.queue.push(Reverse((now + offset, id)));
+            }
+            intervals.insert(id, interval);
         }
-    };
-    let bot = Bot::new(token);
 
-    let health_urls = match get_all_health_urls(pool) {
-        Ok(urls) => urls,
+        self.intervals = intervals;
+    }
+
+    /// Removes and returns the monitors that are due. Removed monitors and monitors
+    /// whose previous check is still running are skipped.
+    fn pop_due(&mut self, now: Instant) -> Vec<i32> {
+        let mut due = Vec::new();
+
+        while let Some(Reverse((at, id))) = self.queue.peek().copied() {
+            if at > now {
+                break;
+            }
+            self.queue.pop();
+
+            if self.intervals.contains_key(&id) && self.in_flight.insert(id) {
+                due.push(id);
+            }
+        }
+
+        due
+    }
+
+    /// Schedules the next check one interval after the previous one started.
+    fn complete(&mut self, id: i32, started: Instant, now: Instant) {
+        self.in_flight.remove(&id);
+
+        if let Some(interval) = self.intervals.get(&id) {
+            self.queue
+                .push(Reverse(((started + *interval).max(now), id)));
+        }
+    }
+
+    fn next_due(&self) -> Option<Instant> {
+        self.queue.peek().map(|Reverse((at, _))| *at)
+    }
+}
+
+fn check_interval(health_url: &HealthUrl) -> Duration {
+    Duration::from_secs(health_url.check_interval_secs.max(1) as u64)
+}
+
+async fn sync_schedule(pool: &DbPool, bot: &Bot, schedule: &mut Schedule) {
+    match get_all_health_urls(pool) {
+        Ok(urls) => schedule.sync(
+            urls.iter().map(|url| (url.id, check_interval(url))),
+            Instant::now(),
+        ),
         Err(e) => {
             tracing::error!("Failed to get health URLs: {:?}", e);
             METRICS.increment_errors();
             ALERTS
                 .send_alert(
-                    &bot,
+                    bot,
                     Severity::Error,
                     "Database",
                     &format!("Failed to get health URLs: {}", e),
                 )
                 .await;
-            return Ok(());
+        }
+    }
+}
+
+async fn run_scheduled_check(
+    pool: &DbPool,
+    client: &Client,
+    bot: &Bot,
+    semaphore: &Semaphore,
+    health_url_id: i32,
+    stats: &Mutex<CycleStats>,
+) {
+    // Load the latest row so state and settings changes since the last sync are respected
+    let health_url = match get_health_url_by_id(pool, health_url_id) {
+        Ok(Some(health_url)) => health_url,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::error!("Failed to load health URL {}: {:?}", health_url_id, e);
+            METRICS.increment_errors();
+            return;
         }
     };
 
-    let url_count = health_urls.len();
-    let semaphore = Arc::new(Semaphore::new(BATCH_SIZE));
-    let stats = Arc::new(Mutex::new(CycleStats::default()));
-    let mut handles = Vec::with_capacity(url_count);
-
-    for health_url in health_urls {
-        let permit = semaphore.clone().acquire_owned().await.unwrap();
-        let client = client.clone();
-        let bot = bot.clone();
-        let pool = pool.clone();
-        let stats = stats.clone();
-
-        let handle = tokio::spawn(async move {
-            let result = check_and_notify(&pool, &client, &bot, &health_url).await;
-
-            // Record stats
-            if let Ok(ref health_result) = result {
-                let mut stats = stats.lock().await;
-                stats.record_result(health_result);
-
-                // Log slow but successful requests
-                if health_result.success && health_result.duration > SLOW_REQUEST_THRESHOLD {
-                    tracing::warn!(
-                        url = %health_url.url,
-                        duration_ms = health_result.duration.as_millis() as u64,
-                        "Slow request (but successful)"
-                    );
-                }
-
-                // Log detailed failure info
-                if !health_result.success {
-                    tracing::warn!(
-                        url = %health_url.url,
-                        status_code = health_result.status_code,
-                        duration_ms = health_result.duration.as_millis() as u64,
-                        failure_reason = ?health_result.failure_reason,
-                        "Health check failed"
-                    );
-                }
-            }
+    let assertions = match get_assertions_by_health_url_id(pool, health_url_id) {
+        Ok(assertions) => assertions,
+        Err(e) => {
+            tracing::error!("Failed to load assertions for {}: {:?}", health_url_id, e);
+            METRICS.increment_errors();
+            return;
+        }
+    };
 
-            if let Err(ref e) = result {
-                tracing::error!("Error checking URL {}: {:?}", health_url.url, e);
-            }
+    let windows = match get_maintenance_windows_by_health_url_id(pool, health_url_id) {
+        Ok(windows) => windows,
+        Err(e) => {
+            tracing::error!(
+                "Failed to load maintenance windows for {}: {:?}",
+                health_url_id,
+                e
+            );
+            METRICS.increment_errors();
+            return;
+        }
+    };
 
-            drop(permit);
-        });
+    let result = check_and_notify(
+        pool,
+        client,
+        bot,
+        semaphore,
+        &health_url,
+        &assertions,
+        &windows,
+    )
+    .await;
+
+    // Record stats
+    if let Ok(ref health_result) = result {
+        stats.lock().await.record_result(health_result);
+
+        // Log slow but successful requests
+        if health_result.success && health_result.duration > SLOW_REQUEST_THRESHOLD {
+            tracing::warn!(
+                url = %health_url.url,
+                duration_ms = health_result.duration.as_millis() as u64,
+                "Slow request (but successful)"
+            );
+        }
 
-        handles.push(handle);
+        // Log detailed failure info
+        if !health_result.success {
+            tracing::warn!(
+                url = %health_url.url,
+                status_code = health_result.status_code,
+                duration_ms = health_result.duration.as_millis() as u64,
+                failure_reason = ?health_result.failure_reason,
+                "Health check failed"
+            );
+        }
     }
 
-    // Wait for all checks to complete
-    for handle in handles {
-        let _ = handle.await;
+    if let Err(ref e) = result {
+        tracing::error!("Error checking URL {}: {:?}", health_url.url, e);
     }
+}
 
-    // Log cycle summary
-    let cycle_duration = cycle_start.elapsed();
-    let final_stats = stats.lock().await;
+async fn report_stats(bot: &Bot, stats: &Mutex<CycleStats>) {
+    let final_stats = std::mem::take(&mut *stats.lock().await);
+    if final_stats.total == 0 {
+        return;
+    }
 
     tracing::info!(
         total = final_stats.total,
@@ -233,29 +381,27 @@ async fn check_health_urls(
         timeout = final_stats.timeout,
         http_error = final_stats.http_error,
         request_error = final_stats.request_error,
+        assertion_failed = final_stats.assertion_failed,
         slow_requests = final_stats.slow_requests,
         max_duration_ms = final_stats.max_duration_ms,
-        cycle_duration_ms = cycle_duration.as_millis() as u64,
-        "Uptime check cycle completed"
+        "Uptime checks completed"
     );
 
     // Alert on mass timeout - this indicates a problem with the bot, not the monitored services
     if final_stats.is_mass_timeout() {
         let message = format!(
-            "MASS TIMEOUT DETECTED: {}/{} checks timed out ({:.1}%). This likely indicates a problem with the monitoring server, not the monitored services. Max request duration: {}ms, Cycle duration: {}ms",
+            "MASS TIMEOUT DETECTED: {}/{} checks timed out ({:.1}%) in the last {}s. This likely indicates a problem with the monitoring server, not the monitored services. Max request duration: {}ms",
             final_stats.timeout,
             final_stats.total,
             final_stats.timeout_percent(),
-            final_stats.max_duration_ms,
-            cycle_duration.as_millis()
+            STATS_REPORT_INTERVAL.as_secs(),
+            final_stats.max_duration_ms
         );
         tracing::error!("{}", message);
         ALERTS
-            .send_alert(&bot, Severity::Critical, "Uptime Bot", &message)
+            .send_alert(bot, Severity::Critical, "Uptime Bot", &message)
             .await;
     }
-
-    Ok(())
 }
 
 fn extract_error_details(e: &reqwest::Error) -> String {
@@ -290,24 +436,80 @@ fn extract_error_details(e: &reqwest::Error) -> String {
     }
 }
 
-pub async fn check_health(client: &Client, url: &str) -> HealthResult {
-    let start = std::time::Instant::now();
-    let response = timeout(TIMEOUT_DURATION, client.get(url).send()).await;
+async fn read_body(mut response: reqwest::Response) -> Result<String, reqwest::Error> {
+    let mut body = Vec::new();
+
+    while let Some(chunk) = response.chunk().await? {
+        let remaining = MAX_BODY_BYTES - body.len();
+        body.extend_from_slice(&chunk[..chunk.len().min(remaining)]);
+        if body.len() >= MAX_BODY_BYTES {
+            break;
+        }
+    }
+
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+/// Requests the URL with a client from [`create_monitoring_client`]. URLs of private or
+/// local IP addresses are refused here, hostnames by the client's resolver.
+pub async fn check_health(
+    client: &Client,
+    url: &str,
+    monitor_request: &MonitorRequest,
+    timeout_duration: Duration,
+    assertions: &[Assertion],
+) -> HealthResult {
+    let start = Instant::now();
+    if let Err(e) = Url::parse(url).map_or(Ok(()), |url| check_public_url(&url)) {
+        return HealthResult {
+            success: false,
+            status_code: 0,
+            duration: start.elapsed(),
+            failure_reason: Some(FailureReason::RequestError),
+            error_message: Some(e),
+            certificate: None,
+        };
+    }
+
+    let request = async {
+        let res = monitor_request.build(client, url).send().await?;
+        let status = res.status();
+        let expected = monitor_request.is_expected_status(status.as_u16());
+        let certificate = res
+            .extensions()
+            .get::<TlsInfo>()
+            .and_then(TlsInfo::peer_certificate)
+            .and_then(parse_certificate);
+        // Only read the body when there is something to assert on
+        let body = if expected && !assertions.is_empty() {
+            Some(read_body(res).await?)
+        } else {
+            None
+        };
+        Ok::<_, reqwest::Error>((status, expected, body, certificate))
+    };
+    let response = timeout(timeout_duration, request).await;
     let duration = start.elapsed();
 
     match response {
-        Ok(Ok(res)) => {
-            let is_success = res.status().is_success();
+        Ok(Ok((status, expected, body, certificate))) => {
+            let assertion_error =
+                body.and_then(|body| evaluate_assertions(assertions, &body).err());
+            let failure_reason = if !expected {
+                Some(FailureReason::HttpError)
+            } else if assertion_error.is_some() {
+                Some(FailureReason::AssertionFailed)
+            } else {
+                None
+            };
+
             HealthResult {
-                success: is_success,
-                status_code: res.status().as_u16(),
+                success: failure_reason.is_none(),
+                status_code: status.as_u16(),
                 duration,
-                failure_reason: if is_success {
-                    None
-                } else {
-                    Some(FailureReason::HttpError)
-                },
-                error_message: None,
+                failure_reason,
+                error_message: assertion_error.map(|e| format!("Assertion failed: {}", e)),
+                certificate,
             }
         }
         Ok(Err(e)) => {
@@ -322,6 +524,7 @@ pub async fn check_health(client: &Client, url: &str) -> HealthResult {
                 duration,
                 failure_reason: Some(failure_reason),
                 error_message: Some(extract_error_details(&e)),
+                certificate: None,
             }
         }
         Err(_) => HealthResult {
@@ -330,10 +533,30 @@ pub async fn check_health(client: &Client, url: &str) -> HealthResult {
             duration,
             failure_reason: Some(FailureReason::Timeout),
             error_message: None,
+            certificate: None,
         },
     }
 }
 
+/// Runs one check of the monitor's kind: an HTTP request, a TCP connect or a DNS lookup.
+pub async fn check_monitor(
+    client: &Client,
+    url: &str,
+    monitor_request: &MonitorRequest,
+    timeout_duration: Duration,
+    assertions: &[Assertion],
+) -> HealthResult {
+    let expected = monitor_request.expected_response.as_deref();
+
+    match MonitorKind::from_url(url) {
+        MonitorKind::Http => {
+            check_health(client, url, monitor_request, timeout_duration, assertions).await
+        }
+        MonitorKind::Tcp => check_tcp(url, expected, timeout_duration).await,
+        MonitorKind::Dns => check_dns(url, expected, timeout_duration).await,
+    }
+}
+
 fn is_retryable(result: &HealthResult) -> bool {
     matches!(
         result.failure_reason,
@@ -341,15 +564,37 @@ fn is_retryable(result: &HealthResult) -> bool {
     )
 }
 
-async fn check_health_with_retry(client: &Client, url: &str) -> HealthResult {
-    let mut result = check_health(client, url).await;
+/// Each attempt holds a permit of `semaphore`, which is released during the backoff.
+async fn check_health_with_retry(
+    client: &Client,
+    semaphore: &Semaphore,
+    health_url: &HealthUrl,
+    assertions: &[Assertion],
+) -> HealthResult {
+    let monitor_request = MonitorRequest::from_health_url(health_url);
+    let timeout_duration = Duration::from_secs(health_url.timeout_secs.max(1) as u64);
+    let max_retries = health_url.max_retries.max(0) as u32;
+
+    let check = || async {
+        let _permit = semaphore.acquire().await;
+        check_monitor(
+            client,
+            &health_url.url,
+            &monitor_request,
+            timeout_duration,
+            assertions,
+        )
+        .await
+    };
+
+    let mut result = check().await;
     let mut retries = 0;
 
-    while retries < MAX_RETRIES && !result.success && is_retryable(&result) {
+    while retries < max_retries && !result.success && is_retryable(&result) {
         let backoff = INITIAL_BACKOFF * 2u32.pow(retries);
         tokio::time::sleep(backoff).await;
         retries += 1;
-        result = check_health(client, url).await;
+        result = check().await;
     }
 
     result
@@ -359,15 +604,48 @@ async fn check_and_notify(
     pool: &DbPool,
     client: &Client,
     bot: &Bot,
+    semaphore: &Semaphore,
     health_url: &HealthUrl,
+    assertions: &[Assertion],
+    windows: &[MaintenanceWindow],
 ) -> Result<HealthResult, Box<dyn std::error::Error + Send + Sync>> {
     METRICS.increment_uptime_check();
 
-    let health_result = check_health_with_retry(client, &health_url.url).await;
-    let previous_status_code = health_url.status_code;
+    let health_result = check_health_with_retry(client, semaphore, health_url, assertions).await;
+
+    let error_detail = (!health_result.success).then(|| format_failure_reason(&health_result));
+    let check = NewHealthCheck {
+        health_url_id: health_url.id,
+        success: health_result.success,
+        status_code: health_result.status_code as i32,
+        duration_ms: health_result.duration.as_millis().min(i32::MAX as u128) as i32,
+        failure_reason: health_result
+            .failure_reason
+            .as_ref()
+            .map(FailureReason::as_str),
+        error_detail: error_detail.as_deref(),
+        during_degradation: health_url.is_degraded,
+    };
+    if let Err(e) = record_health_check(pool, &check) {
+        tracing::error!("Failed to record health check: {:?}", e);
+        METRICS.increment_errors();
+    }
+
+    let now = Utc::now();
+    let (state, transition) = next_check_state(
+        &health_url.check_state(),
+        health_url.confirmation_threshold,
+        health_url.recovery_threshold,
+        health_result.success,
+        now,
+    );
 
-    if let Err(e) = update_health_url_status(pool, health_url.id, health_result.status_code as i32)
-    {
+    if let Err(e) = update_health_url_check_state(
+        pool,
+        health_url.id,
+        health_result.status_code as i32,
+        &state,
+    ) {
         tracing::error!("Failed to update health URL status: {:?}", e);
         METRICS.increment_errors();
         ALERTS
@@ -383,20 +661,43 @@ async fn check_and_notify(
             .await;
     }
 
-    if health_result.success {
-        if !is_success_status(previous_status_code as u16) {
+    if !health_result.success {
+        METRICS.increment_uptime_failure();
+    }
+
+    // Incidents follow the confirmed state, also while alerts are paused or flapping
+    let resolved_incident = track_incident(pool, health_url, &state, &health_result, now);
+
+    // Checks keep running during maintenance so the state is current once it ends,
+    // but the chat only hears about it afterwards
+    if is_in_maintenance(health_url, windows, now) {
+        if health_url.maintenance_started_at.is_none() {
+            if let Err(e) = update_health_url_maintenance_started(pool, health_url.id, Some(now)) {
+                tracing::error!("Failed to record maintenance start: {:?}", e);
+                METRICS.increment_errors();
+            }
+        }
+        return Ok(health_result);
+    }
+
+    if let Some(started_at) = health_url.maintenance_started_at {
+        send_maintenance_summary(pool, bot, health_url, started_at, &state, &health_result).await?;
+    }
+
+    track_latency(pool, bot, health_url, &health_result, now).await?;
+
+    match transition {
+        Transition::Recovered => {
             send_recovery_message(
                 pool,
                 bot,
                 health_url,
-                health_result.status_code,
-                health_result.duration,
+                &health_result,
+                resolved_incident.map(|incident| incident.duration(now)),
             )
             .await?;
         }
-    } else {
-        METRICS.increment_uptime_failure();
-        if is_success_status(previous_status_code as u16) {
+        Transition::WentDown => {
             let reason_text = format_failure_reason(&health_result);
             ALERTS
                 .send_alert(
@@ -409,20 +710,44 @@ async fn check_and_notify(
 
             send_failure_message(pool, bot, health_url, &health_result).await?;
         }
+        Transition::StartedFlapping => {
+            let message = format!(
+                "<b>[FLAPPING]</b> Your endpoint {} went down and recovered {} times within the last hour. Up and down alerts are paused until its state has been stable for {}.",
+                encode_text(health_url.display_name()),
+                state.flap_transitions / 2,
+                format_duration(FLAP_STABLE_AFTER.to_std().unwrap_or_default())
+            );
+            send_monitor_message(pool, bot, health_url, message).await?;
+        }
+        Transition::StoppedFlapping => {
+            let current = if state.is_down {
+                format!("down ({})", format_failure_reason(&health_result))
+            } else {
+                "healthy".to_string()
+            };
+            let message = format!(
+                "<b>[STABLE]</b> Your endpoint {} stopped flapping and is {}. Up and down alerts are active again.",
+                encode_text(health_url.display_name()),
+                encode_text(&current)
+            );
+            send_monitor_message(pool, bot, health_url, message).await?;
+        }
+        Transition::None => {}
     }
 
-    Ok(health_result)
-}
+    if let Some(ref certificate) = health_result.certificate {
+        check_certificate(pool, bot, health_url, certificate).await?;
+    }
 
-fn is_success_status(status_code: u16) -> bool {
-    (200..300).contains(&status_code)
+    Ok(health_result)
 }
 
-async fn send_failure_message(
+/// Sends an HTML message to the chat that owns the monitor, in the topic it was added from.
+async fn send_monitor_message(
     pool: &DbPool,
     bot: &Bot,
     health_url: &HealthUrl,
-    result: &HealthResult,
+    message: String,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let chat = match find_chat_by_chat_id(pool, health_url.chat_id) {
         Ok(Some(c)) => c,
@@ -450,16 +775,14 @@ async fn send_failure_message(
             ))));
         }
     };
+    // Chats that removed the bot everywhere keep their monitors, but aren't messaged
+    if !chat.is_active {
+        return Ok(());
+    }
     let telegram_id = chat.telegram_id.clone();
 
-    let reason_text = format_failure_reason(result);
-    let message = format!(
-        "[ALARM] Health check failed for URL: {}\nReason: {}\nResponse time: {:.2}s. Uptime Bot will keep sending requests every minute but will send you a message only if it becomes healthy again.",
-        health_url.url, reason_text, result.duration.as_secs_f64()
-    );
-
-    let chat_id = match telegram_id.parse::<i64>() {
-        Ok(id) => ChatId(id),
+    let telegram_chat_id = match telegram_id.parse::<i64>() {
+        Ok(id) => id,
         Err(_) => {
             tracing::error!(
                 "Invalid telegram_id '{}' for health_url: {}",
@@ -469,65 +792,245 @@ async fn send_failure_message(
             return Err(Box::new(HealthCheckError::InvalidTelegramId(telegram_id)));
         }
     };
-    bot.send_message(chat_id, message).await?;
-    METRICS.increment_messages_sent_for_bot("uptime");
+    let thread_id = chat.thread_id.and_then(|tid| tid.parse::<i32>().ok());
+    send_uptime_notification(pool, bot, telegram_chat_id, thread_id, &message).await?;
 
     Ok(())
 }
 
-async fn send_recovery_message(
+/// When the failed checks that took the monitor down began, estimated from the
+/// check interval for when the check history has no answer.
+fn estimate_failure_start(
+    consecutive_failures: i32,
+    check_interval_secs: i32,
+    now: DateTime<Utc>,
+) -> DateTime<Utc> {
+    let earlier_failures = consecutive_failures.max(1) - 1;
+    now - chrono::Duration::seconds(earlier_failures as i64 * check_interval_secs as i64)
+}
+
+/// Opens an incident when the monitor goes down and closes it on recovery.
+/// The incident starts at the first failed check, not when the failure was confirmed.
+/// Returns the closed incident.
+fn track_incident(
+    pool: &DbPool,
+    health_url: &HealthUrl,
+    state: &HealthUrlCheckState,
+    result: &HealthResult,
+    now: DateTime<Utc>,
+) -> Option<Incident> {
+    let outcome = match (health_url.is_down, state.is_down) {
+        (false, true) => {
+            let started_at = get_failure_streak_start(pool, health_url.id)
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to load failed checks: {:?}", e);
+                    None
+                })
+                .unwrap_or_else(|| {
+                    estimate_failure_start(
+                        state.consecutive_failures,
+                        health_url.check_interval_secs,
+                        now,
+                    )
+                });
+            open_incident(
+                pool,
+                health_url.id,
+                started_at,
+                &format_failure_reason(result),
+            )
+            .map(|()| None)
+        }
+        (true, false) => resolve_incident(pool, health_url.id, now),
+        _ => return None,
+    };
+
+    outcome.unwrap_or_else(|e| {
+        tracing::error!("Failed to record incident: {:?}", e);
+        METRICS.increment_errors();
+        None
+    })
+}
+
+async fn send_maintenance_summary(
     pool: &DbPool,
     bot: &Bot,
     health_url: &HealthUrl,
-    status_code: u16,
-    duration: Duration,
+    started_at: DateTime<Utc>,
+    state: &HealthUrlCheckState,
+    result: &HealthResult,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let chat = match find_chat_by_chat_id(pool, health_url.chat_id) {
-        Ok(Some(c)) => c,
-        Ok(None) => {
-            tracing::error!("Chat not found for health_url: {}", health_url.id);
-            return Err(Box::new(HealthCheckError::ChatNotFound(health_url.id)));
-        }
+    // Clear the marker first, so a failing send doesn't repeat the summary every check
+    if let Err(e) = update_health_url_maintenance_started(pool, health_url.id, None) {
+        tracing::error!("Failed to clear maintenance start: {:?}", e);
+        METRICS.increment_errors();
+        return Ok(());
+    }
+
+    let stats = match get_check_samples_since(pool, health_url.id, started_at) {
+        Ok(samples) => WindowStats::from_samples(&samples),
         Err(e) => {
-            tracing::error!("Database error: {:?}", e);
+            tracing::error!("Failed to load checks during maintenance: {:?}", e);
             METRICS.increment_errors();
-            ALERTS
-                .send_alert(
-                    bot,
-                    Severity::Error,
-                    "Database",
-                    &format!(
-                        "Failed to find chat for health_url {}: {}",
-                        health_url.id, e
-                    ),
-                )
-                .await;
-            return Err(Box::new(HealthCheckError::DatabaseError(format!(
-                "{:?}",
-                e
-            ))));
+            WindowStats::default()
         }
     };
-    let telegram_id = chat.telegram_id.clone();
 
+    let current_state = match (state.is_down, result.success) {
+        (true, false) => format!("down ({})", format_failure_reason(result)),
+        (true, true) => "down, recovering".to_string(),
+        (false, _) => "healthy".to_string(),
+    };
+    let message =
+        format_maintenance_summary(health_url, started_at, Utc::now(), &stats, &current_state);
+
+    send_monitor_message(pool, bot, health_url, message).await
+}
+
+async fn send_failure_message(
+    pool: &DbPool,
+    bot: &Bot,
+    health_url: &HealthUrl,
+    result: &HealthResult,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let reason_text = format_failure_reason(result);
+    let message = format!(
+        "<b>[ALARM]</b> Health check failed for {}\n<b>Reason:</b> {}\nResponse time: {:.2}s. Uptime Bot will keep sending requests every {} but will send you a message only if it becomes healthy again.",
+        encode_text(health_url.display_name()), encode_text(&reason_text), result.duration.as_secs_f64(), format_duration(check_interval(health_url))
+    );
+
+    send_monitor_message(pool, bot, health_url, message).await
+}
+
+async fn send_recovery_message(
+    pool: &DbPool,
+    bot: &Bot,
+    health_url: &HealthUrl,
+    result: &HealthResult,
+    downtime: Option<Duration>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    // TCP and DNS checks have no status code
+    let mut status = match MonitorKind::from_url(&health_url.url) {
+        MonitorKind::Http => format!(" with status code {}", result.status_code),
+        MonitorKind::Tcp | MonitorKind::Dns => String::new(),
+    };
+    if let Some(downtime) = downtime {
+        status.push_str(&format!(" after {} of downtime", format_duration(downtime)));
+    }
+    let duration = result.duration;
     let message = format!(
-        "[FIXED] Your endpoint {} is now healthy with status code {}. Response time: {:.2}s. Uptime Bot will keep sending requests every minute but will send you a message only if it becomes unhealthy again.",
-        health_url.url, status_code, duration.as_secs_f64()
+        "<b>[FIXED]</b> Your endpoint {} is now healthy{}. Response time: {:.2}s. Uptime Bot will keep sending requests every {} but will send you a message only if it becomes unhealthy again.",
+        encode_text(health_url.display_name()), status, duration.as_secs_f64(), format_duration(check_interval(health_url))
     );
 
-    let chat_id = match telegram_id.parse::<i64>() {
-        Ok(id) => ChatId(id),
-        Err(_) => {
-            tracing::error!(
-                "Invalid telegram_id '{}' for health_url: {}",
-                telegram_id,
-                health_url.id
-            );
-            return Err(Box::new(HealthCheckError::InvalidTelegramId(telegram_id)));
+    send_monitor_message(pool, bot, health_url, message).await
+}
+
+/// Median response time of the monitor's recent successful checks, once there are
+/// enough of them. Slow periods don't raise the baseline they are measured against.
+fn latency_baseline(pool: &DbPool, health_url: &HealthUrl, now: DateTime<Utc>) -> Option<i32> {
+    match get_latency_baseline(
+        pool,
+        health_url.id,
+        baseline_since(health_url.target_changed_at, now),
+        MIN_BASELINE_CHECKS,
+    ) {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            tracing::error!("Failed to load latency baseline: {:?}", e);
+            METRICS.increment_errors();
+            None
+        }
+    }
+}
+
+/// Tells the chat when successful checks get slower than the monitor's latency
+/// threshold, and when they are fast again. Independent of the up/down state.
+async fn track_latency(
+    pool: &DbPool,
+    bot: &Bot,
+    health_url: &HealthUrl,
+    result: &HealthResult,
+    now: DateTime<Utc>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let threshold = LatencyThreshold::from_setting(&health_url.latency_threshold);
+    let current = health_url.latency_state();
+    if threshold == LatencyThreshold::Off && current == HealthUrlLatencyState::default() {
+        return Ok(());
+    }
+
+    let baseline_ms = match threshold {
+        LatencyThreshold::Relative(_) => latency_baseline(pool, health_url, now),
+        _ => None,
+    };
+    // Turning the alerts off drops a degraded state without a notice
+    let (state, transition) = if threshold == LatencyThreshold::Off {
+        (HealthUrlLatencyState::default(), LatencyTransition::None)
+    } else {
+        next_latency_state(
+            &current,
+            threshold.limit(baseline_ms),
+            result.success,
+            result.duration,
+            health_url.confirmation_threshold,
+            health_url.recovery_threshold,
+            now,
+        )
+    };
+
+    if state != current {
+        if let Err(e) = update_health_url_latency_state(pool, health_url.id, &state) {
+            tracing::error!("Failed to update latency state: {:?}", e);
+            METRICS.increment_errors();
+            // Don't notify without recording it, or the notice repeats every check
+            return Ok(());
         }
+    }
+
+    let message = match transition {
+        LatencyTransition::Degraded => format_degraded_message(
+            health_url.display_name(),
+            result.duration,
+            &threshold,
+            baseline_ms,
+        ),
+        LatencyTransition::Recovered => format_latency_recovered_message(
+            health_url.display_name(),
+            result.duration,
+            current
+                .degraded_since
+                .and_then(|since| (now - since).to_std().ok()),
+        ),
+        LatencyTransition::None => return Ok(()),
     };
-    bot.send_message(chat_id, message).await?;
-    METRICS.increment_messages_sent_for_bot("uptime");
+
+    send_monitor_message(pool, bot, health_url, message).await
+}
+
+/// Stores the peer certificate's expiry and warns the chat when it crosses one of
+/// the monitor's thresholds. Independent of the up/down state.
+async fn check_certificate(
+    pool: &DbPool,
+    bot: &Bot,
+    health_url: &HealthUrl,
+    certificate: &CertificateInfo,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let now = Utc::now();
+    let (state, warning) = next_tls_state(health_url, certificate, now);
+
+    if let Some(state) = state {
+        if let Err(e) = update_health_url_tls_state(pool, health_url.id, &state) {
+            tracing::error!("Failed to update TLS state: {:?}", e);
+            METRICS.increment_errors();
+            // Don't warn without recording it, or the warning repeats every check
+            return Ok(());
+        }
+    }
+
+    if warning.is_some() {
+        let message = format_certificate_warning(health_url.display_name(), certificate, now);
+        send_monitor_message(pool, bot, health_url, message).await?;
+    }
 
     Ok(())
 }
@@ -538,32 +1041,160 @@ async fn send_recovery_message(
 /// - Connection timeout: 5 seconds (separate from request timeout)
 /// - Pool idle timeout: 90 seconds (keeps connections alive between check cycles)
 /// - TCP keepalive: enabled to maintain persistent connections
+/// - TLS info: exposes the peer certificate for expiry warnings
+/// - DNS and redirects: hosts with private or local addresses are refused
 ///
 /// This client should be created once and reused across all check cycles
 /// to benefit from connection pooling and DNS caching.
-fn create_monitoring_client() -> Client {
+pub fn create_monitoring_client() -> Client {
     Client::builder()
         .user_agent("NotifineUptimeBot/1.0")
         .connect_timeout(CONNECT_TIMEOUT)
         .pool_idle_timeout(POOL_IDLE_TIMEOUT)
         .tcp_keepalive(Duration::from_secs(60))
+        .tls_info(true)
+        .dns_resolver(Arc::new(PublicResolver))
+        .redirect(public_redirect_policy())
         .build()
         .expect("Failed to create HTTP client")
 }
 
+/// Re-saves monitors whose credentials were stored before they were encrypted.
+fn encrypt_plaintext_secrets(pool: &DbPool) {
+    let health_urls = match get_all_health_urls(pool) {
+        Ok(health_urls) => health_urls,
+        Err(e) => {
+            tracing::error!("Failed to load health URLs: {:?}", e);
+            return;
+        }
+    };
+
+    for health_url in health_urls.iter().filter(|h| has_plaintext_secrets(h)) {
+        let record = match MonitorRequest::from_health_url(health_url).to_record() {
+            Ok(record) => record,
+            Err(e) => {
+                tracing::warn!("Monitor credentials stay unencrypted: {}", e);
+                return;
+            }
+        };
+
+        if let Err(e) = update_health_url_request(pool, health_url.id, &record) {
+            tracing::error!(
+                "Failed to encrypt credentials of health URL {}: {:?}",
+                health_url.id,
+                e
+            );
+        }
+    }
+}
+
 pub async fn run_uptime_checker(pool: DbPool) {
     tracing::info!("Starting uptime checker...");
 
-    // Create a single HTTP client that persists across all check cycles.
+    encrypt_plaintext_secrets(&pool);
+
+    let token = match env::var("UPTIME_TELOXIDE_TOKEN") {
+        Ok(token) => token,
+        Err(_) => {
+            tracing::error!("UPTIME_TELOXIDE_TOKEN not set, uptime checker disabled");
+            return;
+        }
+    };
+    let bot = Bot::new(token);
+
+    // Create a single HTTP client that persists across all checks.
     // This enables DNS caching, connection reuse, and prevents false timeouts
     // caused by DNS resolution delays affecting all monitors simultaneously.
     let client = create_monitoring_client();
 
+    let semaphore = Arc::new(Semaphore::new(BATCH_SIZE));
+    let stats = Arc::new(Mutex::new(CycleStats::default()));
+    let (done_tx, mut done_rx) = mpsc::unbounded_channel::<(i32, Instant)>();
+    let mut schedule = Schedule::default();
+    let mut sync_tick = tokio::time::interval(SCHEDULE_SYNC_INTERVAL);
+    let mut report_tick = tokio::time::interval_at(
+        tokio::time::Instant::now() + STATS_REPORT_INTERVAL,
+        STATS_REPORT_INTERVAL,
+    );
+
     loop {
-        if let Err(e) = check_health_urls(&pool, &client).await {
-            tracing::error!("Error in uptime checker: {:?}", e);
+        let now = Instant::now();
+
+        for health_url_id in schedule.pop_due(now) {
+            let (pool, client, bot) = (pool.clone(), client.clone(), bot.clone());
+            let (semaphore, stats, done_tx) = (semaphore.clone(), stats.clone(), done_tx.clone());
+
+            tokio::spawn(async move {
+                let started = Instant::now();
+                run_scheduled_check(&pool, &client, &bot, &semaphore, health_url_id, &stats).await;
+                let _ = done_tx.send((health_url_id, started));
+            });
+        }
+
+        let next_due = schedule.next_due().unwrap_or(now + SCHEDULE_SYNC_INTERVAL);
+
+        tokio::select! {
+            _ = sync_tick.tick() => sync_schedule(&pool, &bot, &mut schedule).await,
+            _ = report_tick.tick() => report_stats(&bot, &stats).await,
+            Some((health_url_id, started)) = done_rx.recv() => {
+                schedule.complete(health_url_id, started, Instant::now());
+            }
+            _ = tokio::time::sleep_until(tokio::time::Instant::from_std(next_due)) => {}
         }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_failure_start() {
+        let now = Utc::now();
+
+        assert_eq!(estimate_failure_start(1, 60, now), now);
+        assert_eq!(
+            estimate_failure_start(3, 60, now),
+            now - chrono::Duration::minutes(2)
+        );
+        assert_eq!(estimate_failure_start(0, 60, now), now);
+    }
+
+    #[test]
+    fn test_schedule_respects_intervals() {
+        let start = Instant::now();
+        let mut schedule = Schedule::default();
+        schedule.sync(
+            [(1, Duration::from_secs(30)), (2, Duration::from_secs(300))],
+            start,
+        );
+
+        let later = start + INITIAL_SPREAD;
+        let mut due = schedule.pop_due(later);
+        due.sort();
+        assert_eq!(due, vec![1, 2]);
+
+        schedule.complete(1, later, later);
+        schedule.complete(2, later, later);
+
+        assert_eq!(schedule.next_due(), Some(later + Duration::from_secs(30)));
+        assert!(schedule.pop_due(later + Duration::from_secs(29)).is_empty());
+        assert_eq!(schedule.pop_due(later + Duration::from_secs(30)), vec![1]);
+    }
+
+    #[test]
+    fn test_schedule_skips_removed_and_in_flight_monitors() {
+        let start = Instant::now();
+        let mut schedule = Schedule::default();
+        schedule.sync([(1, Duration::from_secs(30))], start);
+        assert_eq!(schedule.pop_due(start + INITIAL_SPREAD), vec![1]);
+
+        // Still running: a duplicate queue entry must not start a second check
+        schedule.queue.push(Reverse((start, 1)));
+        assert!(schedule.pop_due(start + INITIAL_SPREAD).is_empty());
 
-        tokio::time::sleep(Duration::from_secs(60)).await;
+        schedule.sync([], start);
+        schedule.complete(1, start, start);
+        assert_eq!(schedule.next_due(), None);
     }
 }
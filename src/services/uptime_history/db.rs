@@ -0,0 +1,197 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use diesel::prelude::*;
+use notifine::db::{DbError, DbPool};
+use notifine::schema::{health_check_rollups, health_checks};
+
+use super::types::{CheckSample, DailyRollup, WindowStats};
+
+#[derive(Insertable)]
+#[diesel(table_name = health_checks)]
+pub struct NewHealthCheck<'a> {
+    pub health_url_id: i32,
+    pub success: bool,
+    pub status_code: i32,
+    pub duration_ms: i32,
+    pub failure_reason: Option<&'a str>,
+    pub error_detail: Option<&'a str>,
+    /// Taken while response times were degraded, so it is left out of latency baselines.
+    pub during_degradation: bool,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = health_check_rollups)]
+struct NewDailyRollup {
+    health_url_id: i32,
+    day: NaiveDate,
+    total_checks: i32,
+    successful_checks: i32,
+    p50_duration_ms: Option<i32>,
+    p95_duration_ms: Option<i32>,
+}
+
+pub fn record_health_check(pool: &DbPool, check: &NewHealthCheck) -> Result<(), DbError> {
+    let conn = &mut pool.get()?;
+
+    diesel::insert_into(health_checks::table)
+        .values(check)
+        .execute(conn)?;
+
+    Ok(())
+}
+
+pub fn get_check_samples_since(
+    pool: &DbPool,
+    health_url_id: i32,
+    since: DateTime<Utc>,
+) -> Result<Vec<CheckSample>, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(health_checks::table
+        .filter(health_checks::health_url_id.eq(health_url_id))
+        .filter(health_checks::checked_at.ge(since))
+        .select((health_checks::success, health_checks::duration_ms))
+        .load::<CheckSample>(conn)?)
+}
+
+/// When the monitor's current run of failed checks began: its first failed check
+/// after the last successful one.
+pub fn get_failure_streak_start(
+    pool: &DbPool,
+    health_url_id: i32,
+) -> Result<Option<DateTime<Utc>>, DbError> {
+    let conn = &mut pool.get()?;
+
+    let last_success: Option<DateTime<Utc>> = health_checks::table
+        .filter(health_checks::health_url_id.eq(health_url_id))
+        .filter(health_checks::success.eq(true))
+        .select(diesel::dsl::max(health_checks::checked_at))
+        .first(conn)?;
+
+    let mut failures = health_checks::table
+        .filter(health_checks::health_url_id.eq(health_url_id))
+        .filter(health_checks::success.eq(false))
+        .into_boxed();
+    if let Some(last_success) = last_success {
+        failures = failures.filter(health_checks::checked_at.gt(last_success));
+    }
+
+    Ok(failures
+        .select(diesel::dsl::min(health_checks::checked_at))
+        .first(conn)?)
+}
+
+/// The median response time of the monitor's successful checks since `since`, leaving
+/// out checks taken while it was degraded. `None` with fewer than `min_checks` checks.
+pub fn get_latency_baseline(
+    pool: &DbPool,
+    health_url_id: i32,
+    since: DateTime<Utc>,
+    min_checks: i64,
+) -> Result<Option<i32>, DbError> {
+    use diesel::dsl::sql;
+    use diesel::sql_types::{Double, Nullable};
+
+    let conn = &mut pool.get()?;
+
+    let (median, checks): (Option<f64>, i64) = health_checks::table
+        .filter(health_checks::health_url_id.eq(health_url_id))
+        .filter(health_checks::checked_at.ge(since))
+        .filter(health_checks::success.eq(true))
+        .filter(health_checks::during_degradation.eq(false))
+        .select((
+            sql::<Nullable<Double>>("percentile_cont(0.5) WITHIN GROUP (ORDER BY duration_ms)"),
+            diesel::dsl::count_star(),
+        ))
+        .first(conn)?;
+
+    Ok(median
+        .filter(|_| checks >= min_checks)
+        .map(|median| median.round() as i32))
+}
+
+pub fn get_rollups_since(
+    pool: &DbPool,
+    health_url_id: i32,
+    since: NaiveDate,
+) -> Result<Vec<DailyRollup>, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(health_check_rollups::table
+        .filter(health_check_rollups::health_url_id.eq(health_url_id))
+        .filter(health_check_rollups::day.ge(since))
+        .order(health_check_rollups::day.asc())
+        .select(DailyRollup::as_select())
+        .load(conn)?)
+}
+
+/// Aggregates the raw checks of one UTC day into `health_check_rollups`.
+/// Safe to run repeatedly: existing rollups for the day are replaced.
+pub fn rollup_day(pool: &DbPool, day: NaiveDate) -> Result<usize, DbError> {
+    let conn = &mut pool.get()?;
+
+    let start = day.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+    let end = start + chrono::Duration::days(1);
+
+    let rows: Vec<(i32, bool, i32)> = health_checks::table
+        .filter(health_checks::checked_at.ge(start))
+        .filter(health_checks::checked_at.lt(end))
+        .order(health_checks::health_url_id.asc())
+        .select((
+            health_checks::health_url_id,
+            health_checks::success,
+            health_checks::duration_ms,
+        ))
+        .load(conn)?;
+
+    let mut rollups = Vec::new();
+    for chunk in rows.chunk_by(|a, b| a.0 == b.0) {
+        let samples: Vec<CheckSample> = chunk.iter().map(|(_, s, d)| (*s, *d)).collect();
+        let stats = WindowStats::from_samples(&samples);
+
+        rollups.push(NewDailyRollup {
+            health_url_id: chunk[0].0,
+            day,
+            total_checks: stats.total_checks as i32,
+            successful_checks: stats.successful_checks as i32,
+            p50_duration_ms: stats.p50_ms,
+            p95_duration_ms: stats.p95_ms,
+        });
+    }
+
+    for rollup in &rollups {
+        diesel::insert_into(health_check_rollups::table)
+            .values(rollup)
+            .on_conflict((
+                health_check_rollups::health_url_id,
+                health_check_rollups::day,
+            ))
+            .do_update()
+            .set((
+                health_check_rollups::total_checks.eq(rollup.total_checks),
+                health_check_rollups::successful_checks.eq(rollup.successful_checks),
+                health_check_rollups::p50_duration_ms.eq(rollup.p50_duration_ms),
+                health_check_rollups::p95_duration_ms.eq(rollup.p95_duration_ms),
+            ))
+            .execute(conn)?;
+    }
+
+    Ok(rollups.len())
+}
+
+pub fn delete_checks_before(pool: &DbPool, before: DateTime<Utc>) -> Result<usize, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(
+        diesel::delete(health_checks::table.filter(health_checks::checked_at.lt(before)))
+            .execute(conn)?,
+    )
+}
+
+pub fn delete_rollups_before(pool: &DbPool, before: NaiveDate) -> Result<usize, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(
+        diesel::delete(health_check_rollups::table.filter(health_check_rollups::day.lt(before)))
+            .execute(conn)?,
+    )
+}
@@ -0,0 +1,95 @@
+pub mod db;
+pub mod types;
+
+use chrono::{Duration, Utc};
+use notifine::db::{DbError, DbPool};
+
+use db::{
+    delete_checks_before, delete_rollups_before, get_check_samples_since, get_rollups_since,
+    rollup_day,
+};
+pub use db::{record_health_check, NewHealthCheck};
+pub use types::WindowStats;
+
+/// Raw checks are kept long enough to answer the 7 day window exactly.
+const RAW_RETENTION_DAYS: i64 = 8;
+/// Daily rollups back the 30 day window and longer-term availability reporting.
+const ROLLUP_RETENTION_DAYS: i64 = 400;
+/// Days that are (re-)rolled up on each maintenance run, covering late inserts and downtime.
+const ROLLUP_LOOKBACK_DAYS: i64 = 2;
+
+pub struct MonitorStats {
+    pub last_24h: WindowStats,
+    pub last_7d: WindowStats,
+    pub last_30d: WindowStats,
+}
+
+pub fn get_monitor_stats(pool: &DbPool, health_url_id: i32) -> Result<MonitorStats, DbError> {
+    let now = Utc::now();
+    let today = now.date_naive();
+
+    let week_samples = get_check_samples_since(pool, health_url_id, now - Duration::days(7))?;
+    let day_samples = get_check_samples_since(pool, health_url_id, now - Duration::days(1))?;
+    let today_start = today.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+    let today_samples = get_check_samples_since(pool, health_url_id, today_start)?;
+
+    // Completed days come from rollups, today from the raw checks
+    let mut month_parts: Vec<WindowStats> =
+        get_rollups_since(pool, health_url_id, today - Duration::days(30))?
+            .iter()
+            .filter(|rollup| rollup.day < today)
+            .map(WindowStats::from)
+            .collect();
+    month_parts.push(WindowStats::from_samples(&today_samples));
+
+    Ok(MonitorStats {
+        last_24h: WindowStats::from_samples(&day_samples),
+        last_7d: WindowStats::from_samples(&week_samples),
+        last_30d: WindowStats::combine(&month_parts),
+    })
+}
+
+fn run_maintenance(pool: &DbPool) -> Result<(), DbError> {
+    let now = Utc::now();
+    let today = now.date_naive();
+
+    for days_ago in 1..=ROLLUP_LOOKBACK_DAYS {
+        let day = today - Duration::days(days_ago);
+        let count = rollup_day(pool, day)?;
+        tracing::info!("Rolled up {} uptime monitors for {}", count, day);
+    }
+
+    let deleted_checks = delete_checks_before(pool, now - Duration::days(RAW_RETENTION_DAYS))?;
+    let deleted_rollups =
+        delete_rollups_before(pool, today - Duration::days(ROLLUP_RETENTION_DAYS))?;
+    tracing::info!(
+        deleted_checks,
+        deleted_rollups,
+        "Uptime history retention completed"
+    );
+
+    Ok(())
+}
+
+/// Rolls up check history into daily aggregates and enforces retention, once at
+/// startup and then shortly after every UTC midnight.
+pub async fn run_uptime_history_maintenance(pool: DbPool) {
+    tracing::info!("Starting uptime history maintenance...");
+
+    loop {
+        if let Err(e) = run_maintenance(&pool) {
+            tracing::error!("Uptime history maintenance failed: {:?}", e);
+        }
+
+        let now = Utc::now();
+        let next_run = (now.date_naive() + Duration::days(1))
+            .and_hms_opt(0, 10, 0)
+            .unwrap_or_default()
+            .and_utc();
+        let sleep_for = (next_run - now)
+            .to_std()
+            .unwrap_or(std::time::Duration::from_secs(86400));
+
+        tokio::time::sleep(sleep_for).await;
+    }
+}
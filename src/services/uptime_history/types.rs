@@ -0,0 +1,180 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use diesel::prelude::*;
+
+/// A single stored check result: `(success, duration_ms)`.
+pub type CheckSample = (bool, i32);
+
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = notifine::schema::health_check_rollups)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DailyRollup {
+    pub id: i32,
+    pub health_url_id: i32,
+    pub day: NaiveDate,
+    pub total_checks: i32,
+    pub successful_checks: i32,
+    pub p50_duration_ms: Option<i32>,
+    pub p95_duration_ms: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Availability and latency over a time window. Latency percentiles only consider
+/// successful checks so that timeouts don't drown out the real response times.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WindowStats {
+    pub total_checks: i64,
+    pub successful_checks: i64,
+    pub p50_ms: Option<i32>,
+    pub p95_ms: Option<i32>,
+}
+
+/// Nearest-rank percentile of an ascending slice.
+pub fn percentile(sorted: &[i32], p: f64) -> Option<i32> {
+    if sorted.is_empty() {
+        return None;
+    }
+
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+impl WindowStats {
+    pub fn from_samples(samples: &[CheckSample]) -> Self {
+        let mut durations: Vec<i32> = samples
+            .iter()
+            .filter(|(success, _)| *success)
+            .map(|(_, duration_ms)| *duration_ms)
+            .collect();
+        durations.sort_unstable();
+
+        WindowStats {
+            total_checks: samples.len() as i64,
+            successful_checks: durations.len() as i64,
+            p50_ms: percentile(&durations, 50.0),
+            p95_ms: percentile(&durations, 95.0),
+        }
+    }
+
+    /// Merges windows, e.g. daily rollups. Exact percentiles can't be recovered from
+    /// per-day values, so they are averaged weighted by each part's successful checks.
+    pub fn combine(parts: &[WindowStats]) -> Self {
+        let weighted_mean = |value: fn(&WindowStats) -> Option<i32>| {
+            let (sum, weight) = parts
+                .iter()
+                .filter_map(|part| value(part).map(|v| (v as i64, part.successful_checks)))
+                .fold((0i64, 0i64), |(sum, weight), (v, w)| {
+                    (sum + v * w, weight + w)
+                });
+
+            (weight > 0).then(|| (sum / weight) as i32)
+        };
+
+        WindowStats {
+            total_checks: parts.iter().map(|p| p.total_checks).sum(),
+            successful_checks: parts.iter().map(|p| p.successful_checks).sum(),
+            p50_ms: weighted_mean(|p| p.p50_ms),
+            p95_ms: weighted_mean(|p| p.p95_ms),
+        }
+    }
+
+    pub fn uptime_percent(&self) -> Option<f64> {
+        if self.total_checks == 0 {
+            return None;
+        }
+
+        Some(self.successful_checks as f64 / self.total_checks as f64 * 100.0)
+    }
+
+    /// One-line summary such as `99.93% uptime · p50 120ms · p95 340ms (1440 checks)`.
+    pub fn summary(&self) -> String {
+        let Some(uptime) = self.uptime_percent() else {
+            return "no data yet".to_string();
+        };
+
+        let latency = match (self.p50_ms, self.p95_ms) {
+            (Some(p50), Some(p95)) => format!(" · p50 {}ms · p95 {}ms", p50, p95),
+            _ => String::new(),
+        };
+
+        format!(
+            "{:.2}% uptime{} ({} checks)",
+            uptime, latency, self.total_checks
+        )
+    }
+}
+
+impl From<&DailyRollup> for WindowStats {
+    fn from(rollup: &DailyRollup) -> Self {
+        WindowStats {
+            total_checks: rollup.total_checks as i64,
+            successful_checks: rollup.successful_checks as i64,
+            p50_ms: rollup.p50_duration_ms,
+            p95_ms: rollup.p95_duration_ms,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile_nearest_rank() {
+        let values: Vec<i32> = (1..=100).collect();
+        assert_eq!(percentile(&values, 50.0), Some(50));
+        assert_eq!(percentile(&values, 95.0), Some(95));
+        assert_eq!(percentile(&[42], 95.0), Some(42));
+        assert_eq!(percentile(&[], 50.0), None);
+    }
+
+    #[test]
+    fn test_from_samples_ignores_failed_durations() {
+        let samples = vec![(true, 100), (true, 300), (false, 10_000), (true, 200)];
+        let stats = WindowStats::from_samples(&samples);
+
+        assert_eq!(stats.total_checks, 4);
+        assert_eq!(stats.successful_checks, 3);
+        assert_eq!(stats.p50_ms, Some(200));
+        assert_eq!(stats.p95_ms, Some(300));
+        assert_eq!(stats.uptime_percent(), Some(75.0));
+    }
+
+    #[test]
+    fn test_combine_weights_percentiles() {
+        let day_one = WindowStats {
+            total_checks: 100,
+            successful_checks: 100,
+            p50_ms: Some(100),
+            p95_ms: Some(200),
+        };
+        let day_two = WindowStats {
+            total_checks: 100,
+            successful_checks: 50,
+            p50_ms: Some(400),
+            p95_ms: Some(500),
+        };
+
+        let combined = WindowStats::combine(&[day_one, day_two]);
+
+        assert_eq!(combined.total_checks, 200);
+        assert_eq!(combined.successful_checks, 150);
+        assert_eq!(combined.p50_ms, Some(200));
+        assert_eq!(combined.p95_ms, Some(300));
+    }
+
+    #[test]
+    fn test_summary() {
+        assert_eq!(WindowStats::default().summary(), "no data yet");
+
+        let stats = WindowStats {
+            total_checks: 1440,
+            successful_checks: 1439,
+            p50_ms: Some(120),
+            p95_ms: Some(340),
+        };
+        assert_eq!(
+            stats.summary(),
+            "99.93% uptime · p50 120ms · p95 340ms (1440 checks)"
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use notifine::db::{DbError, DbPool};
+use notifine::schema::{health_urls, incidents};
+
+use super::types::Incident;
+
+#[derive(Insertable)]
+#[diesel(table_name = incidents)]
+struct NewIncident<'a> {
+    health_url_id: i32,
+    started_at: DateTime<Utc>,
+    cause: &'a str,
+}
+
+/// Opens an incident for the monitor, unless one is already open.
+pub fn open_incident(
+    pool: &DbPool,
+    health_url_id: i32,
+    started_at: DateTime<Utc>,
+    cause: &str,
+) -> Result<(), DbError> {
+    let conn = &mut pool.get()?;
+
+    diesel::insert_into(incidents::table)
+        .values(&NewIncident {
+            health_url_id,
+            started_at,
+            cause,
+        })
+        .on_conflict_do_nothing()
+        .execute(conn)?;
+
+    Ok(())
+}
+
+/// Closes the monitor's open incident and returns it, if there was one.
+pub fn resolve_incident(
+    pool: &DbPool,
+    health_url_id: i32,
+    resolved_at: DateTime<Utc>,
+) -> Result<Option<Incident>, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(diesel::update(
+        incidents::table
+            .filter(incidents::health_url_id.eq(health_url_id))
+            .filter(incidents::resolved_at.is_null()),
+    )
+    .set(incidents::resolved_at.eq(resolved_at))
+    .returning(Incident::as_returning())
+    .get_result(conn)
+    .optional()?)
+}
+
+/// Incidents of the chat's monitors that were open at some point since `since`,
+/// newest first, with the monitor's label or URL.
+pub fn get_incidents_by_chat_id_since(
+    pool: &DbPool,
+    chat_id: i32,
+    since: DateTime<Utc>,
+) -> Result<Vec<(Incident, String)>, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(incidents::table
+        .inner_join(health_urls::table)
+        .filter(health_urls::chat_id.eq(chat_id))
+        .filter(
+            incidents::resolved_at
+                .is_null()
+                .or(incidents::resolved_at.ge(since)),
+        )
+        .order(incidents::started_at.desc())
+        .select((Incident::as_select(), health_urls::url, health_urls::label))
+        .load::<(Incident, String, Option<String>)>(conn)?
+        .into_iter()
+        .map(|(incident, url, label)| (incident, label.unwrap_or(url)))
+        .collect())
+}
+
+/// Incidents of the given monitors that were open at some point since `since`,
+/// newest first.
+pub fn get_incidents_by_health_url_ids_since(
+    pool: &DbPool,
+    health_url_ids: &[i32],
+    since: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<Incident>, DbError> {
+    if health_url_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let conn = &mut pool.get()?;
+
+    Ok(incidents::table
+        .filter(incidents::health_url_id.eq_any(health_url_ids))
+        .filter(
+            incidents::resolved_at
+                .is_null()
+                .or(incidents::resolved_at.ge(since)),
+        )
+        .order(incidents::started_at.desc())
+        .limit(limit)
+        .select(Incident::as_select())
+        .load(conn)?)
+}
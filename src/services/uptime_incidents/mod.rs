@@ -0,0 +1,126 @@
+pub mod db;
+pub mod types;
+
+use chrono::{Duration, Utc};
+use notifine::db::DbPool;
+use notifine::{find_chat_by_id, get_all_health_urls};
+use std::collections::BTreeMap;
+use std::env;
+use teloxide::Bot;
+
+use crate::observability::METRICS;
+use crate::services::uptime_history::db::get_check_samples_since;
+use crate::services::uptime_history::WindowStats;
+use crate::services::uptime_notifications::send_uptime_notification;
+pub use db::{
+    get_incidents_by_chat_id_since, get_incidents_by_health_url_ids_since, open_incident,
+    resolve_incident,
+};
+pub use types::format_incident_list;
+use types::{format_weekly_report, next_report_at};
+pub use types::{DowntimeSummary, Incident};
+
+/// Period covered by `/incidents`.
+pub const INCIDENT_LIST_DAYS: i64 = 30;
+const REPORT_DAYS: i64 = 7;
+
+async fn send_weekly_reports(pool: &DbPool, bot: &Bot) {
+    let now = Utc::now();
+    let since = now - Duration::days(REPORT_DAYS);
+
+    let health_urls = match get_all_health_urls(pool) {
+        Ok(health_urls) => health_urls,
+        Err(e) => {
+            tracing::error!("Failed to load health URLs for weekly reports: {:?}", e);
+            METRICS.increment_errors();
+            return;
+        }
+    };
+
+    let mut by_chat: BTreeMap<i32, Vec<_>> = BTreeMap::new();
+    for health_url in health_urls {
+        by_chat
+            .entry(health_url.chat_id)
+            .or_default()
+            .push(health_url);
+    }
+
+    let mut sent = 0;
+    for (chat_id, health_urls) in by_chat {
+        let chat = match find_chat_by_id(pool, chat_id) {
+            Ok(Some(chat)) if chat.is_active => chat,
+            Ok(_) => continue,
+            Err(e) => {
+                tracing::error!("Failed to find chat {} for weekly report: {:?}", chat_id, e);
+                METRICS.increment_errors();
+                continue;
+            }
+        };
+
+        let incidents = match get_incidents_by_chat_id_since(pool, chat_id, since) {
+            Ok(incidents) => incidents,
+            Err(e) => {
+                tracing::error!("Failed to load incidents of chat {}: {:?}", chat_id, e);
+                METRICS.increment_errors();
+                continue;
+            }
+        };
+        let incidents: Vec<Incident> = incidents
+            .into_iter()
+            .map(|(incident, _)| incident)
+            .collect();
+        let summary = DowntimeSummary::from_incidents(&incidents, since, now);
+
+        let monitors: Vec<(String, WindowStats)> = health_urls
+            .iter()
+            .map(|health_url| {
+                let samples =
+                    get_check_samples_since(pool, health_url.id, since).unwrap_or_default();
+                (
+                    health_url.display_name().to_string(),
+                    WindowStats::from_samples(&samples),
+                )
+            })
+            .collect();
+
+        let Ok(telegram_chat_id) = chat.telegram_id.parse::<i64>() else {
+            tracing::error!("Invalid telegram_id '{}'", chat.telegram_id);
+            continue;
+        };
+
+        let message = format_weekly_report(since, now, &summary, &monitors);
+        let thread_id = chat.thread_id.and_then(|tid| tid.parse::<i32>().ok());
+        match send_uptime_notification(pool, bot, telegram_chat_id, thread_id, &message).await {
+            Ok(()) => sent += 1,
+            Err(e) => tracing::error!("Failed to send weekly report to chat {}: {}", chat_id, e),
+        }
+
+        // Stay well below Telegram's rate limits
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+
+    tracing::info!("Sent {} weekly uptime reports", sent);
+}
+
+/// Sends every chat with monitors a summary of the past week, on Mondays at 09:00 UTC.
+pub async fn run_weekly_incident_report(pool: DbPool) {
+    let Ok(token) = env::var("UPTIME_TELOXIDE_TOKEN") else {
+        tracing::warn!("UPTIME_TELOXIDE_TOKEN not set, weekly uptime reports disabled");
+        return;
+    };
+    let bot = Bot::new(token);
+
+    loop {
+        let now = Utc::now();
+        let sleep_for = (next_report_at(now) - now)
+            .to_std()
+            .unwrap_or(std::time::Duration::from_secs(3600));
+        tracing::info!(
+            "Next weekly uptime report in {} hours",
+            sleep_for.as_secs() / 3600
+        );
+
+        tokio::time::sleep(sleep_for).await;
+        send_weekly_reports(&pool, &bot).await;
+    }
+}
@@ -0,0 +1,326 @@
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, Utc};
+use diesel::prelude::*;
+use html_escape::encode_text;
+use serde::Serialize;
+use std::time::Duration;
+
+use crate::services::uptime_history::WindowStats;
+use crate::utils::duration::format_duration;
+
+/// Serializes to the public fields shown on status pages.
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = notifine::schema::incidents)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Incident {
+    #[serde(skip)]
+    pub id: i32,
+    #[serde(skip)]
+    pub health_url_id: i32,
+    pub started_at: DateTime<Utc>,
+    /// `None` while the monitor is still down.
+    pub resolved_at: Option<DateTime<Utc>>,
+    #[serde(skip)]
+    pub cause: String,
+    #[serde(skip)]
+    pub created_at: DateTime<Utc>,
+}
+
+impl Incident {
+    /// How long the monitor was down, or has been down so far.
+    pub fn duration(&self, now: DateTime<Utc>) -> Duration {
+        (self.resolved_at.unwrap_or(now) - self.started_at)
+            .to_std()
+            .unwrap_or_default()
+    }
+
+    /// Downtime that falls between `since` and `now`.
+    fn downtime_since(&self, since: DateTime<Utc>, now: DateTime<Utc>) -> Duration {
+        (self.resolved_at.unwrap_or(now) - self.started_at.max(since))
+            .to_std()
+            .unwrap_or_default()
+    }
+
+    pub fn describe(&self, name: &str, now: DateTime<Utc>) -> String {
+        let status = match self.resolved_at {
+            Some(_) => format!("down for {}", format_duration(self.duration(now))),
+            None => format!("down for {} so far", format_duration(self.duration(now))),
+        };
+
+        format!(
+            "{} {} — {}, started {} ({})",
+            if self.resolved_at.is_some() {
+                "✅"
+            } else {
+                "❌"
+            },
+            name,
+            status,
+            self.started_at.format("%Y-%m-%d %H:%M UTC"),
+            self.cause
+        )
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct DowntimeSummary {
+    pub incidents: usize,
+    pub ongoing: usize,
+    /// Downtime within the reporting window.
+    pub total_downtime: Duration,
+    /// Mean time to recovery of the resolved incidents.
+    pub mttr: Option<Duration>,
+}
+
+impl DowntimeSummary {
+    /// Summarizes the incidents that overlap the window from `since` to `now`.
+    pub fn from_incidents(
+        incidents: &[Incident],
+        since: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let resolved: Vec<Duration> = incidents
+            .iter()
+            .filter(|incident| incident.resolved_at.is_some())
+            .map(|incident| incident.duration(now))
+            .collect();
+
+        DowntimeSummary {
+            incidents: incidents.len(),
+            ongoing: incidents.len() - resolved.len(),
+            total_downtime: incidents
+                .iter()
+                .map(|incident| incident.downtime_since(since, now))
+                .sum(),
+            mttr: (!resolved.is_empty())
+                .then(|| resolved.iter().sum::<Duration>() / resolved.len() as u32),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        if self.incidents == 0 {
+            return "No incidents.".to_string();
+        }
+
+        let ongoing = if self.ongoing > 0 {
+            format!(" ({} ongoing)", self.ongoing)
+        } else {
+            String::new()
+        };
+        let mttr = self
+            .mttr
+            .map(format_duration)
+            .unwrap_or_else(|| "-".to_string());
+
+        format!(
+            "Incidents: {}{}\nTotal downtime: {}\nMean time to recovery: {}",
+            self.incidents,
+            ongoing,
+            format_duration(self.total_downtime),
+            mttr
+        )
+    }
+}
+
+/// Incidents listed by `/incidents`, newest first.
+const MAX_LISTED_INCIDENTS: usize = 15;
+
+/// Reply to `/incidents`: the summary and the latest incidents with their monitors.
+pub fn format_incident_list(
+    incidents: &[(Incident, String)],
+    since: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> String {
+    let days = (now - since).num_days();
+    if incidents.is_empty() {
+        return format!("No incidents in the last {} days.", days);
+    }
+
+    let summary = DowntimeSummary::from_incidents(
+        &incidents
+            .iter()
+            .map(|(incident, _)| incident.clone())
+            .collect::<Vec<_>>(),
+        since,
+        now,
+    );
+    let lines: Vec<String> = incidents
+        .iter()
+        .take(MAX_LISTED_INCIDENTS)
+        .map(|(incident, name)| incident.describe(name, now))
+        .collect();
+    let more = match incidents.len().saturating_sub(MAX_LISTED_INCIDENTS) {
+        0 => String::new(),
+        hidden => format!("\n…and {} more", hidden),
+    };
+
+    format!(
+        "Incidents in the last {} days:\n{}\n\n{}{}",
+        days,
+        summary.describe(),
+        lines.join("\n"),
+        more
+    )
+}
+
+/// Weekly reports go out on Mondays at this UTC hour.
+const REPORT_HOUR: u32 = 9;
+
+/// The first Monday 09:00 UTC after `now`.
+pub fn next_report_at(now: DateTime<Utc>) -> DateTime<Utc> {
+    let days_until_monday = (7 - now.weekday().num_days_from_monday() as i64) % 7;
+    let candidate = (now.date_naive() + ChronoDuration::days(days_until_monday))
+        .and_hms_opt(REPORT_HOUR, 0, 0)
+        .unwrap_or_default()
+        .and_utc();
+
+    if candidate > now {
+        candidate
+    } else {
+        candidate + ChronoDuration::days(7)
+    }
+}
+
+/// Weekly HTML message with the chat's incidents and the availability of each monitor.
+pub fn format_weekly_report(
+    since: DateTime<Utc>,
+    now: DateTime<Utc>,
+    summary: &DowntimeSummary,
+    monitors: &[(String, WindowStats)],
+) -> String {
+    let lines: Vec<String> = monitors
+        .iter()
+        .map(|(name, stats)| {
+            let emoji = match stats.uptime_percent() {
+                Some(uptime) if uptime < 99.0 => "⚠️",
+                _ => "✅",
+            };
+            format!("{} {}: {}", emoji, encode_text(name), stats.summary())
+        })
+        .collect();
+
+    format!(
+        "📊 <b>Weekly uptime report</b> ({} – {})\n\n{}\n\n<b>Availability:</b>\n{}",
+        since.format("%b %d"),
+        now.format("%b %d"),
+        summary.describe(),
+        lines.join("\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn incident(started_at: DateTime<Utc>, resolved_at: Option<DateTime<Utc>>) -> Incident {
+        Incident {
+            id: 1,
+            health_url_id: 1,
+            started_at,
+            resolved_at,
+            cause: "Timeout".to_string(),
+            created_at: started_at,
+        }
+    }
+
+    #[test]
+    fn test_downtime_summary() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 26, 9, 0, 0).unwrap();
+        let since = now - chrono::Duration::days(7);
+        let minutes = |m| chrono::Duration::minutes(m);
+
+        let incidents = vec![
+            // Started before the window: only the part inside counts as downtime
+            incident(since - minutes(30), Some(since + minutes(10))),
+            incident(since + minutes(60), Some(since + minutes(80))),
+            incident(now - minutes(5), None),
+        ];
+
+        let summary = DowntimeSummary::from_incidents(&incidents, since, now);
+        assert_eq!(summary.incidents, 3);
+        assert_eq!(summary.ongoing, 1);
+        assert_eq!(summary.total_downtime, Duration::from_secs(35 * 60));
+        assert_eq!(summary.mttr, Some(Duration::from_secs(30 * 60)));
+        assert!(summary.describe().contains("Incidents: 3 (1 ongoing)"));
+
+        let empty = DowntimeSummary::from_incidents(&[], since, now);
+        assert_eq!(empty.mttr, None);
+        assert_eq!(empty.describe(), "No incidents.");
+    }
+
+    #[test]
+    fn test_format_incident_list() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 26, 9, 0, 0).unwrap();
+        let since = now - chrono::Duration::days(30);
+        let started_at = Utc.with_ymd_and_hms(2026, 10, 20, 14, 2, 0).unwrap();
+
+        let list = format_incident_list(
+            &[
+                (
+                    incident(now - chrono::Duration::minutes(3), None),
+                    "https://api.example.com".to_string(),
+                ),
+                (
+                    incident(started_at, Some(started_at + chrono::Duration::minutes(12))),
+                    "https://example.com".to_string(),
+                ),
+            ],
+            since,
+            now,
+        );
+
+        assert!(list.starts_with("Incidents in the last 30 days:\nIncidents: 2 (1 ongoing)"));
+        assert!(list.contains("❌ https://api.example.com — down for 3m so far"));
+        assert!(list.contains(
+            "✅ https://example.com — down for 12m, started 2026-10-20 14:02 UTC (Timeout)"
+        ));
+        assert_eq!(
+            format_incident_list(&[], since, now),
+            "No incidents in the last 30 days."
+        );
+    }
+
+    #[test]
+    fn test_next_report_at() {
+        let monday_9am = Utc.with_ymd_and_hms(2026, 10, 26, 9, 0, 0).unwrap();
+
+        assert_eq!(
+            next_report_at(Utc.with_ymd_and_hms(2026, 10, 22, 15, 0, 0).unwrap()),
+            monday_9am
+        );
+        assert_eq!(
+            next_report_at(Utc.with_ymd_and_hms(2026, 10, 26, 8, 59, 0).unwrap()),
+            monday_9am
+        );
+        assert_eq!(
+            next_report_at(monday_9am),
+            monday_9am + chrono::Duration::days(7)
+        );
+    }
+
+    #[test]
+    fn test_format_weekly_report() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 26, 9, 0, 0).unwrap();
+        let since = now - chrono::Duration::days(7);
+        let stats = |total, successful| WindowStats {
+            total_checks: total,
+            successful_checks: successful,
+            ..Default::default()
+        };
+
+        let report = format_weekly_report(
+            since,
+            now,
+            &DowntimeSummary::default(),
+            &[
+                ("https://example.com".to_string(), stats(100, 100)),
+                ("https://api.example.com".to_string(), stats(100, 90)),
+            ],
+        );
+
+        assert!(report.starts_with("📊 <b>Weekly uptime report</b> (Oct 19 – Oct 26)"));
+        assert!(report.contains("No incidents."));
+        assert!(report.contains("✅ https://example.com: 100.00% uptime (100 checks)"));
+        assert!(report.contains("⚠️ https://api.example.com: 90.00% uptime (100 checks)"));
+    }
+}
@@ -0,0 +1,379 @@
+use chrono::{DateTime, Utc};
+use html_escape::encode_text;
+use notifine::models::HealthUrlLatencyState;
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+use crate::utils::duration::{format_duration, parse_duration};
+
+const ABSOLUTE_RANGE_MS: RangeInclusive<u64> = 100..=60_000;
+const FACTOR_RANGE: RangeInclusive<f64> = 1.5..=20.0;
+/// Relative thresholds compare against the median of the successful checks in this
+/// window, without those taken while the monitor was degraded.
+pub const BASELINE_WINDOW: chrono::Duration = chrono::Duration::hours(24);
+/// With fewer successful checks in the window there is no baseline yet.
+pub const MIN_BASELINE_CHECKS: i64 = 10;
+
+/// Start of the baseline window. Checks of a previous URL don't count.
+pub fn baseline_since(
+    target_changed_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> DateTime<Utc> {
+    let window_start = now - BASELINE_WINDOW;
+    target_changed_at.map_or(window_start, |changed_at| changed_at.max(window_start))
+}
+
+/// When a successful check counts as slow, stored in `latency_threshold` as
+/// `1500ms`, `3x` or empty for off.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LatencyThreshold {
+    Off,
+    Absolute(Duration),
+    /// A multiple of the monitor's usual response time.
+    Relative(f64),
+}
+
+impl LatencyThreshold {
+    /// Reads the stored setting; anything unreadable counts as off.
+    pub fn from_setting(value: &str) -> Self {
+        if let Some(factor) = value.strip_suffix('x') {
+            factor
+                .parse()
+                .map(LatencyThreshold::Relative)
+                .unwrap_or(LatencyThreshold::Off)
+        } else if let Some(ms) = value.strip_suffix("ms") {
+            ms.parse()
+                .map(|ms| LatencyThreshold::Absolute(Duration::from_millis(ms)))
+                .unwrap_or(LatencyThreshold::Off)
+        } else {
+            LatencyThreshold::Off
+        }
+    }
+
+    /// The response time above which a check is slow. Relative thresholds have
+    /// none until there is a baseline.
+    pub fn limit(&self, baseline_ms: Option<i32>) -> Option<Duration> {
+        match self {
+            LatencyThreshold::Off => None,
+            LatencyThreshold::Absolute(limit) => Some(*limit),
+            LatencyThreshold::Relative(factor) => {
+                baseline_ms.map(|ms| Duration::from_secs_f64(ms.max(1) as f64 * factor / 1000.0))
+            }
+        }
+    }
+
+    fn describe(&self, baseline_ms: Option<i32>) -> String {
+        match self {
+            LatencyThreshold::Off => "no limit".to_string(),
+            LatencyThreshold::Absolute(limit) => format!("the limit of {}", format_latency(*limit)),
+            LatencyThreshold::Relative(factor) => match baseline_ms {
+                Some(ms) => format!(
+                    "{}x its usual {}",
+                    factor,
+                    format_latency(Duration::from_millis(ms.max(0) as u64))
+                ),
+                None => format!("{}x its usual response time", factor),
+            },
+        }
+    }
+}
+
+fn format_latency(duration: Duration) -> String {
+    let ms = duration.as_millis();
+    if ms < 1000 {
+        format!("{}ms", ms)
+    } else if ms.is_multiple_of(1000) {
+        format!("{}s", ms / 1000)
+    } else {
+        format!("{:.1}s", duration.as_secs_f64())
+    }
+}
+
+/// Parses the `latency` setting: a response time such as `2s` or `800ms`, a multiple
+/// of the usual response time such as `3x`, or `off`. Returns the value to store.
+pub fn parse_latency_threshold(value: &str) -> Result<String, String> {
+    let value = value.trim().to_lowercase();
+    if matches!(value.as_str(), "off" | "none") {
+        return Ok(String::new());
+    }
+
+    let invalid = || {
+        format!(
+            "Invalid latency '{}'. Use a response time like 2s or 800ms, a multiple of the usual response time like 3x, or off.",
+            value
+        )
+    };
+
+    if let Some(factor) = value.strip_suffix('x') {
+        let factor: f64 = factor.parse().map_err(|_| invalid())?;
+        if !FACTOR_RANGE.contains(&factor) {
+            return Err(format!(
+                "latency must be between {}x and {}x the usual response time.",
+                FACTOR_RANGE.start(),
+                FACTOR_RANGE.end()
+            ));
+        }
+        return Ok(format!("{}x", factor));
+    }
+
+    let ms = match value.strip_suffix("ms") {
+        Some(ms) => ms.parse::<u64>().ok(),
+        None => parse_duration(&value).map(|duration| duration.as_millis() as u64),
+    }
+    .ok_or_else(invalid)?;
+    if !ABSOLUTE_RANGE_MS.contains(&ms) {
+        return Err(format!(
+            "latency must be between {} and {}.",
+            format_latency(Duration::from_millis(*ABSOLUTE_RANGE_MS.start())),
+            format_latency(Duration::from_millis(*ABSOLUTE_RANGE_MS.end()))
+        ));
+    }
+
+    Ok(format!("{}ms", ms))
+}
+
+pub fn format_latency_threshold(value: &str) -> String {
+    match LatencyThreshold::from_setting(value) {
+        LatencyThreshold::Off => "Response time alerts are off".to_string(),
+        LatencyThreshold::Absolute(limit) => {
+            format!("Response time alerts above {}", format_latency(limit))
+        }
+        LatencyThreshold::Relative(factor) => format!(
+            "Response time alerts above {}x the usual response time",
+            factor
+        ),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum LatencyTransition {
+    None,
+    Degraded,
+    Recovered,
+}
+
+/// Computes the response time state after a check. Like up/down alerts, a monitor
+/// is degraded once `confirmation_threshold` successful checks in a row were slower
+/// than `limit`, and back to normal once `recovery_threshold` checks in a row weren't.
+/// Failed checks are reported as down instead and, like checks without a limit (a
+/// relative threshold without baseline), leave the state as it is.
+pub fn next_latency_state(
+    state: &HealthUrlLatencyState,
+    limit: Option<Duration>,
+    success: bool,
+    duration: Duration,
+    confirmation_threshold: i32,
+    recovery_threshold: i32,
+    now: DateTime<Utc>,
+) -> (HealthUrlLatencyState, LatencyTransition) {
+    let Some(limit) = limit.filter(|_| success) else {
+        return (state.clone(), LatencyTransition::None);
+    };
+    let slow = duration > limit;
+
+    if slow == state.is_degraded {
+        let next = HealthUrlLatencyState {
+            latency_streak: 0,
+            ..state.clone()
+        };
+        return (next, LatencyTransition::None);
+    }
+
+    let streak = state.latency_streak.saturating_add(1);
+    let needed = if state.is_degraded {
+        recovery_threshold
+    } else {
+        confirmation_threshold
+    };
+    if streak < needed.max(1) {
+        let next = HealthUrlLatencyState {
+            latency_streak: streak,
+            ..state.clone()
+        };
+        return (next, LatencyTransition::None);
+    }
+
+    if state.is_degraded {
+        (
+            HealthUrlLatencyState::default(),
+            LatencyTransition::Recovered,
+        )
+    } else {
+        let next = HealthUrlLatencyState {
+            is_degraded: true,
+            latency_streak: 0,
+            degraded_since: Some(now),
+        };
+        (next, LatencyTransition::Degraded)
+    }
+}
+
+pub fn format_degraded_message(
+    name: &str,
+    duration: Duration,
+    threshold: &LatencyThreshold,
+    baseline_ms: Option<i32>,
+) -> String {
+    format!(
+        "<b>[DEGRADED]</b> Your endpoint {} is responding slowly: {:.2}s, above {}. It is still up, and Uptime Bot will send you a message once response times are back to normal.",
+        encode_text(name),
+        duration.as_secs_f64(),
+        threshold.describe(baseline_ms)
+    )
+}
+
+pub fn format_latency_recovered_message(
+    name: &str,
+    duration: Duration,
+    degraded_for: Option<Duration>,
+) -> String {
+    let after = degraded_for
+        .map(|degraded_for| format!(" after {} of slow responses", format_duration(degraded_for)))
+        .unwrap_or_default();
+
+    format!(
+        "<b>[NORMAL]</b> Response times of {} are back to normal{}. Response time: {:.2}s.",
+        encode_text(name),
+        after,
+        duration.as_secs_f64()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_baseline_since() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 24, 12, 0, 0).unwrap();
+        let edited = Utc.with_ymd_and_hms(2026, 10, 24, 9, 0, 0).unwrap();
+
+        assert_eq!(baseline_since(None, now), now - BASELINE_WINDOW);
+        assert_eq!(baseline_since(Some(edited), now), edited);
+        assert_eq!(
+            baseline_since(Some(edited - chrono::Duration::days(2)), now),
+            now - BASELINE_WINDOW
+        );
+    }
+
+    #[test]
+    fn test_parse_latency_threshold() {
+        assert_eq!(parse_latency_threshold("2s").unwrap(), "2000ms");
+        assert_eq!(parse_latency_threshold("800ms").unwrap(), "800ms");
+        assert_eq!(parse_latency_threshold("3X").unwrap(), "3x");
+        assert_eq!(parse_latency_threshold("2.5x").unwrap(), "2.5x");
+        assert_eq!(parse_latency_threshold("off").unwrap(), "");
+        assert!(parse_latency_threshold("50ms").is_err());
+        assert!(parse_latency_threshold("2m").is_err());
+        assert!(parse_latency_threshold("1x").is_err());
+        assert!(parse_latency_threshold("fast").is_err());
+
+        assert_eq!(
+            LatencyThreshold::from_setting("2000ms"),
+            LatencyThreshold::Absolute(Duration::from_secs(2))
+        );
+        assert_eq!(
+            LatencyThreshold::from_setting("2.5x"),
+            LatencyThreshold::Relative(2.5)
+        );
+        assert_eq!(LatencyThreshold::from_setting(""), LatencyThreshold::Off);
+        assert_eq!(
+            format_latency_threshold("1500ms"),
+            "Response time alerts above 1.5s"
+        );
+    }
+
+    #[test]
+    fn test_limit() {
+        let absolute = LatencyThreshold::Absolute(Duration::from_secs(2));
+        assert_eq!(absolute.limit(None), Some(Duration::from_secs(2)));
+
+        let relative = LatencyThreshold::Relative(3.0);
+        assert_eq!(relative.limit(None), None);
+        assert_eq!(relative.limit(Some(200)), Some(Duration::from_millis(600)));
+        assert_eq!(LatencyThreshold::Off.limit(Some(200)), None);
+    }
+
+    #[test]
+    fn test_degrades_and_recovers_after_thresholds() {
+        let now = Utc::now();
+        let limit = Some(Duration::from_secs(1));
+        let slow = Duration::from_secs(3);
+        let fast = Duration::from_millis(200);
+        let check = |state: &HealthUrlLatencyState, duration| {
+            next_latency_state(state, limit, true, duration, 2, 2, now)
+        };
+
+        let (state, transition) = check(&HealthUrlLatencyState::default(), slow);
+        assert_eq!(transition, LatencyTransition::None);
+        assert_eq!(state.latency_streak, 1);
+
+        // A fast check in between starts the count over
+        let (state, _) = check(&state, fast);
+        assert_eq!(state.latency_streak, 0);
+
+        let (state, _) = check(&state, slow);
+        let (state, transition) = check(&state, slow);
+        assert_eq!(transition, LatencyTransition::Degraded);
+        assert!(state.is_degraded);
+        assert_eq!(state.degraded_since, Some(now));
+
+        let (state, transition) = check(&state, fast);
+        assert_eq!(transition, LatencyTransition::None);
+        assert!(state.is_degraded);
+        let (state, transition) = check(&state, fast);
+        assert_eq!(transition, LatencyTransition::Recovered);
+        assert_eq!(state, HealthUrlLatencyState::default());
+    }
+
+    #[test]
+    fn test_failed_checks_and_no_limit_keep_the_state() {
+        let now = Utc::now();
+        let degraded = HealthUrlLatencyState {
+            is_degraded: true,
+            latency_streak: 1,
+            degraded_since: Some(now),
+        };
+
+        let (state, transition) = next_latency_state(
+            &degraded,
+            Some(Duration::from_secs(1)),
+            false,
+            Duration::from_secs(10),
+            1,
+            1,
+            now,
+        );
+        assert_eq!(state, degraded);
+        assert_eq!(transition, LatencyTransition::None);
+
+        let (state, transition) =
+            next_latency_state(&degraded, None, true, Duration::from_millis(10), 1, 1, now);
+        assert_eq!(state, degraded);
+        assert_eq!(transition, LatencyTransition::None);
+    }
+
+    #[test]
+    fn test_messages() {
+        let degraded = format_degraded_message(
+            "Payments <API>",
+            Duration::from_millis(2500),
+            &LatencyThreshold::Relative(3.0),
+            Some(400),
+        );
+        assert!(degraded.starts_with(
+            "<b>[DEGRADED]</b> Your endpoint Payments &lt;API&gt; is responding slowly: 2.50s, above 3x its usual 400ms."
+        ));
+
+        let recovered = format_latency_recovered_message(
+            "https://example.com",
+            Duration::from_millis(300),
+            Some(Duration::from_secs(25 * 60)),
+        );
+        assert_eq!(
+            recovered,
+            "<b>[NORMAL]</b> Response times of https://example.com are back to normal after 25m of slow responses. Response time: 0.30s."
+        );
+    }
+}
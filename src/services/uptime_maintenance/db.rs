@@ -0,0 +1,59 @@
+use diesel::prelude::*;
+use notifine::db::{DbError, DbPool};
+use notifine::schema::maintenance_windows;
+
+use super::types::{format_days, MaintenanceWindow, MaintenanceWindowInput};
+
+#[derive(Insertable)]
+#[diesel(table_name = maintenance_windows)]
+struct NewMaintenanceWindow<'a> {
+    health_url_id: i32,
+    days: &'a str,
+    start_time: chrono::NaiveTime,
+    duration_mins: i32,
+    timezone: &'a str,
+}
+
+pub fn create_maintenance_window(
+    pool: &DbPool,
+    health_url_id: i32,
+    input: &MaintenanceWindowInput,
+) -> Result<MaintenanceWindow, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(diesel::insert_into(maintenance_windows::table)
+        .values(&NewMaintenanceWindow {
+            health_url_id,
+            days: &format_days(&input.days),
+            start_time: input.start_time,
+            duration_mins: input.duration_mins,
+            timezone: input.timezone.name(),
+        })
+        .returning(MaintenanceWindow::as_returning())
+        .get_result(conn)?)
+}
+
+pub fn get_maintenance_windows_by_health_url_id(
+    pool: &DbPool,
+    health_url_id: i32,
+) -> Result<Vec<MaintenanceWindow>, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(maintenance_windows::table
+        .filter(maintenance_windows::health_url_id.eq(health_url_id))
+        .order(maintenance_windows::id.asc())
+        .select(MaintenanceWindow::as_select())
+        .load(conn)?)
+}
+
+pub fn delete_maintenance_windows_by_health_url_id(
+    pool: &DbPool,
+    health_url_id: i32,
+) -> Result<usize, DbError> {
+    let conn = &mut pool.get()?;
+
+    Ok(diesel::delete(
+        maintenance_windows::table.filter(maintenance_windows::health_url_id.eq(health_url_id)),
+    )
+    .execute(conn)?)
+}
@@ -0,0 +1,58 @@
+pub mod db;
+pub mod types;
+
+use chrono::{DateTime, Utc};
+use html_escape::encode_text;
+use notifine::models::HealthUrl;
+
+use crate::services::uptime_history::WindowStats;
+use crate::utils::duration::format_duration;
+pub use db::{
+    create_maintenance_window, delete_maintenance_windows_by_health_url_id,
+    get_maintenance_windows_by_health_url_id,
+};
+pub use types::{parse_maintenance_window, parse_pause_duration, MaintenanceWindow};
+
+pub const MAX_WINDOWS_PER_MONITOR: usize = 5;
+
+pub const PAUSE_USAGE: &str = "Usage: /pause <id> [duration]\n\n\
+    Pauses alerts of an endpoint, e.g. /pause 12 2h. Without a duration it stays paused \
+    until /resume <id>. The endpoint is still checked, and you get a summary when alerts resume.";
+
+pub const MAINTENANCE_USAGE: &str = "Usage: /maintenance <id> <days> <HH:MM-HH:MM> [timezone]\n\n\
+    During a maintenance window the endpoint is still checked, but you won't get alerts. \
+    A summary is sent when the window ends.\n\n\
+    Examples:\n\
+    /maintenance 12 sun 02:00-03:00 Europe/Berlin\n\
+    /maintenance 12 mon-fri 23:30-00:15\n\
+    /maintenance 12 daily 04:00-04:10 UTC\n\n\
+    /maintenance <id> lists the windows, /maintenance <id> clear removes them.";
+
+/// Whether alerts of the monitor are currently suppressed by a pause or a maintenance window.
+pub fn is_in_maintenance(
+    health_url: &HealthUrl,
+    windows: &[MaintenanceWindow],
+    now: DateTime<Utc>,
+) -> bool {
+    health_url.is_paused(now) || windows.iter().any(|window| window.is_active(now))
+}
+
+/// HTML message sent once a pause or maintenance window is over.
+pub fn format_maintenance_summary(
+    health_url: &HealthUrl,
+    started_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+    stats: &WindowStats,
+    current_state: &str,
+) -> String {
+    let failed = stats.total_checks - stats.successful_checks;
+
+    format!(
+        "<b>[MAINTENANCE]</b> Alerts for {} are active again after {}.\nDuring maintenance: {}, {} failed checks.\nCurrent state: {}",
+        encode_text(health_url.display_name()),
+        format_duration((now - started_at).to_std().unwrap_or_default()),
+        stats.summary(),
+        failed,
+        encode_text(current_state)
+    )
+}
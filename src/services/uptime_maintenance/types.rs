@@ -0,0 +1,282 @@
+use chrono::{DateTime, Datelike, Duration, NaiveTime, Utc, Weekday};
+use chrono_tz::Tz;
+use diesel::prelude::*;
+use std::ops::RangeInclusive;
+
+use crate::utils::duration::{format_duration, parse_duration};
+
+const MAX_DURATION_MINS: i64 = 24 * 60;
+const PAUSE_RANGE: RangeInclusive<u64> = 60..=30 * 86400;
+
+#[derive(Debug, Clone, Queryable, Selectable)]
+#[diesel(table_name = notifine::schema::maintenance_windows)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MaintenanceWindow {
+    pub id: i32,
+    pub health_url_id: i32,
+    pub days: String,
+    pub start_time: NaiveTime,
+    pub duration_mins: i32,
+    pub timezone: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A validated window as entered with `/maintenance`, before it is stored.
+#[derive(Debug, PartialEq)]
+pub struct MaintenanceWindowInput {
+    pub days: Vec<Weekday>,
+    pub start_time: NaiveTime,
+    pub duration_mins: i32,
+    pub timezone: Tz,
+}
+
+const ALL_DAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+fn parse_weekday(value: &str) -> Result<Weekday, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid day '{}'. Use mon, tue, ... sun.", value))
+}
+
+/// Parses days such as `sun`, `sat,sun`, `mon-fri` or `daily`.
+pub fn parse_days(value: &str) -> Result<Vec<Weekday>, String> {
+    let value = value.to_lowercase();
+    if value == "daily" {
+        return Ok(ALL_DAYS.to_vec());
+    }
+
+    let mut days = Vec::new();
+    for part in value.split(',') {
+        match part.split_once('-') {
+            Some((first, last)) => {
+                let (mut day, last) = (parse_weekday(first)?, parse_weekday(last)?);
+                days.push(day);
+                while day != last {
+                    day = day.succ();
+                    days.push(day);
+                }
+            }
+            None => days.push(parse_weekday(part)?),
+        }
+    }
+
+    Ok(ALL_DAYS
+        .into_iter()
+        .filter(|day| days.contains(day))
+        .collect())
+}
+
+pub fn format_days(days: &[Weekday]) -> String {
+    if days.len() == ALL_DAYS.len() {
+        return "daily".to_string();
+    }
+
+    days.iter()
+        .map(|day| day.to_string().to_lowercase())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn parse_time(value: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(value, "%H:%M")
+        .map_err(|_| format!("Invalid time '{}'. Use 24-hour times like 02:00.", value))
+}
+
+/// Parses `<days> <HH:MM-HH:MM> [timezone]`, e.g. `sun 02:00-03:00 Europe/Berlin`.
+/// Windows ending before they start run past midnight.
+pub fn parse_maintenance_window(args: &str) -> Result<MaintenanceWindowInput, String> {
+    let args: Vec<&str> = args.split_whitespace().collect();
+    let (days, times, timezone) = match args.as_slice() {
+        [days, times] => (days, times, "UTC"),
+        [days, times, timezone] => (days, times, *timezone),
+        _ => return Err("Expected days, a time range and an optional timezone.".to_string()),
+    };
+
+    let days = parse_days(days)?;
+    let (start, end) = times
+        .split_once('-')
+        .ok_or_else(|| format!("Invalid time range '{}'. Use 02:00-03:00.", times))?;
+    let (start_time, end_time) = (parse_time(start)?, parse_time(end)?);
+    if start_time == end_time {
+        return Err("The window must not start and end at the same time.".to_string());
+    }
+
+    let mut duration = end_time - start_time;
+    if duration < Duration::zero() {
+        duration += Duration::days(1);
+    }
+
+    let timezone: Tz = timezone.parse().map_err(|_| {
+        format!(
+            "Unknown timezone '{}'. Use names like UTC or Europe/Berlin.",
+            timezone
+        )
+    })?;
+
+    Ok(MaintenanceWindowInput {
+        days,
+        start_time,
+        duration_mins: duration.num_minutes().min(MAX_DURATION_MINS) as i32,
+        timezone,
+    })
+}
+
+/// Parses the optional duration of `/pause`, e.g. `30m` or `2d`.
+pub fn parse_pause_duration(value: &str) -> Result<std::time::Duration, String> {
+    let duration = parse_duration(value).ok_or_else(|| {
+        format!(
+            "Invalid duration '{}'. Use values like 30m, 2h or 1d.",
+            value
+        )
+    })?;
+
+    if !PAUSE_RANGE.contains(&duration.as_secs()) {
+        return Err(format!(
+            "A pause must last between {} and {}.",
+            format_duration(std::time::Duration::from_secs(*PAUSE_RANGE.start())),
+            format_duration(std::time::Duration::from_secs(*PAUSE_RANGE.end()))
+        ));
+    }
+
+    Ok(duration)
+}
+
+/// Whether `now` falls into a window starting at `start_time` local time on one of `days`.
+/// Local wall-clock times are compared, so windows follow daylight saving changes.
+pub fn is_window_active(
+    days: &[Weekday],
+    start_time: NaiveTime,
+    duration: Duration,
+    timezone: Tz,
+    now: DateTime<Utc>,
+) -> bool {
+    let local = now.with_timezone(&timezone).naive_local();
+
+    // A window that started yesterday may still be running past midnight
+    [local.date(), local.date() - Duration::days(1)]
+        .into_iter()
+        .filter(|date| days.contains(&date.weekday()))
+        .map(|date| date.and_time(start_time))
+        .any(|start| local >= start && local < start + duration)
+}
+
+impl MaintenanceWindow {
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        let (Ok(days), Ok(timezone)) = (parse_days(&self.days), self.timezone.parse::<Tz>()) else {
+            return false;
+        };
+
+        is_window_active(
+            &days,
+            self.start_time,
+            Duration::minutes(self.duration_mins as i64),
+            timezone,
+            now,
+        )
+    }
+
+    pub fn describe(&self) -> String {
+        let end = self.start_time + Duration::minutes(self.duration_mins as i64);
+        format!(
+            "{} {}-{} {}",
+            self.days,
+            self.start_time.format("%H:%M"),
+            end.format("%H:%M"),
+            self.timezone
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("sun"), Ok(vec![Weekday::Sun]));
+        assert_eq!(parse_days("Sat,SUN"), Ok(vec![Weekday::Sat, Weekday::Sun]));
+        assert_eq!(
+            parse_days("fri-mon"),
+            Ok(vec![Weekday::Mon, Weekday::Fri, Weekday::Sat, Weekday::Sun])
+        );
+        assert_eq!(parse_days("daily").unwrap().len(), 7);
+        assert!(parse_days("someday").is_err());
+        assert_eq!(
+            format_days(&parse_days("mon-fri").unwrap()),
+            "mon,tue,wed,thu,fri"
+        );
+        assert_eq!(format_days(&parse_days("mon-sun").unwrap()), "daily");
+    }
+
+    #[test]
+    fn test_parse_maintenance_window() {
+        let window = parse_maintenance_window("sun 02:00-03:30 Europe/Berlin").unwrap();
+        assert_eq!(window.days, vec![Weekday::Sun]);
+        assert_eq!(window.start_time, NaiveTime::from_hms_opt(2, 0, 0).unwrap());
+        assert_eq!(window.duration_mins, 90);
+        assert_eq!(window.timezone, chrono_tz::Europe::Berlin);
+
+        let overnight = parse_maintenance_window("daily 23:30-00:30").unwrap();
+        assert_eq!(overnight.duration_mins, 60);
+        assert_eq!(overnight.timezone, chrono_tz::UTC);
+
+        assert!(parse_maintenance_window("sun 02:00").is_err());
+        assert!(parse_maintenance_window("sun 02:00-02:00").is_err());
+        assert!(parse_maintenance_window("sun 25:00-26:00").is_err());
+        assert!(parse_maintenance_window("sun 02:00-03:00 Mars/Olympus").is_err());
+    }
+
+    #[test]
+    fn test_parse_pause_duration() {
+        assert_eq!(
+            parse_pause_duration("2h"),
+            Ok(std::time::Duration::from_secs(7200))
+        );
+        assert!(parse_pause_duration("30s").is_err());
+        assert!(parse_pause_duration("31d").is_err());
+        assert!(parse_pause_duration("soon").is_err());
+    }
+
+    #[test]
+    fn test_is_window_active_in_timezone() {
+        let start = NaiveTime::from_hms_opt(2, 0, 0).unwrap();
+        let berlin = chrono_tz::Europe::Berlin;
+        // 2026-10-18 is a Sunday; Berlin is UTC+2 until October 25th
+        let at = |h, m| Utc.with_ymd_and_hms(2026, 10, 18, h, m, 0).unwrap();
+
+        let active =
+            |now| is_window_active(&[Weekday::Sun], start, Duration::hours(1), berlin, now);
+        assert!(!active(at(2, 30)));
+        assert!(active(at(0, 0)));
+        assert!(active(at(0, 59)));
+        assert!(!active(at(1, 0)));
+    }
+
+    #[test]
+    fn test_is_window_active_past_midnight() {
+        let start = NaiveTime::from_hms_opt(23, 30, 0).unwrap();
+        let saturday_night = |h, m| Utc.with_ymd_and_hms(2026, 10, 18, h, m, 0).unwrap();
+
+        let active = |now| {
+            is_window_active(
+                &[Weekday::Sat],
+                start,
+                Duration::hours(1),
+                chrono_tz::UTC,
+                now,
+            )
+        };
+        assert!(active(saturday_night(0, 15)));
+        assert!(!active(saturday_night(0, 30)));
+        assert!(!active(saturday_night(23, 45)));
+    }
+}
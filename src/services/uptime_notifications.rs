@@ -0,0 +1,106 @@
+use notifine::db::DbPool;
+use teloxide::prelude::*;
+use teloxide::types::ParseMode;
+use teloxide::RequestError;
+
+use crate::observability::telegram_errors::{
+    classify_telegram_error, get_retry_after_seconds, handle_telegram_error, TelegramErrorKind,
+};
+use crate::observability::METRICS;
+use crate::services::broadcast::db::{handle_bot_removed, migrate_chat_id};
+use crate::services::broadcast::types::BotType;
+
+async fn send(
+    bot: &Bot,
+    telegram_chat_id: i64,
+    thread_id: Option<i32>,
+    message: &str,
+) -> Result<(), RequestError> {
+    let mut request = bot
+        .send_message(ChatId(telegram_chat_id), message)
+        .parse_mode(ParseMode::Html);
+    if let Some(thread_id) = thread_id {
+        request = request.message_thread_id(thread_id);
+    }
+
+    request.await?;
+    METRICS.increment_messages_sent_for_bot("uptime");
+
+    Ok(())
+}
+
+/// Sends an HTML notification of the uptime bot to the chat's topic. Migrated groups
+/// are followed and rate limits waited out once; chats that blocked or removed the
+/// bot are marked unreachable so they are no longer messaged.
+pub async fn send_uptime_notification(
+    pool: &DbPool,
+    bot: &Bot,
+    telegram_chat_id: i64,
+    thread_id: Option<i32>,
+    message: &str,
+) -> Result<(), RequestError> {
+    let Err(error) = send(bot, telegram_chat_id, thread_id, message).await else {
+        return Ok(());
+    };
+
+    let kind = classify_telegram_error(&error);
+    let retried = match kind {
+        TelegramErrorKind::GroupMigrated { new_chat_id } => {
+            match migrate_chat_id(pool, telegram_chat_id, new_chat_id) {
+                Ok(_) => tracing::info!(
+                    "Chat {} migrated to {}, retrying uptime notification",
+                    telegram_chat_id,
+                    new_chat_id
+                ),
+                Err(e) => {
+                    tracing::error!("Failed to migrate chat {}: {:?}", telegram_chat_id, e);
+                    METRICS.increment_errors();
+                }
+            }
+            Some(send(bot, new_chat_id, thread_id, message).await)
+        }
+        TelegramErrorKind::RateLimited => {
+            let retry_after = get_retry_after_seconds(&error).unwrap_or(5);
+            tokio::time::sleep(std::time::Duration::from_secs(retry_after)).await;
+            Some(send(bot, telegram_chat_id, thread_id, message).await)
+        }
+        TelegramErrorKind::BotBlocked
+        | TelegramErrorKind::ChatNotFound
+        | TelegramErrorKind::NotEnoughRights => {
+            if let Err(e) = handle_bot_removed(pool, telegram_chat_id, BotType::Uptime) {
+                tracing::error!(
+                    "Failed to mark uptime bot unreachable for chat {}: {:?}",
+                    telegram_chat_id,
+                    e
+                );
+                METRICS.increment_errors();
+            }
+            None
+        }
+        TelegramErrorKind::NetworkError | TelegramErrorKind::Other => None,
+    };
+
+    match retried {
+        Some(Ok(())) => Ok(()),
+        Some(Err(retry_error)) => {
+            handle_telegram_error(
+                bot,
+                &retry_error,
+                telegram_chat_id,
+                "retrying an uptime notification",
+            )
+            .await;
+            Err(retry_error)
+        }
+        None => {
+            handle_telegram_error(
+                bot,
+                &error,
+                telegram_chat_id,
+                "sending an uptime notification",
+            )
+            .await;
+            Err(error)
+        }
+    }
+}
@@ -0,0 +1,570 @@
+use crate::services::uptime_checker::{FailureReason, HealthResult};
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::redirect::Policy;
+use std::net::{IpAddr, SocketAddr};
+use std::time::{Duration, Instant};
+use tokio::io::AsyncReadExt;
+use tokio::net::{lookup_host, TcpStream};
+use tokio::time::timeout;
+use url::{Host, Url};
+
+/// Banners are matched within the first bytes the server sends.
+const MAX_BANNER_BYTES: usize = 1024;
+const MAX_EXPECTED_ADDRESSES: usize = 10;
+const MAX_REDIRECTS: usize = 10;
+
+/// What kind of check a monitor runs, taken from the scheme of its URL:
+/// `tcp://host:port` and `dns://name` are probes, anything else is HTTP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonitorKind {
+    Http,
+    Tcp,
+    Dns,
+}
+
+impl MonitorKind {
+    pub fn from_url(url: &str) -> MonitorKind {
+        let url = url.trim().to_lowercase();
+        if url.starts_with("tcp://") {
+            MonitorKind::Tcp
+        } else if url.starts_with("dns://") {
+            MonitorKind::Dns
+        } else {
+            MonitorKind::Http
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MonitorKind::Http => "HTTP",
+            MonitorKind::Tcp => "TCP",
+            MonitorKind::Dns => "DNS",
+        }
+    }
+}
+
+fn host(url: &Url) -> Option<String> {
+    match url.host()? {
+        Host::Domain(domain) if !domain.is_empty() => Some(domain.to_string()),
+        Host::Domain(_) => None,
+        Host::Ipv4(ip) => Some(ip.to_string()),
+        Host::Ipv6(ip) => Some(ip.to_string()),
+    }
+}
+
+/// Parses `tcp://host:port` into the address to connect to.
+pub fn parse_tcp_target(url: &str) -> Result<(String, u16), String> {
+    let invalid = || format!("Invalid TCP target '{}'. Use tcp://host:port", url);
+    let parsed = Url::parse(url.trim()).map_err(|_| invalid())?;
+
+    Ok((
+        host(&parsed).ok_or_else(invalid)?,
+        parsed.port().ok_or_else(invalid)?,
+    ))
+}
+
+/// Parses `dns://name` into the name to resolve.
+pub fn parse_dns_target(url: &str) -> Result<String, String> {
+    let invalid = || format!("Invalid DNS target '{}'. Use dns://example.com", url);
+    let parsed = Url::parse(url.trim()).map_err(|_| invalid())?;
+
+    host(&parsed).ok_or_else(invalid)
+}
+
+/// Parses the `--expect` addresses of DNS monitors, e.g. `93.184.216.34,2606:2800::1`.
+pub fn parse_expected_addresses(value: &str) -> Result<Vec<IpAddr>, String> {
+    let addresses = value
+        .split(',')
+        .map(|address| {
+            address
+                .trim()
+                .parse()
+                .map_err(|_| format!("Invalid IP address '{}'.", address.trim()))
+        })
+        .collect::<Result<Vec<IpAddr>, _>>()?;
+
+    if addresses.len() > MAX_EXPECTED_ADDRESSES {
+        return Err(format!(
+            "At most {} expected addresses are allowed.",
+            MAX_EXPECTED_ADDRESSES
+        ));
+    }
+
+    Ok(addresses)
+}
+
+/// Whether the address is on the public internet. Probes must not reach into the
+/// server's own network, e.g. the cloud metadata service at 169.254.169.254.
+pub fn is_public_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [first, second, ..] = ip.octets();
+            !(ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || ip.is_documentation()
+                || first == 0
+                // Carrier-grade NAT, 100.64.0.0/10
+                || (first == 100 && second & 0xc0 == 64))
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public_address(IpAddr::V4(ip)),
+            None => {
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    || ip.is_unique_local()
+                    || ip.is_unicast_link_local())
+            }
+        },
+    }
+}
+
+fn private_target_message(host: &str) -> String {
+    format!(
+        "{} resolves to a private or local address, which can't be monitored",
+        host
+    )
+}
+
+/// Refuses HTTP URLs whose host is a private or local IP address. Hostnames are checked
+/// by [`PublicResolver`] when the request resolves them.
+pub fn check_public_url(url: &Url) -> Result<(), String> {
+    let ip = match url.host() {
+        Some(Host::Ipv4(ip)) => IpAddr::V4(ip),
+        Some(Host::Ipv6(ip)) => IpAddr::V6(ip),
+        _ => return Ok(()),
+    };
+
+    if is_public_address(ip) {
+        Ok(())
+    } else {
+        Err(private_target_message(&ip.to_string()))
+    }
+}
+
+/// DNS resolver for HTTP monitors that fails for hosts with private or local addresses,
+/// including hosts reached through redirects.
+pub struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs = lookup_host((name.as_str(), 0)).await?.collect::<Vec<_>>();
+            if addrs.iter().any(|addr| !is_public_address(addr.ip())) {
+                return Err(private_target_message(name.as_str()).into());
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Follows redirects like reqwest does by default, but not to private or local IP addresses.
+pub fn public_redirect_policy() -> Policy {
+    Policy::custom(|attempt| {
+        if attempt.previous().len() >= MAX_REDIRECTS {
+            attempt.error("too many redirects")
+        } else if let Err(e) = check_public_url(attempt.url()) {
+            attempt.error(e)
+        } else {
+            attempt.follow()
+        }
+    })
+}
+
+fn passed(duration: Duration) -> HealthResult {
+    HealthResult {
+        success: true,
+        status_code: 0,
+        duration,
+        failure_reason: None,
+        error_message: None,
+        certificate: None,
+    }
+}
+
+fn failed(duration: Duration, reason: FailureReason, message: String) -> HealthResult {
+    HealthResult {
+        success: false,
+        status_code: 0,
+        duration,
+        failure_reason: Some(reason),
+        error_message: Some(message),
+        certificate: None,
+    }
+}
+
+/// Reads from the connection until `banner` shows up, the server stops sending or
+/// `MAX_BANNER_BYTES` were read. Returns what was received.
+async fn read_banner(stream: &mut TcpStream, banner: &str) -> std::io::Result<String> {
+    let mut received = Vec::new();
+    let mut buf = [0u8; 256];
+
+    while received.len() < MAX_BANNER_BYTES {
+        let read = stream.read(&mut buf).await?;
+        if read == 0 {
+            break;
+        }
+        received.extend_from_slice(&buf[..read]);
+        if String::from_utf8_lossy(&received).contains(banner) {
+            break;
+        }
+    }
+
+    Ok(String::from_utf8_lossy(&received).into_owned())
+}
+
+/// Connects to `tcp://host:port` and, when `banner` is set, waits for the server to
+/// greet with text containing it (e.g. `SSH-2.0` or `220`). Hosts that resolve to
+/// private or local addresses are refused.
+pub async fn check_tcp(
+    url: &str,
+    banner: Option<&str>,
+    timeout_duration: Duration,
+) -> HealthResult {
+    let start = Instant::now();
+    let (host, port) = match parse_tcp_target(url) {
+        Ok(target) => target,
+        Err(e) => return failed(start.elapsed(), FailureReason::RequestError, e),
+    };
+
+    let addrs = match timeout(timeout_duration, lookup_host((host.as_str(), port))).await {
+        Ok(Ok(addrs)) => addrs.collect::<Vec<_>>(),
+        Ok(Err(e)) => {
+            return failed(
+                start.elapsed(),
+                FailureReason::RequestError,
+                format!("resolution failed: {}", e),
+            )
+        }
+        Err(_) => {
+            return failed(
+                start.elapsed(),
+                FailureReason::Timeout,
+                format!("Timeout (no answer in {}s)", timeout_duration.as_secs()),
+            )
+        }
+    };
+    if addrs.iter().any(|addr| !is_public_address(addr.ip())) {
+        return failed(
+            start.elapsed(),
+            FailureReason::RequestError,
+            private_target_message(&host),
+        );
+    }
+
+    probe_tcp(&addrs, banner, timeout_duration, start).await
+}
+
+/// Connects to the already checked addresses, so a changed DNS answer can't redirect it.
+async fn probe_tcp(
+    addrs: &[SocketAddr],
+    banner: Option<&str>,
+    timeout_duration: Duration,
+    start: Instant,
+) -> HealthResult {
+    let probe = async {
+        let mut stream = TcpStream::connect(addrs).await?;
+        match banner {
+            Some(banner) => read_banner(&mut stream, banner).await.map(Some),
+            None => Ok(None),
+        }
+    };
+
+    match timeout(timeout_duration.saturating_sub(start.elapsed()), probe).await {
+        Ok(Ok(received)) => {
+            let duration = start.elapsed();
+            match (banner, received) {
+                // The received bytes are not echoed, the port may belong to anything
+                (Some(banner), Some(received)) if !received.contains(banner) => failed(
+                    duration,
+                    FailureReason::AssertionFailed,
+                    format!("Banner did not contain '{}'", banner),
+                ),
+                _ => passed(duration),
+            }
+        }
+        Ok(Err(e)) => failed(
+            start.elapsed(),
+            FailureReason::RequestError,
+            format!("connection failed: {}", e),
+        ),
+        Err(_) => failed(
+            start.elapsed(),
+            FailureReason::Timeout,
+            format!(
+                "Timeout (no {} in {}s)",
+                if banner.is_some() {
+                    "banner"
+                } else {
+                    "connection"
+                },
+                timeout_duration.as_secs()
+            ),
+        ),
+    }
+}
+
+/// Resolves `dns://name` with the system resolver and, when `expected` is set,
+/// requires every listed address to be among the A/AAAA records. Other record types
+/// are not checked. Names that resolve to private or local addresses are refused.
+pub async fn check_dns(
+    url: &str,
+    expected: Option<&str>,
+    timeout_duration: Duration,
+) -> HealthResult {
+    let start = Instant::now();
+    let name = match parse_dns_target(url) {
+        Ok(name) => name,
+        Err(e) => return failed(start.elapsed(), FailureReason::RequestError, e),
+    };
+    let expected = match expected.map(parse_expected_addresses).transpose() {
+        Ok(expected) => expected.unwrap_or_default(),
+        Err(e) => return failed(start.elapsed(), FailureReason::RequestError, e),
+    };
+
+    let resolved = match timeout(timeout_duration, lookup_host((name.as_str(), 0))).await {
+        Ok(Ok(addrs)) => addrs.map(|addr| addr.ip()).collect::<Vec<_>>(),
+        Ok(Err(e)) => {
+            return failed(
+                start.elapsed(),
+                FailureReason::RequestError,
+                format!("resolution failed: {}", e),
+            )
+        }
+        Err(_) => {
+            return failed(
+                start.elapsed(),
+                FailureReason::Timeout,
+                format!("Timeout (no answer in {}s)", timeout_duration.as_secs()),
+            )
+        }
+    };
+    let duration = start.elapsed();
+
+    if resolved.iter().any(|ip| !is_public_address(*ip)) {
+        return failed(
+            duration,
+            FailureReason::RequestError,
+            private_target_message(&name),
+        );
+    }
+
+    compare_addresses(&name, &resolved, &expected, duration)
+}
+
+fn compare_addresses(
+    name: &str,
+    resolved: &[IpAddr],
+    expected: &[IpAddr],
+    duration: Duration,
+) -> HealthResult {
+    if resolved.is_empty() {
+        return failed(
+            duration,
+            FailureReason::RequestError,
+            format!("{} has no address records", name),
+        );
+    }
+
+    let missing: Vec<String> = expected
+        .iter()
+        .filter(|address| !resolved.contains(address))
+        .map(IpAddr::to_string)
+        .collect();
+    if !missing.is_empty() {
+        let mut resolved: Vec<String> = resolved.iter().map(IpAddr::to_string).collect();
+        resolved.sort();
+        resolved.dedup();
+        return failed(
+            duration,
+            FailureReason::AssertionFailed,
+            format!(
+                "{} resolved to {} (expected {})",
+                name,
+                resolved.join(", "),
+                missing.join(", ")
+            ),
+        );
+    }
+
+    passed(duration)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_monitor_kind_from_url() {
+        assert_eq!(MonitorKind::from_url("tcp://db:5432"), MonitorKind::Tcp);
+        assert_eq!(MonitorKind::from_url("DNS://example.com"), MonitorKind::Dns);
+        assert_eq!(
+            MonitorKind::from_url("https://example.com"),
+            MonitorKind::Http
+        );
+    }
+
+    #[test]
+    fn test_parse_targets() {
+        assert_eq!(
+            parse_tcp_target("tcp://db.example.com:5432"),
+            Ok(("db.example.com".to_string(), 5432))
+        );
+        assert_eq!(
+            parse_tcp_target("tcp://[::1]:6379"),
+            Ok(("::1".to_string(), 6379))
+        );
+        assert!(parse_tcp_target("tcp://db.example.com").is_err());
+        assert_eq!(
+            parse_dns_target("dns://example.com"),
+            Ok("example.com".to_string())
+        );
+        assert!(parse_dns_target("dns://").is_err());
+    }
+
+    #[test]
+    fn test_parse_expected_addresses() {
+        assert_eq!(parse_expected_addresses("127.0.0.1, ::1").unwrap().len(), 2);
+        assert!(parse_expected_addresses("example.com").is_err());
+    }
+
+    #[test]
+    fn test_is_public_address() {
+        for address in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!is_public_address(address.parse().unwrap()), "{}", address);
+        }
+        assert!(is_public_address("93.184.216.34".parse().unwrap()));
+        assert!(is_public_address("2606:2800:220:1::1".parse().unwrap()));
+    }
+
+    async fn greeting_server(greeting: &'static [u8]) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let _ = socket.write_all(greeting).await;
+            }
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_probe_tcp() {
+        let addrs = [greeting_server(b"SSH-2.0-OpenSSH_9.6\r\n").await];
+        let timeout = Duration::from_secs(2);
+
+        assert!(
+            probe_tcp(&addrs, None, timeout, Instant::now())
+                .await
+                .success
+        );
+        assert!(
+            probe_tcp(&addrs, Some("SSH-2.0"), timeout, Instant::now())
+                .await
+                .success
+        );
+
+        let result = probe_tcp(&addrs, Some("220"), timeout, Instant::now()).await;
+        assert_eq!(result.failure_reason, Some(FailureReason::AssertionFailed));
+        assert_eq!(
+            result.error_message.as_deref(),
+            Some("Banner did not contain '220'")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_probe_tcp_connection_refused() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addrs = [listener.local_addr().unwrap()];
+        drop(listener);
+
+        let result = probe_tcp(&addrs, None, Duration::from_secs(2), Instant::now()).await;
+        assert_eq!(result.failure_reason, Some(FailureReason::RequestError));
+    }
+
+    #[test]
+    fn test_check_public_url() {
+        for url in [
+            "http://127.0.0.1:8080/health",
+            "http://169.254.169.254/latest/meta-data",
+            "http://[::1]/",
+        ] {
+            assert!(
+                check_public_url(&Url::parse(url).unwrap()).is_err(),
+                "{}",
+                url
+            );
+        }
+        assert!(check_public_url(&Url::parse("https://93.184.216.34/").unwrap()).is_ok());
+        assert!(check_public_url(&Url::parse("https://example.com/").unwrap()).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_public_resolver_refuses_local_hosts() {
+        let error = PublicResolver
+            .resolve("localhost".parse().unwrap())
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "localhost resolves to a private or local address, which can't be monitored"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_private_targets_are_refused() {
+        let addr = greeting_server(b"SSH-2.0-OpenSSH_9.6\r\n").await;
+        let timeout = Duration::from_secs(2);
+
+        let result = check_tcp(&format!("tcp://{}", addr), None, timeout).await;
+        assert_eq!(
+            result.error_message.as_deref(),
+            Some("127.0.0.1 resolves to a private or local address, which can't be monitored")
+        );
+
+        let result = check_tcp("tcp://169.254.169.254:80", None, timeout).await;
+        assert!(!result.success);
+        assert_eq!(result.failure_reason, Some(FailureReason::RequestError));
+
+        let result = check_dns("dns://127.0.0.1", None, timeout).await;
+        assert_eq!(
+            result.error_message.as_deref(),
+            Some("127.0.0.1 resolves to a private or local address, which can't be monitored")
+        );
+    }
+
+    #[test]
+    fn test_compare_addresses() {
+        let duration = Duration::from_millis(5);
+        let resolved: Vec<IpAddr> = vec!["93.184.216.34".parse().unwrap()];
+
+        assert!(compare_addresses("example.com", &resolved, &resolved, duration).success);
+
+        let expected = vec!["93.184.216.35".parse().unwrap()];
+        let result = compare_addresses("example.com", &resolved, &expected, duration);
+        assert_eq!(result.failure_reason, Some(FailureReason::AssertionFailed));
+        assert_eq!(
+            result.error_message.as_deref(),
+            Some("example.com resolved to 93.184.216.34 (expected 93.184.216.35)")
+        );
+    }
+}
@@ -0,0 +1,590 @@
+use crate::services::uptime_probes::{
+    parse_dns_target, parse_expected_addresses, parse_tcp_target, MonitorKind,
+};
+use crate::utils::crypto::{decrypt_secret, encrypt_secret, CryptoError};
+use notifine::models::{HealthUrl, HealthUrlRequest};
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::{Client, Method, RequestBuilder};
+use std::ops::RangeInclusive;
+
+const ALLOWED_METHODS: [&str; 7] = ["GET", "HEAD", "POST", "PUT", "PATCH", "DELETE", "OPTIONS"];
+const MAX_HEADERS: usize = 10;
+const MAX_BODY_CHARS: usize = 4096;
+
+pub const NEW_USAGE: &str = "Usage: /new <url> [options]\n\n\
+    HTTP options:\n\
+    --method POST - HTTP method (default GET)\n\
+    --header \"Name: value\" - request header, can be repeated\n\
+    --body '{\"ping\": true}' - request body\n\
+    --auth user:password - basic authentication\n\
+    --status 200-299,401 - accepted status codes (default 2xx)\n\n\
+    TCP and DNS monitors:\n\
+    /new tcp://host:port [--banner SSH-2.0] - connect, optionally wait for a greeting\n\
+    /new dns://name [--expect 1.2.3.4] - resolve A/AAAA records, optionally require addresses\n\
+    Private and local addresses can't be monitored.\n\n\
+    Example:\n\
+    /new https://api.example.com/health --header \"Authorization: Bearer abc\" --status 200,204";
+
+pub const EDIT_USAGE: &str = "Usage: /edit <id> <url> [options]\n\n\
+    Changes the URL and request of a monitor and keeps its ID, settings and history. \
+    The options are the same as for /new and replace the current ones.\n\n\
+    Example:\n\
+    /edit 12 https://api.example.com/v2/health --status 200,204";
+
+pub const RENAME_USAGE: &str = "Usage: /rename <id> <label>\n\n\
+    The label is shown in alerts and the list instead of the URL. \
+    Send /rename <id> without a label to remove it.\n\n\
+    Example:\n\
+    /rename 12 Payments API";
+
+const MAX_LABEL_CHARS: usize = 100;
+
+/// The request a monitor sends, parsed from the stored `HealthUrl` columns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorRequest {
+    pub method: Method,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+    pub basic_auth: Option<(String, Option<String>)>,
+    pub expected_status: Vec<RangeInclusive<u16>>,
+    /// Banner of TCP monitors or comma separated addresses of DNS monitors.
+    pub expected_response: Option<String>,
+}
+
+impl Default for MonitorRequest {
+    fn default() -> Self {
+        MonitorRequest {
+            method: Method::GET,
+            headers: Vec::new(),
+            body: None,
+            basic_auth: None,
+            expected_status: vec![200..=299],
+            expected_response: None,
+        }
+    }
+}
+
+/// Parses status lists such as `200`, `200-299` or `200-299,301,401`.
+pub fn parse_status_ranges(input: &str) -> Result<Vec<RangeInclusive<u16>>, String> {
+    let invalid = || {
+        format!(
+            "Invalid status '{}'. Use codes and ranges like 200-299,401",
+            input
+        )
+    };
+
+    input
+        .split(',')
+        .map(|part| {
+            let part = part.trim();
+            let (start, end) = part.split_once('-').unwrap_or((part, part));
+            let start: u16 = start.trim().parse().map_err(|_| invalid())?;
+            let end: u16 = end.trim().parse().map_err(|_| invalid())?;
+
+            if !(100..=599).contains(&start) || !(100..=599).contains(&end) || start > end {
+                return Err(invalid());
+            }
+
+            Ok(start..=end)
+        })
+        .collect()
+}
+
+fn parse_basic_auth(value: &str) -> (String, Option<String>) {
+    match value.split_once(':') {
+        Some((user, password)) => (user.to_string(), Some(password.to_string())),
+        None => (value.to_string(), None),
+    }
+}
+
+/// Header values and basic auth may carry credentials, so they are stored encrypted
+/// with `TOKEN_ENCRYPTION_KEY`. Values saved before that are read as they are.
+fn reveal(value: &str) -> String {
+    decrypt_secret(value).unwrap_or_else(|_| value.to_string())
+}
+
+fn stored_header_values(health_url: &HealthUrl) -> impl Iterator<Item = (&String, &str)> {
+    health_url
+        .request_headers
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(name, value)| Some((name, value.as_str()?)))
+}
+
+/// Whether the monitor still has credentials from before they were stored encrypted.
+pub fn has_plaintext_secrets(health_url: &HealthUrl) -> bool {
+    health_url
+        .basic_auth
+        .iter()
+        .map(String::as_str)
+        .chain(stored_header_values(health_url).map(|(_, value)| value))
+        .any(|value| decrypt_secret(value).is_err())
+}
+
+impl MonitorRequest {
+    /// Falls back to defaults for values that can't be parsed, so a bad row never stops checks.
+    pub fn from_health_url(health_url: &HealthUrl) -> Self {
+        let defaults = MonitorRequest::default();
+
+        let headers = stored_header_values(health_url)
+            .map(|(name, value)| (name.clone(), reveal(value)))
+            .collect();
+
+        MonitorRequest {
+            method: Method::from_bytes(health_url.http_method.as_bytes())
+                .unwrap_or(defaults.method),
+            headers,
+            body: health_url.request_body.clone(),
+            basic_auth: health_url
+                .basic_auth
+                .as_deref()
+                .map(|basic_auth| parse_basic_auth(&reveal(basic_auth))),
+            expected_status: health_url
+                .expected_status
+                .as_deref()
+                .and_then(|status| parse_status_ranges(status).ok())
+                .unwrap_or(defaults.expected_status),
+            expected_response: health_url.expected_response.clone(),
+        }
+    }
+
+    /// The columns to store, with header values and basic auth encrypted. Fails when
+    /// there are credentials but no encryption key is configured.
+    pub fn to_record(&self) -> Result<HealthUrlRequest, CryptoError> {
+        self.to_record_with(encrypt_secret)
+    }
+
+    fn to_record_with(
+        &self,
+        encrypt: impl Fn(&str) -> Result<String, CryptoError>,
+    ) -> Result<HealthUrlRequest, CryptoError> {
+        let headers = self
+            .headers
+            .iter()
+            .map(|(name, value)| Ok((name.clone(), serde_json::Value::String(encrypt(value)?))))
+            .collect::<Result<serde_json::Map<String, serde_json::Value>, CryptoError>>()?;
+
+        let basic_auth = self
+            .basic_auth
+            .as_ref()
+            .map(|(user, password)| match password {
+                Some(password) => encrypt(&format!("{}:{}", user, password)),
+                None => encrypt(user),
+            })
+            .transpose()?;
+
+        Ok(HealthUrlRequest {
+            http_method: self.method.to_string(),
+            request_headers: serde_json::Value::Object(headers),
+            request_body: self.body.clone(),
+            basic_auth,
+            expected_status: (self.expected_status != MonitorRequest::default().expected_status)
+                .then(|| self.expected_status_text()),
+            expected_response: self.expected_response.clone(),
+        })
+    }
+
+    pub fn is_expected_status(&self, status_code: u16) -> bool {
+        self.expected_status
+            .iter()
+            .any(|range| range.contains(&status_code))
+    }
+
+    fn expected_status_text(&self) -> String {
+        self.expected_status
+            .iter()
+            .map(|range| {
+                if range.start() == range.end() {
+                    range.start().to_string()
+                } else {
+                    format!("{}-{}", range.start(), range.end())
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    pub fn build(&self, client: &Client, url: &str) -> RequestBuilder {
+        let mut request = client.request(self.method.clone(), url);
+
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        if let Some((user, password)) = &self.basic_auth {
+            request = request.basic_auth(user, password.as_ref());
+        }
+        if let Some(body) = &self.body {
+            request = request.body(body.clone());
+        }
+
+        request
+    }
+
+    /// Short description of non-default options, without revealing secrets.
+    pub fn describe(&self) -> Option<String> {
+        let mut parts = Vec::new();
+
+        if self.method != Method::GET {
+            parts.push(self.method.to_string());
+        }
+        if !self.headers.is_empty() {
+            let names: Vec<&str> = self.headers.iter().map(|(name, _)| name.as_str()).collect();
+            parts.push(format!("headers: {}", names.join(", ")));
+        }
+        if self.body.is_some() {
+            parts.push("with body".to_string());
+        }
+        if self.basic_auth.is_some() {
+            parts.push("basic auth".to_string());
+        }
+        if self.expected_status != MonitorRequest::default().expected_status {
+            parts.push(format!("expects {}", self.expected_status_text()));
+        }
+        if let Some(expected) = &self.expected_response {
+            parts.push(format!("expects '{}'", expected));
+        }
+
+        (!parts.is_empty()).then(|| parts.join(" · "))
+    }
+
+    /// Whether the options contain credentials that shouldn't stay visible in the chat.
+    pub fn has_secrets(&self) -> bool {
+        self.basic_auth.is_some() || !self.headers.is_empty()
+    }
+}
+
+/// Splits on whitespace, keeping single- or double-quoted sections together. Typographic
+/// quotes are accepted too, since Telegram clients often substitute them while typing.
+fn split_args(input: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+
+    for c in input.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => current.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            (None, '“') => {
+                quote = Some('”');
+                in_arg = true;
+            }
+            (None, '‘') => {
+                quote = Some('’');
+                in_arg = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+
+    if quote.is_some() {
+        return Err("Unclosed quote.".to_string());
+    }
+    if in_arg {
+        args.push(current);
+    }
+
+    Ok(args)
+}
+
+/// TCP and DNS monitors don't make HTTP requests, so only their own option applies.
+fn validate_options_for_kind(
+    url: &str,
+    request: &MonitorRequest,
+    options: &[String],
+) -> Result<(), String> {
+    let kind = MonitorKind::from_url(url);
+    let allowed: &[&str] = match kind {
+        MonitorKind::Http => &[
+            "--method", "-X", "--header", "-H", "--body", "-d", "--auth", "-u", "--status",
+        ],
+        MonitorKind::Tcp => &["--banner"],
+        MonitorKind::Dns => &["--expect"],
+    };
+
+    if let Some(option) = options
+        .iter()
+        .find(|option| !allowed.contains(&option.as_str()))
+    {
+        return Err(format!(
+            "{} can't be used with {} monitors.",
+            option,
+            kind.as_str()
+        ));
+    }
+
+    match kind {
+        MonitorKind::Http => {}
+        MonitorKind::Tcp => {
+            parse_tcp_target(url)?;
+        }
+        MonitorKind::Dns => {
+            parse_dns_target(url)?;
+            if let Some(expected) = &request.expected_response {
+                parse_expected_addresses(expected)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses the arguments of `/new`: the URL followed by request options.
+pub fn parse_new_monitor_args(input: &str) -> Result<(String, MonitorRequest), String> {
+    let args = split_args(input)?;
+    let mut args = args.into_iter();
+    let url = args
+        .next()
+        .ok_or_else(|| "Please provide a URL.".to_string())?;
+    let mut request = MonitorRequest::default();
+    let mut options = Vec::new();
+
+    while let Some(option) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {}.", option))
+        };
+
+        match option.as_str() {
+            "--method" | "-X" => {
+                let method = value()?.to_uppercase();
+                if !ALLOWED_METHODS.contains(&method.as_str()) {
+                    return Err(format!("Unsupported method '{}'.", method));
+                }
+                request.method = Method::from_bytes(method.as_bytes())
+                    .map_err(|_| format!("Unsupported method '{}'.", method))?;
+            }
+            "--header" | "-H" => {
+                let header = value()?;
+                let (name, header_value) = header
+                    .split_once(':')
+                    .ok_or_else(|| format!("Invalid header '{}'. Use \"Name: value\".", header))?;
+                let (name, header_value) = (name.trim(), header_value.trim());
+                HeaderName::from_bytes(name.as_bytes())
+                    .map_err(|_| format!("Invalid header name '{}'.", name))?;
+                HeaderValue::from_str(header_value)
+                    .map_err(|_| format!("Invalid value for header '{}'.", name))?;
+
+                if request.headers.len() >= MAX_HEADERS {
+                    return Err(format!("At most {} headers are allowed.", MAX_HEADERS));
+                }
+                request
+                    .headers
+                    .push((name.to_string(), header_value.to_string()));
+            }
+            "--body" | "-d" => {
+                let body = value()?;
+                if body.chars().count() > MAX_BODY_CHARS {
+                    return Err(format!(
+                        "The body can be at most {} characters.",
+                        MAX_BODY_CHARS
+                    ));
+                }
+                request.body = Some(body);
+            }
+            "--auth" | "-u" => request.basic_auth = Some(parse_basic_auth(&value()?)),
+            "--status" => request.expected_status = parse_status_ranges(&value()?)?,
+            "--banner" | "--expect" => request.expected_response = Some(value()?),
+            _ => return Err(format!("Unknown option '{}'.", option)),
+        }
+        options.push(option);
+    }
+
+    validate_options_for_kind(&url, &request, &options)?;
+
+    Ok((url, request))
+}
+
+/// Parses the arguments of `/edit`: the monitor's ID followed by the arguments of `/new`.
+pub fn parse_edit_monitor_args(input: &str) -> Result<(i32, String, MonitorRequest), String> {
+    let input = input.trim_start();
+    let (id, rest) = input.split_once(char::is_whitespace).unwrap_or((input, ""));
+    let id = id
+        .parse::<i32>()
+        .map_err(|_| "Please provide the numeric ID of the endpoint.".to_string())?;
+    let (url, request) = parse_new_monitor_args(rest)?;
+
+    Ok((id, url, request))
+}
+
+/// Parses the arguments of `/rename`: the monitor's ID and the new label, if any.
+pub fn parse_rename_args(input: &str) -> Result<(i32, Option<String>), String> {
+    let input = input.trim();
+    let (id, label) = input.split_once(char::is_whitespace).unwrap_or((input, ""));
+    let id = id
+        .parse::<i32>()
+        .map_err(|_| "Please provide the numeric ID of the endpoint.".to_string())?;
+
+    let label = label.split_whitespace().collect::<Vec<_>>().join(" ");
+    if label.chars().count() > MAX_LABEL_CHARS {
+        return Err(format!(
+            "The label can be at most {} characters.",
+            MAX_LABEL_CHARS
+        ));
+    }
+
+    Ok((id, (!label.is_empty()).then_some(label)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::crypto::{decrypt_with_key, encrypt_with_key, parse_key};
+
+    const TEST_KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    #[test]
+    fn test_parse_plain_url() {
+        let (url, request) = parse_new_monitor_args("https://example.com").unwrap();
+
+        assert_eq!(url, "https://example.com");
+        assert_eq!(request, MonitorRequest::default());
+        assert_eq!(request.describe(), None);
+    }
+
+    #[test]
+    fn test_parse_typographic_quotes() {
+        let (_, request) =
+            parse_new_monitor_args("https://example.com -H “X-Token: a b” --body ‘{}’").unwrap();
+
+        assert_eq!(
+            request.headers,
+            vec![("X-Token".to_string(), "a b".to_string())]
+        );
+        assert_eq!(request.body.as_deref(), Some("{}"));
+    }
+
+    #[test]
+    fn test_parse_all_options() {
+        let (url, request) = parse_new_monitor_args(
+            r#"https://api.example.com/ping --method post --header "Authorization: Bearer abc def" -H 'X-Env: prod' --body '{"ping": true}' --auth bot:s3cret --status 200-299,401"#,
+        )
+        .unwrap();
+
+        assert_eq!(url, "https://api.example.com/ping");
+        assert_eq!(request.method, Method::POST);
+        assert_eq!(
+            request.headers,
+            vec![
+                ("Authorization".to_string(), "Bearer abc def".to_string()),
+                ("X-Env".to_string(), "prod".to_string()),
+            ]
+        );
+        assert_eq!(request.body.as_deref(), Some(r#"{"ping": true}"#));
+        assert_eq!(
+            request.basic_auth,
+            Some(("bot".to_string(), Some("s3cret".to_string())))
+        );
+        assert!(request.is_expected_status(401));
+        assert!(!request.is_expected_status(404));
+        assert!(request.has_secrets());
+        assert_eq!(
+            request.describe().unwrap(),
+            "POST · headers: Authorization, X-Env · with body · basic auth · expects 200-299,401"
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_options() {
+        assert!(parse_new_monitor_args("").is_err());
+        assert!(parse_new_monitor_args("https://a.com --method TRACE").is_err());
+        assert!(parse_new_monitor_args("https://a.com --header NoColon").is_err());
+        assert!(parse_new_monitor_args("https://a.com --status 700").is_err());
+        assert!(parse_new_monitor_args("https://a.com --status 299-200").is_err());
+        assert!(parse_new_monitor_args("https://a.com --body").is_err());
+        assert!(parse_new_monitor_args("https://a.com --body 'open").is_err());
+        assert!(parse_new_monitor_args("https://a.com --verbose").is_err());
+    }
+
+    #[test]
+    fn test_parse_edit_monitor_args() {
+        let (id, url, request) =
+            parse_edit_monitor_args("12 https://example.com/v2 --status 200,204").unwrap();
+        assert_eq!(id, 12);
+        assert_eq!(url, "https://example.com/v2");
+        assert!(request.is_expected_status(204));
+
+        assert!(parse_edit_monitor_args("").is_err());
+        assert!(parse_edit_monitor_args("12").is_err());
+        assert!(parse_edit_monitor_args("https://example.com").is_err());
+    }
+
+    #[test]
+    fn test_parse_rename_args() {
+        assert_eq!(
+            parse_rename_args("12  Payments   API ").unwrap(),
+            (12, Some("Payments API".to_string()))
+        );
+        assert_eq!(parse_rename_args("12").unwrap(), (12, None));
+        assert!(parse_rename_args("Payments API").is_err());
+        assert!(parse_rename_args(&format!("12 {}", "a".repeat(101))).is_err());
+    }
+
+    #[test]
+    fn test_parse_tcp_and_dns_monitors() {
+        let (url, request) =
+            parse_new_monitor_args("tcp://mail.example.com:25 --banner 220").unwrap();
+        assert_eq!(url, "tcp://mail.example.com:25");
+        assert_eq!(request.expected_response.as_deref(), Some("220"));
+        assert_eq!(request.describe().unwrap(), "expects '220'");
+
+        let (_, request) =
+            parse_new_monitor_args("dns://example.com --expect 93.184.216.34").unwrap();
+        assert_eq!(
+            request.to_record().unwrap().expected_response.as_deref(),
+            Some("93.184.216.34")
+        );
+
+        assert!(parse_new_monitor_args("tcp://db.example.com").is_err());
+        assert!(parse_new_monitor_args("tcp://db:5432 --method POST").is_err());
+        assert!(parse_new_monitor_args("dns://example.com --expect nope").is_err());
+        assert!(parse_new_monitor_args("https://a.com --banner 220").is_err());
+    }
+
+    #[test]
+    fn test_record_round_trip() {
+        let key = parse_key(TEST_KEY).unwrap();
+        let encrypt = |value: &str| encrypt_with_key(&key, value);
+        let (_, request) = parse_new_monitor_args(
+            "https://a.com -X PUT -H 'Authorization: Bearer abc' -u admin:hunter2 --status 204",
+        )
+        .unwrap();
+        let record = request.to_record_with(encrypt).unwrap();
+
+        assert_eq!(record.http_method, "PUT");
+        assert_eq!(record.expected_status.as_deref(), Some("204"));
+
+        // Credentials are only stored encrypted
+        let basic_auth = record.basic_auth.unwrap();
+        assert!(!basic_auth.contains("hunter2"));
+        assert_eq!(
+            decrypt_with_key(&key, &basic_auth).unwrap(),
+            "admin:hunter2"
+        );
+        let authorization = record.request_headers["Authorization"].as_str().unwrap();
+        assert!(!authorization.contains("abc"));
+        assert_eq!(decrypt_with_key(&key, authorization).unwrap(), "Bearer abc");
+
+        assert_eq!(
+            MonitorRequest::default()
+                .to_record_with(encrypt)
+                .unwrap()
+                .expected_status,
+            None
+        );
+    }
+}
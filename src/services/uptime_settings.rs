@@ -0,0 +1,158 @@
+use notifine::models::{HealthUrl, HealthUrlSettings};
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+use crate::services::uptime_latency::{format_latency_threshold, parse_latency_threshold};
+use crate::services::uptime_tls::{format_warning_days, parse_warning_days};
+use crate::utils::duration::{format_duration, parse_duration};
+
+const INTERVAL_RANGE: RangeInclusive<u64> = 30..=86400;
+const TIMEOUT_RANGE: RangeInclusive<u64> = 1..=60;
+const RETRIES_RANGE: RangeInclusive<i32> = 0..=5;
+const CONFIRMATION_RANGE: RangeInclusive<i32> = 1..=10;
+
+pub const SETTINGS_USAGE: &str =
+    "Usage: /settings <id> [interval=5m] [timeout=10s] [retries=2] [confirm=3] [recover=2] [tls=30,14,7,1] [latency=2s]\n\n\
+    interval - how often the endpoint is checked (30s to 1d)\n\
+    timeout - how long to wait for a response (1s to 60s)\n\
+    retries - quick retries within a check on timeouts and connection errors (0 to 5)\n\
+    confirm - failed checks in a row before you are alerted (1 to 10)\n\
+    recover - successful checks in a row before it counts as recovered (1 to 10)\n\
+    tls - days before an HTTPS certificate expires to warn you, or off\n\
+    latency - response time above which the endpoint counts as degraded, e.g. 2s, 800ms, 3x its usual response time, or off\n\n\
+    Send /settings <id> without options to see the current settings.";
+
+fn parse_secs(value: &str, range: RangeInclusive<u64>, name: &str) -> Result<i32, String> {
+    let secs = parse_duration(value).map(|d| d.as_secs()).ok_or_else(|| {
+        format!(
+            "Invalid {} '{}'. Use values like 30s, 5m or 1h.",
+            name, value
+        )
+    })?;
+
+    if !range.contains(&secs) {
+        return Err(format!(
+            "{} must be between {} and {}.",
+            name,
+            format_duration(Duration::from_secs(*range.start())),
+            format_duration(Duration::from_secs(*range.end()))
+        ));
+    }
+
+    Ok(secs as i32)
+}
+
+fn parse_count(value: &str, range: RangeInclusive<i32>, name: &str) -> Result<i32, String> {
+    let count: i32 = value
+        .parse()
+        .map_err(|_| format!("Invalid {} '{}'. Use a whole number.", name, value))?;
+
+    if !range.contains(&count) {
+        return Err(format!(
+            "{} must be between {} and {}.",
+            name,
+            range.start(),
+            range.end()
+        ));
+    }
+
+    Ok(count)
+}
+
+/// Parses `key=value` options of the `/settings` command.
+pub fn parse_monitor_settings(args: &[&str]) -> Result<HealthUrlSettings, String> {
+    let mut settings = HealthUrlSettings::default();
+
+    for arg in args {
+        let (key, value) = arg
+            .split_once('=')
+            .ok_or_else(|| format!("Expected key=value, got '{}'.", arg))?;
+
+        match key.to_lowercase().as_str() {
+            "interval" => {
+                settings.check_interval_secs = Some(parse_secs(value, INTERVAL_RANGE, "interval")?)
+            }
+            "timeout" => settings.timeout_secs = Some(parse_secs(value, TIMEOUT_RANGE, "timeout")?),
+            "retries" => settings.max_retries = Some(parse_count(value, RETRIES_RANGE, "retries")?),
+            "confirm" => {
+                settings.confirmation_threshold =
+                    Some(parse_count(value, CONFIRMATION_RANGE, "confirm")?)
+            }
+            "recover" => {
+                settings.recovery_threshold =
+                    Some(parse_count(value, CONFIRMATION_RANGE, "recover")?)
+            }
+            "tls" => settings.tls_warning_days = Some(parse_warning_days(value)?),
+            "latency" => settings.latency_threshold = Some(parse_latency_threshold(value)?),
+            _ => return Err(format!("Unknown setting '{}'.", key)),
+        }
+    }
+
+    Ok(settings)
+}
+
+pub fn format_monitor_settings(health_url: &HealthUrl) -> String {
+    format!(
+        "Checked every {}, timeout {}, {} retries, alert after {} failed checks in a row, recovered after {} successful checks in a row. {}. {}.",
+        format_duration(Duration::from_secs(health_url.check_interval_secs as u64)),
+        format_duration(Duration::from_secs(health_url.timeout_secs as u64)),
+        health_url.max_retries,
+        health_url.confirmation_threshold,
+        health_url.recovery_threshold,
+        format_warning_days(&health_url.tls_warning_days),
+        format_latency_threshold(&health_url.latency_threshold)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_monitor_settings() {
+        let settings = parse_monitor_settings(&[
+            "interval=5m",
+            "timeout=15s",
+            "retries=0",
+            "confirm=3",
+            "recover=2",
+            "tls=14,3",
+            "latency=3x",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            settings,
+            HealthUrlSettings {
+                check_interval_secs: Some(300),
+                timeout_secs: Some(15),
+                max_retries: Some(0),
+                confirmation_threshold: Some(3),
+                recovery_threshold: Some(2),
+                tls_warning_days: Some("14,3".to_string()),
+                latency_threshold: Some("3x".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_monitor_settings_partial() {
+        let settings = parse_monitor_settings(&["confirm=2"]).unwrap();
+
+        assert_eq!(settings.confirmation_threshold, Some(2));
+        assert_eq!(settings.check_interval_secs, None);
+    }
+
+    #[test]
+    fn test_parse_monitor_settings_rejects_invalid() {
+        assert!(parse_monitor_settings(&["interval=10s"]).is_err());
+        assert!(parse_monitor_settings(&["timeout=2m"]).is_err());
+        assert!(parse_monitor_settings(&["retries=9"]).is_err());
+        assert!(parse_monitor_settings(&["confirm=0"]).is_err());
+        assert!(parse_monitor_settings(&["recover=11"]).is_err());
+        assert!(parse_monitor_settings(&["color=blue"]).is_err());
+        assert!(parse_monitor_settings(&["tls=0"]).is_err());
+        assert!(parse_monitor_settings(&["latency=1x"]).is_err());
+        assert!(parse_monitor_settings(&["interval"]).is_err());
+    }
+}
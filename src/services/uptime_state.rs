@@ -0,0 +1,215 @@
+use chrono::{DateTime, Duration, Utc};
+use notifine::models::HealthUrlCheckState;
+
+/// Confirmed up/down changes are counted within this window to detect flapping.
+const FLAP_WINDOW: Duration = Duration::hours(1);
+/// A monitor is flapping once it changed state this many times within the window,
+/// i.e. went down and recovered twice.
+const FLAP_TRANSITIONS: i32 = 4;
+/// A flapping monitor is stable again after this long without a state change.
+pub const FLAP_STABLE_AFTER: Duration = Duration::minutes(30);
+
+#[derive(Debug, PartialEq)]
+pub enum Transition {
+    None,
+    WentDown,
+    Recovered,
+    /// Up/down alerts are collapsed into this notice while the monitor flaps.
+    StartedFlapping,
+    /// The monitor has settled; `is_down` of the new state tells in which state.
+    StoppedFlapping,
+}
+
+/// Computes the monitor's state after a check. A monitor is only reported down once
+/// `confirmation_threshold` checks in a row failed, and up again once
+/// `recovery_threshold` checks in a row succeeded. Rapid up/down changes are
+/// reported once as flapping instead of as individual alerts.
+pub fn next_check_state(
+    state: &HealthUrlCheckState,
+    confirmation_threshold: i32,
+    recovery_threshold: i32,
+    success: bool,
+    now: DateTime<Utc>,
+) -> (HealthUrlCheckState, Transition) {
+    let mut next = state.clone();
+    let mut changed = false;
+
+    if success {
+        next.consecutive_failures = 0;
+        next.consecutive_successes = state.consecutive_successes.saturating_add(1);
+        if state.is_down && next.consecutive_successes >= recovery_threshold.max(1) {
+            next.is_down = false;
+            changed = true;
+        }
+    } else {
+        next.consecutive_successes = 0;
+        next.consecutive_failures = state.consecutive_failures.saturating_add(1);
+        if !state.is_down && next.consecutive_failures >= confirmation_threshold.max(1) {
+            next.is_down = true;
+            changed = true;
+        }
+    }
+
+    if !changed {
+        let settled = state.is_flapping
+            && state
+                .last_transition_at
+                .is_none_or(|at| now - at >= FLAP_STABLE_AFTER);
+        if settled {
+            next.is_flapping = false;
+            next.flap_window_started_at = None;
+            next.flap_transitions = 0;
+            return (next, Transition::StoppedFlapping);
+        }
+        return (next, Transition::None);
+    }
+
+    next.last_transition_at = Some(now);
+    match state.flap_window_started_at {
+        Some(started) if now - started <= FLAP_WINDOW => {
+            next.flap_transitions = state.flap_transitions.saturating_add(1);
+        }
+        _ => {
+            next.flap_window_started_at = Some(now);
+            next.flap_transitions = 1;
+        }
+    }
+
+    if state.is_flapping {
+        return (next, Transition::None);
+    }
+    if next.flap_transitions >= FLAP_TRANSITIONS {
+        next.is_flapping = true;
+        return (next, Transition::StartedFlapping);
+    }
+
+    let transition = if next.is_down {
+        Transition::WentDown
+    } else {
+        Transition::Recovered
+    };
+    (next, transition)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 21, 12, 0, 0).unwrap()
+    }
+
+    fn state(consecutive_failures: i32, is_down: bool) -> HealthUrlCheckState {
+        HealthUrlCheckState {
+            consecutive_failures,
+            is_down,
+            ..Default::default()
+        }
+    }
+
+    /// `(consecutive_failures, is_down, transition)` after a check with recovery after one success.
+    fn step(
+        consecutive_failures: i32,
+        is_down: bool,
+        confirmation_threshold: i32,
+        success: bool,
+    ) -> (i32, bool, Transition) {
+        let (next, transition) = next_check_state(
+            &state(consecutive_failures, is_down),
+            confirmation_threshold,
+            1,
+            success,
+            now(),
+        );
+        (next.consecutive_failures, next.is_down, transition)
+    }
+
+    #[test]
+    fn test_next_check_state_waits_for_confirmation() {
+        assert_eq!(step(0, false, 3, false), (1, false, Transition::None));
+        assert_eq!(step(1, false, 3, false), (2, false, Transition::None));
+        assert_eq!(step(2, false, 3, false), (3, true, Transition::WentDown));
+        assert_eq!(step(3, true, 3, false), (4, true, Transition::None));
+        assert_eq!(step(4, true, 3, true), (0, false, Transition::Recovered));
+    }
+
+    #[test]
+    fn test_next_check_state_resets_on_success() {
+        assert_eq!(step(2, false, 3, true), (0, false, Transition::None));
+        assert_eq!(step(0, false, 1, false), (1, true, Transition::WentDown));
+    }
+
+    #[test]
+    fn test_next_check_state_waits_for_recovery() {
+        let down = state(5, true);
+
+        let (next, transition) = next_check_state(&down, 1, 2, true, now());
+        assert_eq!(transition, Transition::None);
+        assert!(next.is_down);
+        assert_eq!(next.consecutive_successes, 1);
+
+        let (next, transition) = next_check_state(&next, 1, 2, true, now());
+        assert_eq!(transition, Transition::Recovered);
+        assert!(!next.is_down);
+
+        // A failure in between starts the count over
+        let (next, _) = next_check_state(&down, 1, 2, true, now());
+        let (next, _) = next_check_state(&next, 1, 2, false, now());
+        let (next, transition) = next_check_state(&next, 1, 2, true, now());
+        assert_eq!(transition, Transition::None);
+        assert!(next.is_down);
+    }
+
+    #[test]
+    fn test_flapping_collapses_alerts_until_stable() {
+        let mut current = HealthUrlCheckState::default();
+        let mut transitions = Vec::new();
+
+        for minute in 0..6 {
+            let at = now() + Duration::minutes(minute * 5);
+            let (next, transition) = next_check_state(&current, 1, 1, minute % 2 == 1, at);
+            transitions.push(transition);
+            current = next;
+        }
+
+        assert_eq!(
+            transitions,
+            vec![
+                Transition::WentDown,
+                Transition::Recovered,
+                Transition::WentDown,
+                Transition::StartedFlapping,
+                Transition::None,
+                Transition::None,
+            ]
+        );
+        assert!(current.is_flapping);
+
+        // Still up, but not for long enough
+        let last_change = now() + Duration::minutes(25);
+        let (current, transition) =
+            next_check_state(&current, 1, 1, true, last_change + Duration::minutes(10));
+        assert_eq!(transition, Transition::None);
+
+        let (current, transition) =
+            next_check_state(&current, 1, 1, true, last_change + FLAP_STABLE_AFTER);
+        assert_eq!(transition, Transition::StoppedFlapping);
+        assert!(!current.is_flapping);
+        assert!(!current.is_down);
+        assert_eq!(current.flap_transitions, 0);
+    }
+
+    #[test]
+    fn test_slow_changes_are_not_flapping() {
+        let mut current = HealthUrlCheckState::default();
+
+        for hour in 0..6 {
+            let at = now() + Duration::hours(hour);
+            let (next, transition) = next_check_state(&current, 1, 1, hour % 2 == 1, at);
+            assert_ne!(transition, Transition::StartedFlapping);
+            current = next;
+        }
+        assert!(!current.is_flapping);
+    }
+}
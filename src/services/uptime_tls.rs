@@ -0,0 +1,384 @@
+use chrono::{DateTime, Duration, Utc};
+use html_escape::encode_text;
+use notifine::models::{HealthUrl, HealthUrlTlsState};
+use openssl::asn1::Asn1Time;
+use openssl::nid::Nid;
+use openssl::x509::{X509NameRef, X509};
+use std::ops::RangeInclusive;
+
+pub const DEFAULT_WARNING_DAYS: &str = "30,14,7,1";
+const WARNING_DAYS_RANGE: RangeInclusive<i32> = 1..=365;
+const MAX_WARNING_THRESHOLDS: usize = 6;
+const MAX_SHOWN_NAMES: usize = 10;
+
+/// The parts of an HTTPS monitor's peer certificate that expiry warnings need.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CertificateInfo {
+    pub expires_at: DateTime<Utc>,
+    pub issuer: String,
+    pub names: Vec<String>,
+}
+
+fn name_entry(name: &X509NameRef, nid: Nid) -> Option<String> {
+    name.entries_by_nid(nid)
+        .next()
+        .and_then(|entry| entry.data().as_utf8().ok())
+        .map(|value| value.to_string())
+}
+
+/// Parses a DER encoded certificate, as exposed by reqwest's `TlsInfo`.
+pub fn parse_certificate(der: &[u8]) -> Option<CertificateInfo> {
+    let cert = X509::from_der(der).ok()?;
+
+    let since_epoch = Asn1Time::from_unix(0).ok()?.diff(cert.not_after()).ok()?;
+    let expires_at =
+        DateTime::from_timestamp(since_epoch.days as i64 * 86400 + since_epoch.secs as i64, 0)?;
+
+    let issuer_name = cert.issuer_name();
+    let issuer = name_entry(issuer_name, Nid::ORGANIZATIONNAME)
+        .or_else(|| name_entry(issuer_name, Nid::COMMONNAME))
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let names = cert
+        .subject_alt_names()
+        .map(|names| {
+            names
+                .iter()
+                .filter_map(|name| name.dnsname().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(CertificateInfo {
+        expires_at,
+        issuer,
+        names,
+    })
+}
+
+/// Parses the `tls=` option of `/settings`: a comma separated list of days such as
+/// `30,14,7,1`, or `off`. Returns the normalized value to store.
+pub fn parse_warning_days(value: &str) -> Result<String, String> {
+    if matches!(value.to_lowercase().as_str(), "off" | "none") {
+        return Ok(String::new());
+    }
+
+    let mut days = value
+        .split(',')
+        .map(|day| {
+            let day: i32 = day
+                .trim()
+                .parse()
+                .map_err(|_| format!("Invalid tls '{}'. Use days like 30,14,7,1 or off.", value))?;
+            if !WARNING_DAYS_RANGE.contains(&day) {
+                return Err(format!(
+                    "tls days must be between {} and {}.",
+                    WARNING_DAYS_RANGE.start(),
+                    WARNING_DAYS_RANGE.end()
+                ));
+            }
+            Ok(day)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    days.sort_unstable_by(|a, b| b.cmp(a));
+    days.dedup();
+
+    if days.len() > MAX_WARNING_THRESHOLDS {
+        return Err(format!(
+            "At most {} tls thresholds are supported.",
+            MAX_WARNING_THRESHOLDS
+        ));
+    }
+
+    Ok(days
+        .iter()
+        .map(|day| day.to_string())
+        .collect::<Vec<_>>()
+        .join(","))
+}
+
+fn warning_thresholds(value: &str) -> Vec<i32> {
+    value
+        .split(',')
+        .filter_map(|day| day.trim().parse().ok())
+        .collect()
+}
+
+pub fn format_warning_days(value: &str) -> String {
+    let days = warning_thresholds(value);
+    if days.is_empty() {
+        return "TLS expiry warnings are off".to_string();
+    }
+
+    let days: Vec<String> = days.iter().map(|day| day.to_string()).collect();
+    format!("TLS expiry warnings {} days before expiry", days.join(", "))
+}
+
+/// Compares a freshly seen certificate with the stored state. Returns the state to
+/// store, if it changed, and the threshold to warn about, if one was newly crossed.
+/// A certificate that expires later than the stored one counts as renewed, so its
+/// warnings start over.
+pub fn next_tls_state(
+    health_url: &HealthUrl,
+    cert: &CertificateInfo,
+    now: DateTime<Utc>,
+) -> (Option<HealthUrlTlsState>, Option<i32>) {
+    let renewed = health_url
+        .tls_expires_at
+        .is_none_or(|stored| cert.expires_at > stored);
+    let last_warning_days = if renewed {
+        None
+    } else {
+        health_url.tls_last_warning_days
+    };
+
+    let days_left = days_left(cert, now);
+    let warning = warning_thresholds(&health_url.tls_warning_days)
+        .into_iter()
+        .filter(|days| days_left <= *days as i64)
+        .min()
+        .filter(|days| last_warning_days.is_none_or(|last| *days < last));
+
+    let state = HealthUrlTlsState {
+        tls_expires_at: Some(cert.expires_at),
+        tls_issuer: Some(cert.issuer.clone()),
+        tls_last_warning_days: warning.or(last_warning_days),
+    };
+    let changed = health_url.tls_expires_at != state.tls_expires_at
+        || health_url.tls_issuer != state.tls_issuer
+        || health_url.tls_last_warning_days != state.tls_last_warning_days;
+
+    (changed.then_some(state), warning)
+}
+
+fn days_left(cert: &CertificateInfo, now: DateTime<Utc>) -> i64 {
+    (cert.expires_at - now).num_days()
+}
+
+pub fn format_certificate_warning(
+    name: &str,
+    cert: &CertificateInfo,
+    now: DateTime<Utc>,
+) -> String {
+    let remaining = cert.expires_at - now;
+    let expires_in = if remaining < Duration::days(1) {
+        "in less than a day".to_string()
+    } else if remaining < Duration::days(2) {
+        "in 1 day".to_string()
+    } else {
+        format!("in {} days", remaining.num_days())
+    };
+
+    let mut names = cert
+        .names
+        .iter()
+        .take(MAX_SHOWN_NAMES)
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ");
+    if cert.names.len() > MAX_SHOWN_NAMES {
+        names.push_str(&format!(" (+{} more)", cert.names.len() - MAX_SHOWN_NAMES));
+    }
+    if names.is_empty() {
+        names = "none".to_string();
+    }
+
+    format!(
+        "<b>[CERT]</b> The TLS certificate for {} expires {} ({}).\n<b>Issuer:</b> {}\n<b>Names:</b> {}\nRenew it before then, or checks will start failing.",
+        encode_text(name),
+        expires_in,
+        cert.expires_at.format("%Y-%m-%d %H:%M UTC"),
+        encode_text(&cert.issuer),
+        encode_text(&names)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use diesel::pg::data_types::PgTimestamp;
+    use openssl::ec::{EcGroup, EcKey};
+    use openssl::hash::MessageDigest;
+    use openssl::pkey::PKey;
+    use openssl::x509::extension::SubjectAlternativeName;
+    use openssl::x509::X509NameBuilder;
+
+    fn health_url(
+        tls_expires_at: Option<DateTime<Utc>>,
+        tls_last_warning_days: Option<i32>,
+    ) -> HealthUrl {
+        HealthUrl {
+            id: 1,
+            url: "https://example.com".to_string(),
+            chat_id: 1,
+            status_code: 200,
+            created_at: PgTimestamp(0),
+            updated_at: PgTimestamp(0),
+            check_interval_secs: 60,
+            timeout_secs: 10,
+            max_retries: 2,
+            confirmation_threshold: 1,
+            consecutive_failures: 0,
+            is_down: false,
+            http_method: "GET".to_string(),
+            request_headers: serde_json::json!({}),
+            request_body: None,
+            basic_auth: None,
+            expected_status: None,
+            tls_expires_at,
+            tls_issuer: Some("Let's Encrypt".to_string()),
+            tls_warning_days: DEFAULT_WARNING_DAYS.to_string(),
+            tls_last_warning_days,
+            expected_response: None,
+            recovery_threshold: 1,
+            consecutive_successes: 0,
+            is_flapping: false,
+            flap_window_started_at: None,
+            flap_transitions: 0,
+            last_transition_at: None,
+            paused_at: None,
+            paused_until: None,
+            maintenance_started_at: None,
+            show_on_status_page: false,
+            label: None,
+            latency_threshold: String::new(),
+            is_degraded: false,
+            latency_streak: 0,
+            degraded_since: None,
+            target_changed_at: None,
+        }
+    }
+
+    fn cert(expires_at: DateTime<Utc>) -> CertificateInfo {
+        CertificateInfo {
+            expires_at,
+            issuer: "Let's Encrypt".to_string(),
+            names: vec!["example.com".to_string()],
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 20, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_certificate() {
+        let key = PKey::from_ec_key(
+            EcKey::generate(&EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap()).unwrap(),
+        )
+        .unwrap();
+        let mut issuer = X509NameBuilder::new().unwrap();
+        issuer
+            .append_entry_by_nid(Nid::ORGANIZATIONNAME, "Test CA")
+            .unwrap();
+        let issuer = issuer.build();
+
+        let mut builder = X509::builder().unwrap();
+        builder.set_version(2).unwrap();
+        builder.set_issuer_name(&issuer).unwrap();
+        builder.set_subject_name(&issuer).unwrap();
+        builder.set_pubkey(&key).unwrap();
+        builder
+            .set_not_before(&Asn1Time::from_unix(1_790_000_000).unwrap())
+            .unwrap();
+        builder
+            .set_not_after(&Asn1Time::from_unix(1_800_000_000).unwrap())
+            .unwrap();
+        let names = SubjectAlternativeName::new()
+            .dns("example.com")
+            .dns("www.example.com")
+            .build(&builder.x509v3_context(None, None))
+            .unwrap();
+        builder.append_extension(names).unwrap();
+        builder.sign(&key, MessageDigest::sha256()).unwrap();
+        let der = builder.build().to_der().unwrap();
+
+        assert_eq!(
+            parse_certificate(&der),
+            Some(CertificateInfo {
+                expires_at: DateTime::from_timestamp(1_800_000_000, 0).unwrap(),
+                issuer: "Test CA".to_string(),
+                names: vec!["example.com".to_string(), "www.example.com".to_string()],
+            })
+        );
+        assert_eq!(parse_certificate(b"not a certificate"), None);
+    }
+
+    #[test]
+    fn test_parse_warning_days() {
+        assert_eq!(parse_warning_days("7,30,1,14,7").unwrap(), "30,14,7,1");
+        assert_eq!(parse_warning_days("off").unwrap(), "");
+        assert!(parse_warning_days("0").is_err());
+        assert!(parse_warning_days("30,soon").is_err());
+        assert!(parse_warning_days("1,2,3,4,5,6,7").is_err());
+    }
+
+    #[test]
+    fn test_format_warning_days() {
+        assert_eq!(
+            format_warning_days(DEFAULT_WARNING_DAYS),
+            "TLS expiry warnings 30, 14, 7, 1 days before expiry"
+        );
+        assert_eq!(format_warning_days(""), "TLS expiry warnings are off");
+    }
+
+    #[test]
+    fn test_next_tls_state_warns_once_per_threshold() {
+        let expires_at = now() + Duration::days(10);
+
+        // First sighting already inside the 14 day window: one warning, not two
+        let (state, warning) = next_tls_state(&health_url(None, None), &cert(expires_at), now());
+        assert_eq!(warning, Some(14));
+        assert_eq!(state.unwrap().tls_last_warning_days, Some(14));
+
+        // Same certificate, same window: nothing to store or send
+        let (state, warning) = next_tls_state(
+            &health_url(Some(expires_at), Some(14)),
+            &cert(expires_at),
+            now(),
+        );
+        assert_eq!((state, warning), (None, None));
+
+        // Crossing into the 7 day window warns again
+        let (_, warning) = next_tls_state(
+            &health_url(Some(expires_at), Some(14)),
+            &cert(expires_at),
+            now() + Duration::days(3),
+        );
+        assert_eq!(warning, Some(7));
+    }
+
+    #[test]
+    fn test_next_tls_state_resets_after_renewal() {
+        let old_expiry = now() + Duration::days(2);
+        let new_expiry = now() + Duration::days(90);
+
+        let (state, warning) = next_tls_state(
+            &health_url(Some(old_expiry), Some(7)),
+            &cert(new_expiry),
+            now(),
+        );
+        assert_eq!(warning, None);
+        assert_eq!(
+            state,
+            Some(HealthUrlTlsState {
+                tls_expires_at: Some(new_expiry),
+                tls_issuer: Some("Let's Encrypt".to_string()),
+                tls_last_warning_days: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_format_certificate_warning() {
+        let mut cert = cert(now() + Duration::days(7) + Duration::hours(3));
+        cert.names.push("www.example.com".to_string());
+
+        assert_eq!(
+            format_certificate_warning("https://example.com", &cert, now()),
+            "<b>[CERT]</b> The TLS certificate for https://example.com expires in 7 days (2026-10-27 15:00 UTC).\n<b>Issuer:</b> Let's Encrypt\n<b>Names:</b> example.com, www.example.com\nRenew it before then, or checks will start failing."
+        );
+    }
+}
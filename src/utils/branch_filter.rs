@@ -18,34 +18,45 @@ impl BranchFilter {
     }
 
     pub fn should_process(&self, branch: &str) -> bool {
-        // If no filters are specified, process all branches
-        if self.include_patterns.is_empty() && self.exclude_patterns.is_empty() {
-            return true;
-        }
+        matches_patterns(&self.include_patterns, &self.exclude_patterns, branch)
+    }
+}
 
-        // Check exclusions first - if branch matches any exclude pattern, don't process
-        for pattern in &self.exclude_patterns {
-            if pattern.matches(branch) {
-                return false;
-            }
-        }
+/// Shared include/exclude matching used by both branch and tag filters.
+pub(crate) fn matches_patterns(
+    include_patterns: &[Pattern],
+    exclude_patterns: &[Pattern],
+    name: &str,
+) -> bool {
+    // If no filters are specified, process everything
+    if include_patterns.is_empty() && exclude_patterns.is_empty() {
+        return true;
+    }
 
-        // If we have include patterns, branch must match at least one
-        if !self.include_patterns.is_empty() {
-            for pattern in &self.include_patterns {
-                if pattern.matches(branch) {
-                    return true;
-                }
-            }
+    // Check exclusions first - if name matches any exclude pattern, don't process
+    for pattern in exclude_patterns {
+        if pattern.matches(name) {
             return false;
         }
+    }
 
-        // If we only have exclude patterns and branch didn't match any, process it
-        true
+    // If we have include patterns, name must match at least one
+    if !include_patterns.is_empty() {
+        for pattern in include_patterns {
+            if pattern.matches(name) {
+                return true;
+            }
+        }
+        return false;
     }
+
+    // If we only have exclude patterns and name didn't match any, process it
+    true
 }
 
-fn parse_patterns(patterns_str: Option<&str>) -> Result<Vec<Pattern>, glob::PatternError> {
+pub(crate) fn parse_patterns(
+    patterns_str: Option<&str>,
+) -> Result<Vec<Pattern>, glob::PatternError> {
     match patterns_str {
         Some(s) if !s.is_empty() => s
             .split(',')
@@ -0,0 +1,56 @@
+use notifine::db::DbPool;
+use notifine::find_chat_by_telegram_chat_id;
+use teloxide::prelude::*;
+use teloxide::types::{Chat, Message};
+
+/// Private chats are always allowed; in groups only administrators may change
+/// chat settings or trigger actions with the chat's token.
+pub async fn is_chat_admin(bot: &Bot, chat: &Chat, user_id: UserId) -> bool {
+    if chat.is_private() {
+        return true;
+    }
+
+    match bot.get_chat_member(chat.id, user_id).await {
+        Ok(member) => member.is_privileged(),
+        Err(e) => {
+            tracing::warn!("Failed to check chat member {}: {}", user_id, e);
+            false
+        }
+    }
+}
+
+/// Checks that the sender of a command is a chat administrator, replying with
+/// `denied` when they aren't.
+pub async fn ensure_chat_admin(bot: &Bot, msg: &Message, denied: &str) -> ResponseResult<bool> {
+    let Some(user) = msg.from() else {
+        return Ok(false);
+    };
+
+    if !is_chat_admin(bot, &msg.chat, user.id).await {
+        bot.send_message(msg.chat.id, denied).await?;
+        return Ok(false);
+    }
+
+    Ok(true)
+}
+
+/// The id of the chat's row, replying to the user when the chat isn't set up yet.
+pub async fn find_chat_id(bot: &Bot, msg: &Message, pool: &DbPool) -> ResponseResult<Option<i32>> {
+    match find_chat_by_telegram_chat_id(pool, &msg.chat.id.0.to_string()) {
+        Ok(Some(chat)) => Ok(Some(chat.id)),
+        Ok(None) => {
+            bot.send_message(
+                msg.chat.id,
+                "This chat has no webhook yet. Send /start first.",
+            )
+            .await?;
+            Ok(None)
+        }
+        Err(e) => {
+            tracing::error!("Failed to find chat {}: {:?}", msg.chat.id.0, e);
+            bot.send_message(msg.chat.id, "Failed to load chat settings.")
+                .await?;
+            Ok(None)
+        }
+    }
+}
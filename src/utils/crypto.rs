@@ -0,0 +1,126 @@
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use std::env;
+
+const NONCE_LENGTH: usize = 12;
+
+#[derive(Debug)]
+pub enum CryptoError {
+    MissingKey,
+    InvalidKey,
+    InvalidCiphertext,
+}
+
+impl std::fmt::Display for CryptoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CryptoError::MissingKey => write!(f, "TOKEN_ENCRYPTION_KEY not set"),
+            CryptoError::InvalidKey => {
+                write!(f, "TOKEN_ENCRYPTION_KEY must be 64 hex characters")
+            }
+            CryptoError::InvalidCiphertext => write!(f, "Failed to decrypt value"),
+        }
+    }
+}
+
+impl std::error::Error for CryptoError {}
+
+pub(crate) fn parse_key(hex_key: &str) -> Result<Key<Aes256Gcm>, CryptoError> {
+    let bytes = hex::decode(hex_key.trim()).map_err(|_| CryptoError::InvalidKey)?;
+    if bytes.len() != 32 {
+        return Err(CryptoError::InvalidKey);
+    }
+    Ok(*Key::<Aes256Gcm>::from_slice(&bytes))
+}
+
+fn key_from_env() -> Result<Key<Aes256Gcm>, CryptoError> {
+    let hex_key = env::var("TOKEN_ENCRYPTION_KEY").map_err(|_| CryptoError::MissingKey)?;
+    parse_key(&hex_key)
+}
+
+pub(crate) fn encrypt_with_key(
+    key: &Key<Aes256Gcm>,
+    plaintext: &str,
+) -> Result<String, CryptoError> {
+    let cipher = Aes256Gcm::new(key);
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_bytes())
+        .map_err(|_| CryptoError::InvalidCiphertext)?;
+
+    let mut combined = nonce.to_vec();
+    combined.extend_from_slice(&ciphertext);
+    Ok(BASE64.encode(combined))
+}
+
+pub(crate) fn decrypt_with_key(key: &Key<Aes256Gcm>, encoded: &str) -> Result<String, CryptoError> {
+    let combined = BASE64
+        .decode(encoded)
+        .map_err(|_| CryptoError::InvalidCiphertext)?;
+    if combined.len() <= NONCE_LENGTH {
+        return Err(CryptoError::InvalidCiphertext);
+    }
+
+    let (nonce, ciphertext) = combined.split_at(NONCE_LENGTH);
+    let plaintext = Aes256Gcm::new(key)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| CryptoError::InvalidCiphertext)?;
+
+    String::from_utf8(plaintext).map_err(|_| CryptoError::InvalidCiphertext)
+}
+
+/// Encrypts a secret with AES-256-GCM using `TOKEN_ENCRYPTION_KEY`. The result is
+/// base64 of the random nonce followed by the ciphertext.
+pub fn encrypt_secret(plaintext: &str) -> Result<String, CryptoError> {
+    encrypt_with_key(&key_from_env()?, plaintext)
+}
+
+pub fn decrypt_secret(encoded: &str) -> Result<String, CryptoError> {
+    decrypt_with_key(&key_from_env()?, encoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    #[test]
+    fn test_encrypt_decrypt_roundtrip() {
+        let key = parse_key(TEST_KEY).unwrap();
+        let encrypted = encrypt_with_key(&key, "ghp_secret").unwrap();
+
+        assert_ne!(encrypted, "ghp_secret");
+        assert_eq!(decrypt_with_key(&key, &encrypted).unwrap(), "ghp_secret");
+    }
+
+    #[test]
+    fn test_nonce_is_random() {
+        let key = parse_key(TEST_KEY).unwrap();
+
+        assert_ne!(
+            encrypt_with_key(&key, "ghp_secret").unwrap(),
+            encrypt_with_key(&key, "ghp_secret").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_tampered_ciphertext_is_rejected() {
+        let key = parse_key(TEST_KEY).unwrap();
+        let mut bytes = BASE64
+            .decode(encrypt_with_key(&key, "ghp_secret").unwrap())
+            .unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+
+        assert!(decrypt_with_key(&key, &BASE64.encode(bytes)).is_err());
+    }
+
+    #[test]
+    fn test_invalid_key() {
+        assert!(parse_key("abcd").is_err());
+        assert!(parse_key("not hex").is_err());
+    }
+}
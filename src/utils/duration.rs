@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+/// Parses durations such as `45`, `30s`, `5m`, `2h` or `1d`. A bare number means seconds.
+pub fn parse_duration(input: &str) -> Option<Duration> {
+    let input = input.trim().to_lowercase();
+    let split = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    let (value, unit) = input.split_at(split);
+    let value: u64 = value.parse().ok()?;
+
+    let multiplier = match unit {
+        "" | "s" | "sec" | "secs" => 1,
+        "m" | "min" | "mins" => 60,
+        "h" | "hr" | "hrs" => 3600,
+        "d" | "day" | "days" => 86400,
+        _ => return None,
+    };
+
+    value.checked_mul(multiplier).map(Duration::from_secs)
+}
+
+/// Formats a duration compactly using its two largest units, e.g. `1h 30m` or `45s`.
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let units = [(86400, "d"), (3600, "h"), (60, "m"), (1, "s")];
+
+    let parts: Vec<String> = units
+        .iter()
+        .scan(secs, |remaining, &(size, label)| {
+            let value = *remaining / size;
+            *remaining %= size;
+            Some((value, label))
+        })
+        .filter(|(value, _)| *value > 0)
+        .take(2)
+        .map(|(value, label)| format!("{}{}", value, label))
+        .collect();
+
+    if parts.is_empty() {
+        "0s".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("45"), Some(Duration::from_secs(45)));
+        assert_eq!(parse_duration("30s"), Some(Duration::from_secs(30)));
+        assert_eq!(parse_duration("5m"), Some(Duration::from_secs(300)));
+        assert_eq!(parse_duration("25h"), Some(Duration::from_secs(90000)));
+        assert_eq!(parse_duration("1D"), Some(Duration::from_secs(86400)));
+        assert_eq!(parse_duration("m"), None);
+        assert_eq!(parse_duration("5w"), None);
+        assert_eq!(parse_duration(""), None);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(0)), "0s");
+        assert_eq!(format_duration(Duration::from_secs(45)), "45s");
+        assert_eq!(format_duration(Duration::from_secs(5400)), "1h 30m");
+        assert_eq!(format_duration(Duration::from_secs(90061)), "1d 1h");
+    }
+}
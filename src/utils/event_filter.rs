@@ -0,0 +1,114 @@
+use super::branch_filter::{matches_patterns, parse_patterns};
+use glob::Pattern;
+
+/// Include/exclude filter for event names. Opt-in events are only processed when an
+/// include pattern names them. Listing opt-in events by name adds them to the
+/// delivered events instead of limiting the others to the list.
+#[derive(Debug)]
+pub struct EventFilter {
+    include_patterns: Vec<Pattern>,
+    opt_in_patterns: Vec<Pattern>,
+    exclude_patterns: Vec<Pattern>,
+    opt_in_events: &'static [&'static str],
+}
+
+fn is_wildcard(pattern: &Pattern) -> bool {
+    pattern.as_str().contains(['*', '?', '['])
+}
+
+impl EventFilter {
+    pub fn new(
+        include: Option<&str>,
+        exclude: Option<&str>,
+        opt_in_events: &'static [&'static str],
+    ) -> Result<Self, glob::PatternError> {
+        let exclude_patterns = parse_patterns(exclude)?;
+
+        let mut include_patterns = Vec::new();
+        let mut opt_in_patterns = Vec::new();
+        for pattern in parse_patterns(include)? {
+            let names_opt_in = opt_in_events.iter().any(|event| pattern.matches(event));
+            if names_opt_in {
+                opt_in_patterns.push(pattern.clone());
+            }
+            if !names_opt_in || is_wildcard(&pattern) {
+                include_patterns.push(pattern);
+            }
+        }
+
+        Ok(EventFilter {
+            include_patterns,
+            opt_in_patterns,
+            exclude_patterns,
+            opt_in_events,
+        })
+    }
+
+    pub fn should_process(&self, event: &str) -> bool {
+        if self.opt_in_events.contains(&event) {
+            return self
+                .opt_in_patterns
+                .iter()
+                .any(|pattern| pattern.matches(event))
+                && matches_patterns(&[], &self.exclude_patterns, event);
+        }
+
+        matches_patterns(&self.include_patterns, &self.exclude_patterns, event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPT_IN: &[&str] = &["discussion", "discussion_comment", "star", "fork"];
+
+    #[test]
+    fn test_no_filters() {
+        let filter = EventFilter::new(None, None, OPT_IN).unwrap();
+        assert!(filter.should_process("push"));
+        assert!(!filter.should_process("star"));
+    }
+
+    #[test]
+    fn test_include_and_exclude() {
+        let filter = EventFilter::new(
+            Some("push,star,discussion*"),
+            Some("discussion_comment"),
+            OPT_IN,
+        )
+        .unwrap();
+        assert!(filter.should_process("push"));
+        assert!(!filter.should_process("issues"));
+        assert!(filter.should_process("star"));
+        assert!(filter.should_process("discussion"));
+        assert!(!filter.should_process("discussion_comment"));
+        assert!(!filter.should_process("fork"));
+    }
+
+    #[test]
+    fn test_opt_in_events_are_additive() {
+        let filter = EventFilter::new(Some("star,fork"), None, OPT_IN).unwrap();
+        assert!(filter.should_process("push"));
+        assert!(filter.should_process("pull_request"));
+        assert!(filter.should_process("star"));
+        assert!(filter.should_process("fork"));
+        assert!(!filter.should_process("discussion"));
+    }
+
+    #[test]
+    fn test_exclude_only() {
+        let filter = EventFilter::new(None, Some("check_run"), OPT_IN).unwrap();
+        assert!(filter.should_process("push"));
+        assert!(!filter.should_process("check_run"));
+        assert!(!filter.should_process("fork"));
+    }
+
+    #[test]
+    fn test_catch_all_includes_opt_in_events() {
+        let filter = EventFilter::new(Some("*"), Some("check_run"), OPT_IN).unwrap();
+        assert!(filter.should_process("push"));
+        assert!(filter.should_process("star"));
+        assert!(!filter.should_process("check_run"));
+    }
+}
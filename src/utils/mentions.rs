@@ -0,0 +1,96 @@
+/// Extracts `@username` mentions from free text such as comment bodies or commit messages.
+///
+/// Matches are deduplicated case-insensitively and returned lowercased, since both
+/// GitHub and GitLab usernames are case-insensitive. Email addresses are skipped.
+pub fn extract_at_mentions(text: &str) -> Vec<String> {
+    let mut mentions: Vec<String> = Vec::new();
+    let mut previous: Option<char> = None;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((index, ch)) = chars.next() {
+        let preceded_by_word = previous.is_some_and(|p| p.is_alphanumeric() || p == '_');
+        previous = Some(ch);
+
+        if ch != '@' || preceded_by_word {
+            continue;
+        }
+
+        let start = index + ch.len_utf8();
+        let mut end = start;
+        while let Some(&(next_index, next)) = chars.peek() {
+            if next.is_ascii_alphanumeric() || matches!(next, '-' | '_' | '.') {
+                end = next_index + next.len_utf8();
+                previous = Some(next);
+                chars.next();
+            } else {
+                break;
+            }
+        }
+
+        // A trailing dot ends the sentence rather than the username
+        let username = text[start..end].trim_end_matches('.').to_lowercase();
+        if !username.is_empty() && !mentions.contains(&username) {
+            mentions.push(username);
+        }
+    }
+
+    mentions
+}
+
+/// Validates a Telegram username, with or without the leading `@`, and returns it without it.
+pub fn normalize_telegram_username(input: &str) -> Option<String> {
+    let username = input.trim().trim_start_matches('@');
+    let valid = (5..=32).contains(&username.len())
+        && username
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    valid.then(|| username.to_string())
+}
+
+/// Formats Telegram handles as a line appended to notifications, e.g. `cc @alice @bob`.
+pub fn format_telegram_mentions(telegram_usernames: &[String]) -> String {
+    let handles: Vec<String> = telegram_usernames
+        .iter()
+        .map(|username| format!("@{username}"))
+        .collect();
+
+    format!("cc {}", handles.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_at_mentions() {
+        let mentions = extract_at_mentions("Thanks @Alice and @bob-smith! cc @alice, ping @carol.");
+        assert_eq!(mentions, vec!["alice", "bob-smith", "carol"]);
+    }
+
+    #[test]
+    fn test_extract_at_mentions_skips_emails() {
+        assert!(extract_at_mentions("mail me at alice@example.com").is_empty());
+        assert!(extract_at_mentions("just an @ sign").is_empty());
+    }
+
+    #[test]
+    fn test_normalize_telegram_username() {
+        assert_eq!(
+            normalize_telegram_username("@alice_tg"),
+            Some("alice_tg".to_string())
+        );
+        assert_eq!(
+            normalize_telegram_username("alice_tg"),
+            Some("alice_tg".to_string())
+        );
+        assert_eq!(normalize_telegram_username("@abc"), None);
+        assert_eq!(normalize_telegram_username("@alice-tg"), None);
+    }
+
+    #[test]
+    fn test_format_telegram_mentions() {
+        let usernames = vec!["alice_tg".to_string(), "bob_tg".to_string()];
+        assert_eq!(format_telegram_mentions(&usernames), "cc @alice_tg @bob_tg");
+    }
+}
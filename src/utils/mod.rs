@@ -1,2 +1,8 @@
 pub mod branch_filter;
+pub mod chat_commands;
+pub mod crypto;
+pub mod duration;
+pub mod event_filter;
+pub mod mentions;
+pub mod tag_filter;
 pub mod telegram_admin;
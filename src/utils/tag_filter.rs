@@ -0,0 +1,54 @@
+use super::branch_filter::{matches_patterns, parse_patterns};
+use glob::Pattern;
+
+#[derive(Debug)]
+pub struct TagFilter {
+    include_patterns: Vec<Pattern>,
+    exclude_patterns: Vec<Pattern>,
+}
+
+impl TagFilter {
+    pub fn new(include: Option<&str>, exclude: Option<&str>) -> Result<Self, glob::PatternError> {
+        let include_patterns = parse_patterns(include)?;
+        let exclude_patterns = parse_patterns(exclude)?;
+
+        Ok(TagFilter {
+            include_patterns,
+            exclude_patterns,
+        })
+    }
+
+    pub fn should_process(&self, tag: &str) -> bool {
+        matches_patterns(&self.include_patterns, &self.exclude_patterns, tag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_filters() {
+        let filter = TagFilter::new(None, None).unwrap();
+        assert!(filter.should_process("latest"));
+        assert!(filter.should_process("v1.0.0"));
+    }
+
+    #[test]
+    fn test_release_tags_only() {
+        let filter = TagFilter::new(Some("v*"), Some("*-rc*")).unwrap();
+        assert!(filter.should_process("v1.0.0"));
+        assert!(filter.should_process("v2.1"));
+        assert!(!filter.should_process("v2.0.0-rc1"));
+        assert!(!filter.should_process("latest"));
+        assert!(!filter.should_process("sha-abc123"));
+    }
+
+    #[test]
+    fn test_exclude_only() {
+        let filter = TagFilter::new(None, Some("nightly,sha-*")).unwrap();
+        assert!(filter.should_process("latest"));
+        assert!(!filter.should_process("nightly"));
+        assert!(!filter.should_process("sha-abc123"));
+    }
+}
@@ -4,27 +4,33 @@ use crate::observability::telegram_errors::{
     classify_telegram_error, get_retry_after_seconds, handle_telegram_error, TelegramErrorKind,
 };
 use crate::observability::{ALERTS, METRICS};
+use crate::services::actions::db::find_chat_api_token;
+use crate::services::actions::{build_keyboard, NotificationButton};
 use crate::services::broadcast::db::{
     handle_bot_removed, migrate_chat_id, upsert_chat_bot_subscription,
 };
 use crate::services::broadcast::types::BotType;
+use crate::services::mentions::db::{find_linked_telegram_user_ids, find_telegram_usernames};
+use crate::services::mentions::Provider;
+use crate::utils::mentions::format_telegram_mentions;
 use crate::utils::telegram_admin::send_message_to_admin;
 use actix_web::HttpResponse;
 use html_escape::encode_text;
 use notifine::db::DbPool;
 use notifine::{find_chat_by_id, find_webhook_by_webhook_url};
+use teloxide::prelude::*;
 
 const TELEGRAM_MAX_MESSAGE_BYTES: usize = 4096;
 const TRUNCATION_SUFFIX: &str = "\n\n... (truncated)";
 // Reserve space for closing tags and suffix
 const SAFE_MARGIN: usize = 200;
 
-fn truncate_message(message: String) -> String {
-    if message.len() <= TELEGRAM_MAX_MESSAGE_BYTES {
+fn truncate_message(message: String, max_bytes: usize) -> String {
+    if message.len() <= max_bytes {
         return message;
     }
 
-    let max_content_bytes = TELEGRAM_MAX_MESSAGE_BYTES - TRUNCATION_SUFFIX.len() - SAFE_MARGIN;
+    let max_content_bytes = max_bytes.saturating_sub(TRUNCATION_SUFFIX.len() + SAFE_MARGIN);
 
     // Truncate by bytes, but ensure we don't cut in the middle of a UTF-8 character
     let mut truncated = String::new();
@@ -76,6 +82,74 @@ pub struct WebhookContext<'a> {
     pub token: String,
     pub event_name: &'a str,
     pub source: &'a str,
+    /// Provider usernames to ping through the chat's `/map` user mappings
+    pub mentions: Vec<String>,
+    /// Provider usernames that also get the notification as a direct message, if linked
+    pub direct_mentions: Vec<String>,
+    /// Inline keyboard buttons; action buttons are dropped unless the chat stored a token
+    pub buttons: Vec<NotificationButton>,
+}
+
+/// Sends the notification as a direct message to users who linked their account with `/link`.
+/// Only members of the webhook's chat get it, so linking an account doesn't expose events
+/// of chats the user can't read.
+async fn send_personal_notifications(
+    bot: &BotService,
+    pool: &DbPool,
+    provider: Provider,
+    usernames: &[String],
+    group_chat_id: i64,
+    message: &str,
+) {
+    let telegram_user_ids = match find_linked_telegram_user_ids(pool, provider, usernames) {
+        Ok(ids) => ids,
+        Err(e) => {
+            tracing::warn!("Failed to resolve linked users: {:?}", e);
+            return;
+        }
+    };
+
+    for telegram_user_id in telegram_user_ids {
+        // The group message already reached a private chat webhook
+        if telegram_user_id == group_chat_id {
+            continue;
+        }
+
+        let is_member = match bot
+            .bot
+            .get_chat_member(ChatId(group_chat_id), UserId(telegram_user_id as u64))
+            .await
+        {
+            Ok(member) => member.is_present(),
+            Err(e) => {
+                tracing::debug!(
+                    "Failed to check chat member {} of {}: {}",
+                    telegram_user_id,
+                    group_chat_id,
+                    e
+                );
+                false
+            }
+        };
+        if !is_member {
+            continue;
+        }
+
+        if let Err(e) = bot
+            .send_telegram_message(TelegramMessage {
+                chat_id: telegram_user_id,
+                thread_id: None,
+                message: format!("🔔 {message}"),
+            })
+            .await
+        {
+            tracing::warn!(
+                "Failed to send personal notification to {}: {}",
+                telegram_user_id,
+                e
+            );
+        }
+    }
 }
 
 pub async fn process_webhook(ctx: WebhookContext<'_>) -> HttpResponse {
@@ -172,21 +246,58 @@ pub async fn process_webhook(ctx: WebhookContext<'_>) -> HttpResponse {
         }
     };
 
-    let message = truncate_message(ctx.message);
+    let provider = Provider::parse(ctx.source);
+
+    let mut mention_line = String::new();
+    if let Some(provider) = provider.filter(|_| !ctx.mentions.is_empty()) {
+        match find_telegram_usernames(ctx.pool, chat.id, provider, &ctx.mentions) {
+            Ok(telegram_usernames) if !telegram_usernames.is_empty() => {
+                mention_line.push_str("\n\n");
+                mention_line.push_str(&format_telegram_mentions(&telegram_usernames));
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Failed to resolve mentions for chat {}: {:?}", chat_id, e),
+        }
+    }
+
+    // Truncate the body only, so the mentions always make it into the message
+    let body = truncate_message(
+        ctx.message,
+        TELEGRAM_MAX_MESSAGE_BYTES.saturating_sub(mention_line.len()),
+    );
+    let message = format!("{}{}", body, mention_line);
+
+    let has_api_token = provider.is_some_and(|provider| {
+        matches!(
+            find_chat_api_token(ctx.pool, chat.id, provider),
+            Ok(Some(_))
+        )
+    });
+    let buttons: Vec<NotificationButton> = ctx
+        .buttons
+        .into_iter()
+        .filter(|button| has_api_token || matches!(button, NotificationButton::Url { .. }))
+        .collect();
+    let keyboard = build_keyboard(&buttons);
 
     let result = bot
-        .send_telegram_message(TelegramMessage {
-            chat_id: telegram_chat_id,
-            thread_id,
-            message: message.clone(),
-        })
+        .send_telegram_message_with_keyboard(
+            TelegramMessage {
+                chat_id: telegram_chat_id,
+                thread_id,
+                message: message.clone(),
+            },
+            keyboard.clone(),
+        )
         .await;
 
     let bot_type = BotType::parse(ctx.bot_name);
+    let mut delivered_chat_id = None;
 
     match &result {
         Ok(_) => {
             METRICS.increment_messages_sent_for_bot(ctx.source);
+            delivered_chat_id = Some(telegram_chat_id);
             if let Some(bt) = bot_type {
                 if let Err(e) = upsert_chat_bot_subscription(ctx.pool, telegram_chat_id, bt, true) {
                     tracing::warn!("Failed to track subscription for {:?}: {:?}", bt, e);
@@ -218,15 +329,19 @@ pub async fn process_webhook(ctx: WebhookContext<'_>) -> HttpResponse {
                                 new_chat_id
                             );
                             let retry_result = bot
-                                .send_telegram_message(TelegramMessage {
-                                    chat_id: *new_chat_id,
-                                    thread_id,
-                                    message: message.clone(),
-                                })
+                                .send_telegram_message_with_keyboard(
+                                    TelegramMessage {
+                                        chat_id: *new_chat_id,
+                                        thread_id,
+                                        message: message.clone(),
+                                    },
+                                    keyboard.clone(),
+                                )
                                 .await;
 
                             if retry_result.is_ok() {
                                 METRICS.increment_messages_sent_for_bot(ctx.source);
+                                delivered_chat_id = Some(*new_chat_id);
                                 recovery_succeeded = true;
                                 if let Some(bt) = bot_type {
                                     if let Err(sub_err) = upsert_chat_bot_subscription(
@@ -277,15 +392,19 @@ pub async fn process_webhook(ctx: WebhookContext<'_>) -> HttpResponse {
                                 telegram_chat_id
                             );
                             let retry_result = bot
-                                .send_telegram_message(TelegramMessage {
-                                    chat_id: *new_chat_id,
-                                    thread_id,
-                                    message: message.clone(),
-                                })
+                                .send_telegram_message_with_keyboard(
+                                    TelegramMessage {
+                                        chat_id: *new_chat_id,
+                                        thread_id,
+                                        message: message.clone(),
+                                    },
+                                    keyboard.clone(),
+                                )
                                 .await;
 
                             if retry_result.is_ok() {
                                 METRICS.increment_messages_sent_for_bot(ctx.source);
+                                delivered_chat_id = Some(*new_chat_id);
                                 recovery_succeeded = true;
                                 if let Some(bt) = bot_type {
                                     if let Err(sub_err) = upsert_chat_bot_subscription(
@@ -379,15 +498,19 @@ pub async fn process_webhook(ctx: WebhookContext<'_>) -> HttpResponse {
                     tokio::time::sleep(std::time::Duration::from_secs(retry_after)).await;
 
                     let retry_result = bot
-                        .send_telegram_message(TelegramMessage {
-                            chat_id: telegram_chat_id,
-                            thread_id,
-                            message: message.clone(),
-                        })
+                        .send_telegram_message_with_keyboard(
+                            TelegramMessage {
+                                chat_id: telegram_chat_id,
+                                thread_id,
+                                message: message.clone(),
+                            },
+                            keyboard.clone(),
+                        )
                         .await;
 
                     if retry_result.is_ok() {
                         METRICS.increment_messages_sent_for_bot(ctx.source);
+                        delivered_chat_id = Some(telegram_chat_id);
                         recovery_succeeded = true;
                         if let Some(bt) = bot_type {
                             if let Err(sub_err) =
@@ -416,15 +539,19 @@ pub async fn process_webhook(ctx: WebhookContext<'_>) -> HttpResponse {
                     tokio::time::sleep(std::time::Duration::from_secs(2)).await;
 
                     let retry_result = bot
-                        .send_telegram_message(TelegramMessage {
-                            chat_id: telegram_chat_id,
-                            thread_id,
-                            message: message.clone(),
-                        })
+                        .send_telegram_message_with_keyboard(
+                            TelegramMessage {
+                                chat_id: telegram_chat_id,
+                                thread_id,
+                                message: message.clone(),
+                            },
+                            keyboard.clone(),
+                        )
                         .await;
 
                     if retry_result.is_ok() {
                         METRICS.increment_messages_sent_for_bot(ctx.source);
+                        delivered_chat_id = Some(telegram_chat_id);
                         recovery_succeeded = true;
                         if let Some(bt) = bot_type {
                             if let Err(sub_err) =
@@ -467,6 +594,22 @@ pub async fn process_webhook(ctx: WebhookContext<'_>) -> HttpResponse {
         }
     }
 
+    // Direct messages only follow a delivered group message and leave out its mention line
+    if let (Some(provider), Some(group_chat_id)) = (
+        provider.filter(|_| !ctx.direct_mentions.is_empty()),
+        delivered_chat_id,
+    ) {
+        send_personal_notifications(
+            &bot,
+            ctx.pool,
+            provider,
+            &ctx.direct_mentions,
+            group_chat_id,
+            &body,
+        )
+        .await;
+    }
+
     if let Err(e) = send_message_to_admin(
         &bot.bot,
         format!(
@@ -0,0 +1,56 @@
+use crate::utils::tag_filter::TagFilter;
+use crate::webhooks::common::{process_webhook, WebhookContext};
+use crate::webhooks::dockerhub::webhook_handlers::handle_push_event;
+use actix_web::{post, web, HttpResponse, Responder};
+use notifine::db::DbPool;
+use serde::Deserialize;
+use std::env;
+
+#[derive(Debug, Deserialize)]
+pub struct QueryParams {
+    pub tag: Option<String>,
+    pub exclude_tag: Option<String>,
+}
+
+/// Docker Hub has no event header and only sends repository push notifications,
+/// which are delivered to the chat through the Beep bot.
+#[post("/dockerhub/{webhook_url}")]
+pub async fn handle_dockerhub_webhook(
+    pool: web::Data<DbPool>,
+    webhook_url: web::Path<String>,
+    query: web::Query<QueryParams>,
+    body: web::Bytes,
+) -> impl Responder {
+    let tag_filter = match TagFilter::new(query.tag.as_deref(), query.exclude_tag.as_deref()) {
+        Ok(filter) => Some(filter),
+        Err(e) => {
+            tracing::error!("Invalid tag filter pattern: {}", e);
+            return HttpResponse::BadRequest().finish();
+        }
+    };
+
+    let message = handle_push_event(&body, tag_filter.as_ref());
+    tracing::info!("Message: {}", message);
+
+    let beep_token = match env::var("BEEP_TELOXIDE_TOKEN") {
+        Ok(token) => token,
+        Err(_) => {
+            tracing::error!("BEEP_TELOXIDE_TOKEN not set");
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    process_webhook(WebhookContext {
+        pool: pool.get_ref(),
+        webhook_url: &webhook_url,
+        message,
+        bot_name: "Beep",
+        token: beep_token,
+        event_name: "dockerhub_push",
+        source: "beep",
+        mentions: Vec::new(),
+        direct_mentions: Vec::new(),
+        buttons: Vec::new(),
+    })
+    .await
+}
@@ -0,0 +1,3 @@
+pub mod push;
+
+pub use push::handle_push_event;
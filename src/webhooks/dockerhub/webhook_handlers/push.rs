@@ -0,0 +1,101 @@
+use crate::utils::tag_filter::TagFilter;
+use actix_web::web;
+use html_escape::encode_text;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct PushEvent {
+    push_data: PushData,
+    repository: Repository,
+}
+
+#[derive(Debug, Deserialize)]
+struct PushData {
+    pusher: String,
+    tag: String,
+    #[serde(default)]
+    digest: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Repository {
+    repo_name: String,
+    repo_url: Option<String>,
+}
+
+pub fn handle_push_event(body: &web::Bytes, tag_filter: Option<&TagFilter>) -> String {
+    let push_event: PushEvent = match serde_json::from_slice(body) {
+        Ok(e) => e,
+        Err(e) => {
+            tracing::error!("Failed to parse Docker Hub push event: {}", e);
+            tracing::error!("Raw payload: {}", String::from_utf8_lossy(body));
+            return String::new();
+        }
+    };
+
+    let tag_raw = &push_event.push_data.tag;
+
+    if let Some(filter) = tag_filter {
+        if !filter.should_process(tag_raw) {
+            tracing::info!("Filtered out Docker Hub push event for tag: {}", tag_raw);
+            return String::new();
+        }
+    }
+
+    let repo_name = encode_text(&push_event.repository.repo_name);
+    let tag = encode_text(tag_raw);
+    let pusher = encode_text(&push_event.push_data.pusher);
+
+    let repository = match &push_event.repository.repo_url {
+        Some(url) => format!("<a href=\"{url}\">{repo_name}</a>"),
+        None => format!("<b>{repo_name}</b>"),
+    };
+
+    let mut message = format!("🐳 <b>{pusher}</b> pushed {repository}:<code>{tag}</code>");
+
+    if let Some(digest) = &push_event.push_data.digest {
+        message.push_str(&format!("\nDigest: <code>{}</code>", encode_text(digest)));
+    }
+
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUSH_PAYLOAD: &str = r#"{
+        "callback_url": "https://registry.hub.docker.com/u/acme/api/hook/abc/",
+        "push_data": {
+            "pushed_at": 1417566161,
+            "pusher": "trustedbuilder",
+            "tag": "v1.2.0"
+        },
+        "repository": {
+            "name": "api",
+            "namespace": "acme",
+            "repo_name": "acme/api",
+            "repo_url": "https://hub.docker.com/r/acme/api",
+            "status": "Active"
+        }
+    }"#;
+
+    #[test]
+    fn test_push_event() {
+        let body = web::Bytes::from(PUSH_PAYLOAD);
+        let result = handle_push_event(&body, None);
+
+        assert_eq!(
+            result,
+            "🐳 <b>trustedbuilder</b> pushed <a href=\"https://hub.docker.com/r/acme/api\">acme/api</a>:<code>v1.2.0</code>"
+        );
+    }
+
+    #[test]
+    fn test_push_event_tag_filter() {
+        let body = web::Bytes::from(PUSH_PAYLOAD);
+
+        let filter = TagFilter::new(None, Some("v*")).unwrap();
+        assert!(handle_push_event(&body, Some(&filter)).is_empty());
+    }
+}
@@ -1,12 +1,25 @@
 use crate::utils::branch_filter::BranchFilter;
+use crate::utils::event_filter::EventFilter;
+use crate::utils::tag_filter::TagFilter;
 use crate::webhooks::common::{process_webhook, WebhookContext};
+use crate::webhooks::github::suite_runs::{record_check_run, take_check_runs};
+use crate::webhooks::github::webhook_handlers::buttons::extract_buttons;
+use crate::webhooks::github::webhook_handlers::mentions::{
+    extract_mentions, extract_personal_mentions,
+};
 use crate::webhooks::github::webhook_handlers::{
-    handle_check_run_event, handle_comment_event, handle_create_event, handle_delete_event,
-    handle_issue_event, handle_ping_event, handle_pull_request_event, handle_push_event,
-    handle_wiki_event, handle_workflow_run_event,
+    handle_check_run_event, handle_check_suite_event, handle_code_scanning_alert_event,
+    handle_comment_event, handle_create_event, handle_delete_event, handle_dependabot_alert_event,
+    handle_discussion_comment_event, handle_discussion_event, handle_fork_event,
+    handle_issue_event, handle_member_event, handle_merge_group_event, handle_ping_event,
+    handle_public_event, handle_pull_request_event, handle_push_event,
+    handle_registry_package_event, handle_repository_vulnerability_alert_event,
+    handle_review_event, handle_secret_scanning_alert_event, handle_star_event, handle_wiki_event,
+    handle_workflow_run_event, AlertSeverity,
 };
 use actix_web::{post, web, HttpRequest, HttpResponse, Responder};
 use notifine::db::DbPool;
+use notifine::find_webhook_by_webhook_url;
 use serde::Deserialize;
 use std::env;
 
@@ -14,6 +27,41 @@ use std::env;
 pub struct QueryParams {
     pub branch: Option<String>,
     pub exclude_branch: Option<String>,
+    pub tag: Option<String>,
+    pub exclude_tag: Option<String>,
+    pub min_severity: Option<String>,
+    pub events: Option<String>,
+    pub exclude_events: Option<String>,
+    pub suite_summary: Option<bool>,
+}
+
+/// Community events are noisy on popular repositories, so they are only delivered
+/// when listed in the `events` query parameter. Listing them keeps the other events.
+const OPT_IN_EVENTS: &[&str] = &[
+    "discussion",
+    "discussion_comment",
+    "star",
+    "fork",
+    "member",
+    "public",
+];
+
+fn is_event_enabled(event_filter: &EventFilter, event: &str) -> bool {
+    match event {
+        // Always confirm the webhook setup, whatever the filter says
+        "ping" => true,
+        e => event_filter.should_process(e),
+    }
+}
+
+fn find_webhook_id(pool: &DbPool, webhook_url: &str) -> Option<i32> {
+    match find_webhook_by_webhook_url(pool, webhook_url) {
+        Ok(webhook) => webhook.map(|webhook| webhook.id),
+        Err(e) => {
+            tracing::error!("Failed to find webhook {}: {:?}", webhook_url, e);
+            None
+        }
+    }
 }
 
 #[post("/github/{webhook_url}")]
@@ -36,9 +84,44 @@ pub async fn handle_github_webhook(
                 }
             };
 
+        let tag_filter = match TagFilter::new(query.tag.as_deref(), query.exclude_tag.as_deref()) {
+            Ok(filter) => Some(filter),
+            Err(e) => {
+                tracing::error!("Invalid tag filter pattern: {}", e);
+                return HttpResponse::BadRequest().finish();
+            }
+        };
+
+        let min_severity = match query.min_severity.as_deref() {
+            Some(value) => match AlertSeverity::parse(value) {
+                Some(severity) => Some(severity),
+                None => {
+                    tracing::error!("Invalid min_severity value: {}", value);
+                    return HttpResponse::BadRequest().finish();
+                }
+            },
+            None => None,
+        };
+
+        let event_filter = match EventFilter::new(
+            query.events.as_deref(),
+            query.exclude_events.as_deref(),
+            OPT_IN_EVENTS,
+        ) {
+            Ok(filter) => filter,
+            Err(e) => {
+                tracing::error!("Invalid event filter pattern: {}", e);
+                return HttpResponse::BadRequest().finish();
+            }
+        };
+
         let event_str = event_name.to_str().unwrap_or("unknown");
 
         let message = match event_str {
+            _ if !is_event_enabled(&event_filter, event_str) => {
+                tracing::info!("Filtered out event: {}", event_str);
+                String::new()
+            }
             "ping" => handle_ping_event(&body),
             "push" => handle_push_event(&body, branch_filter.as_ref()),
             "issues" => handle_issue_event(&body),
@@ -46,15 +129,68 @@ pub async fn handle_github_webhook(
             "issue_comment" | "pull_request_review_comment" | "commit_comment" => {
                 handle_comment_event(&body, false)
             }
-            "check_run" => handle_check_run_event(&body),
+            "pull_request_review" => handle_review_event(&body),
+            "check_run" => handle_check_run_event(
+                &body,
+                query.suite_summary.unwrap_or(false),
+                |suite_id, name, conclusion| {
+                    let Some(webhook_id) = find_webhook_id(&pool, &webhook_url) else {
+                        return;
+                    };
+                    if let Err(e) = record_check_run(&pool, webhook_id, suite_id, name, conclusion)
+                    {
+                        tracing::error!("Failed to record check run: {:?}", e);
+                    }
+                },
+            ),
+            "check_suite" => handle_check_suite_event(
+                &body,
+                branch_filter.as_ref(),
+                query.suite_summary.unwrap_or(false),
+                |suite_id| {
+                    let Some(webhook_id) = find_webhook_id(&pool, &webhook_url) else {
+                        return Vec::new();
+                    };
+                    take_check_runs(&pool, webhook_id, suite_id).unwrap_or_else(|e| {
+                        tracing::error!("Failed to load check runs: {:?}", e);
+                        Vec::new()
+                    })
+                },
+            ),
+            "merge_group" => handle_merge_group_event(&body, branch_filter.as_ref()),
             "create" => handle_create_event(&body, branch_filter.as_ref()),
             "delete" => handle_delete_event(&body, branch_filter.as_ref()),
             "gollum" => handle_wiki_event(&body),
             "workflow_run" => handle_workflow_run_event(&body, branch_filter.as_ref()),
+            "package" | "registry_package" => {
+                handle_registry_package_event(&body, tag_filter.as_ref())
+            }
+            "dependabot_alert" => handle_dependabot_alert_event(&body, min_severity),
+            "code_scanning_alert" => handle_code_scanning_alert_event(&body, min_severity),
+            "secret_scanning_alert" => handle_secret_scanning_alert_event(&body, min_severity),
+            "repository_vulnerability_alert" => {
+                handle_repository_vulnerability_alert_event(&body, min_severity)
+            }
+            "discussion" => handle_discussion_event(&body),
+            "discussion_comment" => handle_discussion_comment_event(&body),
+            "star" => handle_star_event(&body),
+            "fork" => handle_fork_event(&body),
+            "member" => handle_member_event(&body),
+            "public" => handle_public_event(&body),
             _ => String::new(),
         };
         tracing::info!("Message: {}", message);
 
+        let (mentions, direct_mentions, buttons) = if message.is_empty() {
+            (Vec::new(), Vec::new(), Vec::new())
+        } else {
+            (
+                extract_mentions(event_str, &body),
+                extract_personal_mentions(event_str, &body),
+                extract_buttons(event_str, &body),
+            )
+        };
+
         let github_token = match env::var("GITHUB_TELOXIDE_TOKEN") {
             Ok(token) => token,
             Err(_) => {
@@ -71,9 +207,35 @@ pub async fn handle_github_webhook(
             token: github_token,
             event_name: event_str,
             source: "github",
+            mentions,
+            direct_mentions,
+            buttons,
         })
         .await
     } else {
         HttpResponse::BadRequest().finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(events: Option<&str>) -> EventFilter {
+        EventFilter::new(events, None, OPT_IN_EVENTS).unwrap()
+    }
+
+    #[test]
+    fn test_community_events_are_enabled_on_top_of_the_defaults() {
+        let star_only = filter(Some("star"));
+        assert!(is_event_enabled(&star_only, "push"));
+        assert!(is_event_enabled(&star_only, "pull_request"));
+        assert!(is_event_enabled(&star_only, "star"));
+        assert!(!is_event_enabled(&star_only, "fork"));
+
+        let defaults = filter(None);
+        assert!(is_event_enabled(&defaults, "push"));
+        assert!(!is_event_enabled(&defaults, "star"));
+        assert!(is_event_enabled(&filter(Some("issues")), "ping"));
+    }
+}
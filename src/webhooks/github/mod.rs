@@ -1,2 +1,3 @@
 pub mod http_server;
+pub mod suite_runs;
 pub mod webhook_handlers;
@@ -0,0 +1,70 @@
+use chrono::{Duration, Utc};
+use diesel::prelude::*;
+use notifine::db::{DbError, DbPool};
+use notifine::schema::check_suite_runs;
+
+/// Runs of suites that never send a `completed` event are dropped after this long.
+const SUITE_RUNS_TTL: Duration = Duration::hours(6);
+
+/// Stores the conclusion of a completed check run for the webhook's suite summary.
+/// Re-runs report the same check again, so only the latest conclusion is kept.
+pub fn record_check_run(
+    pool: &DbPool,
+    webhook_id: i32,
+    suite_id: i64,
+    name: &str,
+    conclusion: &str,
+) -> Result<(), DbError> {
+    let conn = &mut pool.get()?;
+    let now = Utc::now();
+
+    diesel::insert_into(check_suite_runs::table)
+        .values((
+            check_suite_runs::webhook_id.eq(webhook_id),
+            check_suite_runs::suite_id.eq(suite_id),
+            check_suite_runs::name.eq(name),
+            check_suite_runs::conclusion.eq(conclusion),
+            check_suite_runs::updated_at.eq(now),
+        ))
+        .on_conflict((
+            check_suite_runs::webhook_id,
+            check_suite_runs::suite_id,
+            check_suite_runs::name,
+        ))
+        .do_update()
+        .set((
+            check_suite_runs::conclusion.eq(conclusion),
+            check_suite_runs::updated_at.eq(now),
+        ))
+        .execute(conn)?;
+
+    Ok(())
+}
+
+/// Removes and returns the recorded runs of the webhook's suite as `(name, conclusion)`.
+/// Also drops the runs of the webhook's suites that never completed.
+pub fn take_check_runs(
+    pool: &DbPool,
+    webhook_id: i32,
+    suite_id: i64,
+) -> Result<Vec<(String, String)>, DbError> {
+    let conn = &mut pool.get()?;
+
+    diesel::delete(
+        check_suite_runs::table
+            .filter(check_suite_runs::webhook_id.eq(webhook_id))
+            .filter(check_suite_runs::updated_at.lt(Utc::now() - SUITE_RUNS_TTL)),
+    )
+    .execute(conn)?;
+
+    let mut runs: Vec<(String, String)> = diesel::delete(
+        check_suite_runs::table
+            .filter(check_suite_runs::webhook_id.eq(webhook_id))
+            .filter(check_suite_runs::suite_id.eq(suite_id)),
+    )
+    .returning((check_suite_runs::name, check_suite_runs::conclusion))
+    .get_results(conn)?;
+    runs.sort();
+
+    Ok(runs)
+}
@@ -0,0 +1,144 @@
+use super::utils::parse_webhook_payload;
+use crate::services::actions::{Action, NotificationButton};
+use actix_web::web;
+use serde_json::Value;
+
+/// Inline keyboard buttons for a GitHub notification. Action buttons are only shown by
+/// `process_webhook` when the chat has stored an API token.
+pub fn extract_buttons(event: &str, body: &web::Bytes) -> Vec<NotificationButton> {
+    let payload: Value = match parse_webhook_payload(body) {
+        Ok(payload) => payload,
+        Err(_) => return Vec::new(),
+    };
+    let action = payload["action"].as_str().unwrap_or("");
+    let repository_id = payload["repository"]["id"].as_i64();
+
+    match event {
+        "pull_request" => {
+            let pull_request = &payload["pull_request"];
+            let Some(url) = pull_request["html_url"].as_str() else {
+                return Vec::new();
+            };
+
+            let mut buttons = vec![NotificationButton::url("Open PR", url)];
+            if matches!(
+                action,
+                "opened" | "reopened" | "review_requested" | "ready_for_review"
+            ) {
+                buttons.push(NotificationButton::url(
+                    "View diff",
+                    &format!("{url}/files"),
+                ));
+                if let (Some(repository_id), Some(number)) =
+                    (repository_id, pull_request["number"].as_i64())
+                {
+                    buttons.push(NotificationButton::action(
+                        "Approve",
+                        Action::GithubApprove {
+                            repository_id,
+                            number,
+                        },
+                    ));
+                }
+            }
+            buttons
+        }
+        "pull_request_review" => payload["pull_request"]["html_url"]
+            .as_str()
+            .map(|url| vec![NotificationButton::url("Open PR", url)])
+            .unwrap_or_default(),
+        "issues" => payload["issue"]["html_url"]
+            .as_str()
+            .map(|url| vec![NotificationButton::url("Open issue", url)])
+            .unwrap_or_default(),
+        "workflow_run" => {
+            let workflow_run = &payload["workflow_run"];
+            let Some(url) = workflow_run["html_url"].as_str() else {
+                return Vec::new();
+            };
+
+            let mut buttons = vec![NotificationButton::url("Open run", url)];
+            let failed = matches!(
+                workflow_run["conclusion"].as_str(),
+                Some("failure" | "timed_out" | "cancelled")
+            );
+            if action == "completed" && failed {
+                if let (Some(repository_id), Some(run_id)) =
+                    (repository_id, workflow_run["id"].as_i64())
+                {
+                    buttons.push(NotificationButton::action(
+                        "Rerun workflow",
+                        Action::GithubRerun {
+                            repository_id,
+                            run_id,
+                        },
+                    ));
+                }
+            }
+            buttons
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pull_request_buttons() {
+        let body = web::Bytes::from(
+            r#"{
+                "action": "opened",
+                "pull_request": {"html_url": "https://github.com/acme/api/pull/12", "number": 12},
+                "repository": {"id": 42}
+            }"#,
+        );
+
+        assert_eq!(
+            extract_buttons("pull_request", &body),
+            vec![
+                NotificationButton::url("Open PR", "https://github.com/acme/api/pull/12"),
+                NotificationButton::url("View diff", "https://github.com/acme/api/pull/12/files"),
+                NotificationButton::action(
+                    "Approve",
+                    Action::GithubApprove {
+                        repository_id: 42,
+                        number: 12
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_failed_workflow_run_buttons() {
+        let body = web::Bytes::from(
+            r#"{
+                "action": "completed",
+                "workflow_run": {"id": 777, "html_url": "https://github.com/acme/api/actions/runs/777", "conclusion": "failure"},
+                "repository": {"id": 42}
+            }"#,
+        );
+        let buttons = extract_buttons("workflow_run", &body);
+
+        assert_eq!(buttons.len(), 2);
+        assert_eq!(
+            buttons[1],
+            NotificationButton::action(
+                "Rerun workflow",
+                Action::GithubRerun {
+                    repository_id: 42,
+                    run_id: 777
+                }
+            )
+        );
+
+        let succeeded = web::Bytes::from(
+            String::from_utf8(body.to_vec())
+                .unwrap()
+                .replace("failure", "success"),
+        );
+        assert_eq!(extract_buttons("workflow_run", &succeeded).len(), 1);
+    }
+}
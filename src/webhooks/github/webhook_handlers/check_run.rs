@@ -16,6 +16,12 @@ struct CheckRun {
     html_url: String,
     status: String,
     conclusion: Option<String>,
+    check_suite: Option<CheckSuiteRef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckSuiteRef {
+    id: i64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -29,7 +35,14 @@ struct Sender {
     login: String,
 }
 
-pub fn handle_check_run_event(body: &web::Bytes) -> String {
+/// With `suite_summary` enabled, completed runs are passed to `record_run` as
+/// `(suite id, name, conclusion)` for the check suite summary, and the individual
+/// run messages are suppressed.
+pub fn handle_check_run_event(
+    body: &web::Bytes,
+    suite_summary: bool,
+    record_run: impl FnOnce(i64, &str, &str),
+) -> String {
     let check_event: CheckRunEvent = match parse_webhook_payload(body) {
         Ok(event) => event,
         Err(e) => {
@@ -41,6 +54,17 @@ pub fn handle_check_run_event(body: &web::Bytes) -> String {
 
     let action = &check_event.action;
     let check_run = &check_event.check_run;
+
+    if suite_summary {
+        if action == "completed" {
+            if let Some(suite) = &check_run.check_suite {
+                let conclusion = check_run.conclusion.as_deref().unwrap_or("unknown");
+                record_run(suite.id, &check_run.name, conclusion);
+            }
+        }
+        return String::new();
+    }
+
     let repository_name = &check_event.repository.name;
     let repository_url = &check_event.repository.html_url;
     let sender = &check_event.sender.login;
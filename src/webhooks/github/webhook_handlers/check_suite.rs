@@ -0,0 +1,231 @@
+use super::utils::parse_webhook_payload;
+use crate::utils::branch_filter::BranchFilter;
+use actix_web::web;
+use html_escape::encode_text;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct CheckSuiteEvent {
+    action: String,
+    check_suite: CheckSuite,
+    repository: Repository,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckSuite {
+    id: i64,
+    head_branch: Option<String>,
+    head_sha: String,
+    conclusion: Option<String>,
+    app: Option<App>,
+    latest_check_runs_count: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct App {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Repository {
+    name: String,
+    html_url: String,
+}
+
+fn conclusion_emoji(conclusion: &str) -> &'static str {
+    match conclusion {
+        "success" => "✅",
+        "failure" | "timed_out" | "startup_failure" => "❌",
+        "cancelled" => "⚠️",
+        "skipped" | "neutral" => "⏭️",
+        "action_required" => "✋",
+        _ => "❓",
+    }
+}
+
+fn is_failed_conclusion(conclusion: &str) -> bool {
+    matches!(
+        conclusion,
+        "failure" | "timed_out" | "cancelled" | "action_required" | "startup_failure"
+    )
+}
+
+/// Summarizes a completed suite with the `(name, conclusion)` runs recorded for it,
+/// which `take_runs` returns by suite id. Only webhooks with `suite_summary` get
+/// summaries, the others are notified per check run.
+pub fn handle_check_suite_event(
+    body: &web::Bytes,
+    branch_filter: Option<&BranchFilter>,
+    suite_summary: bool,
+    take_runs: impl FnOnce(i64) -> Vec<(String, String)>,
+) -> String {
+    if !suite_summary {
+        return String::new();
+    }
+
+    let suite_event: CheckSuiteEvent = match parse_webhook_payload(body) {
+        Ok(event) => event,
+        Err(e) => {
+            tracing::error!("Failed to parse check suite event: {}", e);
+            tracing::error!("Raw payload: {}", String::from_utf8_lossy(body));
+            return String::new();
+        }
+    };
+
+    if suite_event.action != "completed" {
+        return String::new();
+    }
+
+    let check_suite = &suite_event.check_suite;
+    let branch_raw = check_suite.head_branch.as_deref().unwrap_or("");
+
+    if let Some(filter) = branch_filter {
+        if !filter.should_process(branch_raw) {
+            tracing::info!("Filtered out check suite event for branch: {}", branch_raw);
+            return String::new();
+        }
+    }
+
+    let runs = take_runs(check_suite.id);
+
+    let repository_name = encode_text(&suite_event.repository.name);
+    let repository_url = &suite_event.repository.html_url;
+    let head_sha = &check_suite.head_sha;
+    let short_sha = &head_sha[..head_sha.len().min(7)];
+    let commit_url = format!("{repository_url}/commit/{head_sha}");
+    let conclusion = check_suite.conclusion.as_deref().unwrap_or("unknown");
+    let status_emoji = conclusion_emoji(conclusion);
+    let app_name = check_suite
+        .app
+        .as_ref()
+        .map(|app| format!(" ({})", encode_text(&app.name)))
+        .unwrap_or_default();
+
+    let mut message = format!("{status_emoji} Checks for <a href=\"{commit_url}\">{short_sha}</a>");
+    if !branch_raw.is_empty() {
+        message.push_str(&format!(" on branch {}", encode_text(branch_raw)));
+    }
+    message.push_str(&format!(
+        " in <a href=\"{repository_url}\">{repository_name}</a> completed with status: {conclusion}{app_name}"
+    ));
+
+    if runs.is_empty() {
+        if let Some(count) = check_suite.latest_check_runs_count {
+            message.push_str(&format!("\n{count} checks"));
+        }
+        return message;
+    }
+
+    let (failed, passed): (Vec<_>, Vec<_>) = runs
+        .iter()
+        .partition(|(_, run_conclusion)| is_failed_conclusion(run_conclusion));
+
+    if !failed.is_empty() {
+        let names: Vec<String> = failed
+            .iter()
+            .map(|(name, run_conclusion)| {
+                format!("{} {}", conclusion_emoji(run_conclusion), encode_text(name))
+            })
+            .collect();
+        message.push_str(&format!("\n\n<b>Failed:</b>\n{}", names.join("\n")));
+    }
+
+    if !passed.is_empty() {
+        let names: Vec<String> = passed
+            .iter()
+            .map(|(name, _)| encode_text(name).into_owned())
+            .collect();
+        message.push_str(&format!(
+            "\n\n<b>Passed ({}):</b> {}",
+            passed.len(),
+            names.join(", ")
+        ));
+    }
+
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suite_payload(suite_id: i64, conclusion: &str) -> String {
+        format!(
+            r#"{{
+                "action": "completed",
+                "check_suite": {{
+                    "id": {suite_id},
+                    "head_branch": "main",
+                    "head_sha": "a1b2c3d4e5f6",
+                    "status": "completed",
+                    "conclusion": "{conclusion}",
+                    "app": {{"name": "GitHub Actions"}},
+                    "latest_check_runs_count": 3
+                }},
+                "repository": {{"name": "api", "html_url": "https://github.com/acme/api"}},
+                "sender": {{"login": "octocat"}}
+            }}"#
+        )
+    }
+
+    fn runs(runs: &[(&str, &str)]) -> Vec<(String, String)> {
+        runs.iter()
+            .map(|(name, conclusion)| (name.to_string(), conclusion.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_check_suite_summary_groups_runs() {
+        let body = web::Bytes::from(suite_payload(9001, "failure"));
+        let result = handle_check_suite_event(&body, None, true, |suite_id| {
+            assert_eq!(suite_id, 9001);
+            runs(&[
+                ("lint", "success"),
+                ("test", "failure"),
+                ("build", "success"),
+            ])
+        });
+
+        assert!(result.starts_with("❌ Checks for <a href=\"https://github.com/acme/api/commit/a1b2c3d4e5f6\">a1b2c3d</a> on branch main"));
+        assert!(result.contains("<b>Failed:</b>\n❌ test"));
+        assert!(result.contains("<b>Passed (2):</b> lint, build"));
+    }
+
+    #[test]
+    fn test_check_suite_with_only_passed_runs() {
+        let body = web::Bytes::from(suite_payload(9002, "success"));
+        let result = handle_check_suite_event(&body, None, true, |_| runs(&[("test", "success")]));
+
+        assert!(!result.contains("Failed"));
+        assert!(result.contains("<b>Passed (1):</b> test"));
+    }
+
+    #[test]
+    fn test_check_suite_without_recorded_runs() {
+        let body = web::Bytes::from(suite_payload(9003, "success"));
+        let result = handle_check_suite_event(&body, None, true, |_| Vec::new());
+
+        assert!(result.ends_with("(GitHub Actions)\n3 checks"));
+    }
+
+    #[test]
+    fn test_check_suite_needs_suite_summary() {
+        let body = web::Bytes::from(suite_payload(9004, "success"));
+        let result = handle_check_suite_event(&body, None, false, |_| {
+            panic!("runs are only taken for summaries")
+        });
+
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_filtered_suite_keeps_its_runs() {
+        let body = web::Bytes::from(suite_payload(9005, "success"));
+        let filter = BranchFilter::new(Some("release/*"), None).unwrap();
+        let result = handle_check_suite_event(&body, Some(&filter), true, |_| {
+            panic!("runs of filtered suites are not taken")
+        });
+
+        assert!(result.is_empty());
+    }
+}
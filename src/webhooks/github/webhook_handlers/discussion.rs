@@ -0,0 +1,181 @@
+use super::utils::{parse_webhook_payload, truncate_body};
+use actix_web::web;
+use html_escape::encode_text;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct DiscussionEvent {
+    action: String,
+    discussion: Discussion,
+    answer: Option<Answer>,
+    repository: Repository,
+    sender: Sender,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DiscussionCommentEvent {
+    action: String,
+    comment: Comment,
+    discussion: Discussion,
+    repository: Repository,
+    sender: Sender,
+}
+
+#[derive(Debug, Deserialize)]
+struct Discussion {
+    html_url: String,
+    number: i64,
+    title: String,
+    category: Option<Category>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Category {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Answer {
+    html_url: String,
+    user: Sender,
+}
+
+#[derive(Debug, Deserialize)]
+struct Comment {
+    html_url: String,
+    body: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Repository {
+    name: String,
+    html_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Sender {
+    login: String,
+}
+
+pub fn handle_discussion_event(body: &web::Bytes) -> String {
+    let discussion_event: DiscussionEvent = match parse_webhook_payload(body) {
+        Ok(event) => event,
+        Err(e) => {
+            tracing::error!("Failed to parse discussion event: {}", e);
+            tracing::error!("Raw payload: {}", String::from_utf8_lossy(body));
+            return String::new();
+        }
+    };
+
+    let discussion = &discussion_event.discussion;
+    let discussion_url = &discussion.html_url;
+    let discussion_number = discussion.number;
+    let discussion_title = encode_text(&discussion.title);
+    let repository_name = encode_text(&discussion_event.repository.name);
+    let repository_url = &discussion_event.repository.html_url;
+    let sender = encode_text(&discussion_event.sender.login);
+
+    match discussion_event.action.as_str() {
+        "created" => {
+            let category = discussion
+                .category
+                .as_ref()
+                .map(|c| format!(" in {}", encode_text(&c.name)))
+                .unwrap_or_default();
+            format!(
+                "💬 <b>{sender}</b> started discussion <a href=\"{discussion_url}\">#{discussion_number}</a>{category} in <a href=\"{repository_url}\">{repository_name}</a>:\n{discussion_title}"
+            )
+        }
+        "answered" => match &discussion_event.answer {
+            Some(answer) => format!(
+                "✅ Discussion <a href=\"{discussion_url}\">#{discussion_number}</a> in <a href=\"{repository_url}\">{repository_name}</a> was <a href=\"{answer_url}\">answered</a> by <b>{answerer}</b>:\n{discussion_title}",
+                answer_url = answer.html_url,
+                answerer = encode_text(&answer.user.login)
+            ),
+            None => format!(
+                "✅ <b>{sender}</b> marked discussion <a href=\"{discussion_url}\">#{discussion_number}</a> in <a href=\"{repository_url}\">{repository_name}</a> as answered"
+            ),
+        },
+        "closed" => format!(
+            "<b>{sender}</b> closed discussion <a href=\"{discussion_url}\">#{discussion_number}</a> in <a href=\"{repository_url}\">{repository_name}</a>"
+        ),
+        "reopened" => format!(
+            "<b>{sender}</b> reopened discussion <a href=\"{discussion_url}\">#{discussion_number}</a> in <a href=\"{repository_url}\">{repository_name}</a>"
+        ),
+        _ => String::new(),
+    }
+}
+
+pub fn handle_discussion_comment_event(body: &web::Bytes) -> String {
+    let comment_event: DiscussionCommentEvent = match parse_webhook_payload(body) {
+        Ok(event) => event,
+        Err(e) => {
+            tracing::error!("Failed to parse discussion comment event: {}", e);
+            tracing::error!("Raw payload: {}", String::from_utf8_lossy(body));
+            return String::new();
+        }
+    };
+
+    if comment_event.action != "created" {
+        return String::new();
+    }
+
+    let discussion = &comment_event.discussion;
+    let comment_url = &comment_event.comment.html_url;
+    let discussion_number = discussion.number;
+    let repository_name = encode_text(&comment_event.repository.name);
+    let repository_url = &comment_event.repository.html_url;
+    let sender = encode_text(&comment_event.sender.login);
+    let comment = truncate_body(&comment_event.comment.body, comment_url);
+
+    format!(
+        "<b>{sender}</b> commented on discussion <a href=\"{comment_url}\">#{discussion_number}</a> in <a href=\"{repository_url}\">{repository_name}</a>:\n{comment}"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discussion_answered() {
+        let payload = r#"{
+            "action": "answered",
+            "discussion": {
+                "html_url": "https://github.com/acme/api/discussions/5",
+                "number": 5,
+                "title": "How do I configure retries?",
+                "category": {"name": "Q&A"}
+            },
+            "answer": {
+                "html_url": "https://github.com/acme/api/discussions/5#discussioncomment-1",
+                "user": {"login": "maintainer"}
+            },
+            "repository": {"name": "api", "html_url": "https://github.com/acme/api"},
+            "sender": {"login": "asker"}
+        }"#;
+        let result = handle_discussion_event(&web::Bytes::from(payload));
+
+        assert!(result.contains("was <a href=\"https://github.com/acme/api/discussions/5#discussioncomment-1\">answered</a> by <b>maintainer</b>"));
+    }
+
+    #[test]
+    fn test_discussion_comment_truncated() {
+        let long_body = "a".repeat(150);
+        let payload = format!(
+            r#"{{
+                "action": "created",
+                "comment": {{"html_url": "https://example.com/c/1", "body": "{long_body}"}},
+                "discussion": {{"html_url": "https://example.com/d/1", "number": 1, "title": "t"}},
+                "repository": {{"name": "api", "html_url": "https://github.com/acme/api"}},
+                "sender": {{"login": "octocat"}}
+            }}"#
+        );
+        let result = handle_discussion_comment_event(&web::Bytes::from(payload));
+
+        assert!(result.ends_with(&format!(
+            "{}<a href=\"https://example.com/c/1\">...</a>",
+            "a".repeat(100)
+        )));
+    }
+}
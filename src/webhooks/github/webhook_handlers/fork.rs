@@ -0,0 +1,51 @@
+use super::utils::parse_webhook_payload;
+use actix_web::web;
+use html_escape::encode_text;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct ForkEvent {
+    forkee: Forkee,
+    repository: Repository,
+    sender: Sender,
+}
+
+#[derive(Debug, Deserialize)]
+struct Forkee {
+    full_name: String,
+    html_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Repository {
+    name: String,
+    html_url: String,
+    forks_count: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct Sender {
+    login: String,
+}
+
+pub fn handle_fork_event(body: &web::Bytes) -> String {
+    let fork_event: ForkEvent = match parse_webhook_payload(body) {
+        Ok(event) => event,
+        Err(e) => {
+            tracing::error!("Failed to parse fork event: {}", e);
+            tracing::error!("Raw payload: {}", String::from_utf8_lossy(body));
+            return String::new();
+        }
+    };
+
+    let repository_name = encode_text(&fork_event.repository.name);
+    let repository_url = &fork_event.repository.html_url;
+    let forks_count = fork_event.repository.forks_count;
+    let forkee_name = encode_text(&fork_event.forkee.full_name);
+    let forkee_url = &fork_event.forkee.html_url;
+    let sender = encode_text(&fork_event.sender.login);
+
+    format!(
+        "🍴 <b>{sender}</b> forked <a href=\"{repository_url}\">{repository_name}</a> to <a href=\"{forkee_url}\">{forkee_name}</a> ({forks_count} forks)"
+    )
+}
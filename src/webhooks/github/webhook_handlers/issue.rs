@@ -9,6 +9,7 @@ pub struct IssueEvent {
     issue: Issue,
     repository: Repository,
     sender: Sender,
+    assignee: Option<Sender>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -57,6 +58,13 @@ pub fn handle_issue_event(body: &web::Bytes) -> String {
         "reopened" => format!(
             "<b>{sender}</b> reopened issue <a href=\"{issue_url}\">#{issue_number}</a> in <a href=\"{repository_url}\">{repository_name}</a>"
         ),
+        "assigned" => match &issue_event.assignee {
+            Some(assignee) => format!(
+                "<b>{sender}</b> assigned <b>{}</b> to issue <a href=\"{issue_url}\">#{issue_number}</a> in <a href=\"{repository_url}\">{repository_name}</a>:\n{issue_title}",
+                encode_text(&assignee.login)
+            ),
+            None => String::new(),
+        },
         _ => String::new(),
     }
 }
@@ -0,0 +1,56 @@
+use super::utils::parse_webhook_payload;
+use actix_web::web;
+use html_escape::encode_text;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct MemberEvent {
+    action: String,
+    member: Member,
+    repository: Repository,
+    sender: Sender,
+}
+
+#[derive(Debug, Deserialize)]
+struct Member {
+    login: String,
+    html_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Repository {
+    name: String,
+    html_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Sender {
+    login: String,
+}
+
+pub fn handle_member_event(body: &web::Bytes) -> String {
+    let member_event: MemberEvent = match parse_webhook_payload(body) {
+        Ok(event) => event,
+        Err(e) => {
+            tracing::error!("Failed to parse member event: {}", e);
+            tracing::error!("Raw payload: {}", String::from_utf8_lossy(body));
+            return String::new();
+        }
+    };
+
+    let repository_name = encode_text(&member_event.repository.name);
+    let repository_url = &member_event.repository.html_url;
+    let member = encode_text(&member_event.member.login);
+    let member_url = &member_event.member.html_url;
+    let sender = encode_text(&member_event.sender.login);
+
+    match member_event.action.as_str() {
+        "added" => format!(
+            "👋 <b>{sender}</b> added <a href=\"{member_url}\">{member}</a> as a collaborator to <a href=\"{repository_url}\">{repository_name}</a>"
+        ),
+        "removed" => format!(
+            "<b>{sender}</b> removed <a href=\"{member_url}\">{member}</a> from <a href=\"{repository_url}\">{repository_name}</a>"
+        ),
+        _ => String::new(),
+    }
+}
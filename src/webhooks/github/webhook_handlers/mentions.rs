@@ -0,0 +1,191 @@
+use super::utils::parse_webhook_payload;
+use crate::utils::mentions::extract_at_mentions;
+use actix_web::web;
+use serde_json::Value;
+
+fn login(value: &Value) -> Option<String> {
+    value["login"].as_str().map(str::to_lowercase)
+}
+
+fn logins(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|users| users.iter().filter_map(login).collect())
+        .unwrap_or_default()
+}
+
+fn text_mentions(value: &Value) -> Vec<String> {
+    value.as_str().map(extract_at_mentions).unwrap_or_default()
+}
+
+/// Collects the GitHub usernames an event is relevant to: assignees, requested reviewers,
+/// pull request authors on reviews and `@mentions` in comment bodies and commit messages.
+/// The user who triggered the event is never included.
+pub fn extract_mentions(event: &str, body: &web::Bytes) -> Vec<String> {
+    let payload: Value = match parse_webhook_payload(body) {
+        Ok(payload) => payload,
+        Err(_) => return Vec::new(),
+    };
+    let action = payload["action"].as_str().unwrap_or("");
+
+    let usernames: Vec<String> = match (event, action) {
+        ("push", _) => payload["commits"]
+            .as_array()
+            .map(|commits| {
+                commits
+                    .iter()
+                    .flat_map(|commit| text_mentions(&commit["message"]))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        ("pull_request", "opened") => {
+            let pull_request = &payload["pull_request"];
+            let mut usernames = logins(&pull_request["requested_reviewers"]);
+            usernames.extend(logins(&pull_request["assignees"]));
+            usernames.extend(text_mentions(&pull_request["body"]));
+            usernames
+        }
+        ("pull_request", "review_requested") => {
+            login(&payload["requested_reviewer"]).into_iter().collect()
+        }
+        ("pull_request" | "issues", "assigned") => {
+            login(&payload["assignee"]).into_iter().collect()
+        }
+        ("issues", "opened") => {
+            let issue = &payload["issue"];
+            let mut usernames = logins(&issue["assignees"]);
+            usernames.extend(text_mentions(&issue["body"]));
+            usernames
+        }
+        ("issue_comment" | "pull_request_review_comment" | "commit_comment", _) => {
+            text_mentions(&payload["comment"]["body"])
+        }
+        ("pull_request_review", _) => {
+            let mut usernames: Vec<String> = login(&payload["pull_request"]["user"])
+                .into_iter()
+                .collect();
+            usernames.extend(text_mentions(&payload["review"]["body"]));
+            usernames
+        }
+        _ => Vec::new(),
+    };
+
+    let sender = login(&payload["sender"]);
+    let mut mentions: Vec<String> = Vec::new();
+    for username in usernames {
+        if Some(&username) != sender.as_ref() && !mentions.contains(&username) {
+            mentions.push(username);
+        }
+    }
+
+    mentions
+}
+
+/// The subset of [`extract_mentions`] that warrants a direct message to linked users:
+/// review requests, assignments and `@mentions` in comments.
+pub fn extract_personal_mentions(event: &str, body: &web::Bytes) -> Vec<String> {
+    let action = match parse_webhook_payload::<Value>(body) {
+        Ok(payload) => payload["action"].as_str().unwrap_or("").to_string(),
+        Err(_) => return Vec::new(),
+    };
+
+    let is_personal = matches!(
+        (event, action.as_str()),
+        ("pull_request", "review_requested")
+            | ("pull_request" | "issues", "assigned")
+            | (
+                "issue_comment" | "pull_request_review_comment" | "commit_comment",
+                _
+            )
+    );
+
+    if is_personal {
+        extract_mentions(event, body)
+    } else {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pull_request_opened_mentions() {
+        let body = web::Bytes::from(
+            r#"{
+                "action": "opened",
+                "pull_request": {
+                    "body": "Fixes the cache, cc @Carol and @alice",
+                    "requested_reviewers": [{"login": "Bob"}],
+                    "assignees": [{"login": "alice"}]
+                },
+                "sender": {"login": "alice"}
+            }"#,
+        );
+
+        assert_eq!(
+            extract_mentions("pull_request", &body),
+            vec!["bob", "carol"]
+        );
+    }
+
+    #[test]
+    fn test_review_requested_mentions() {
+        let body = web::Bytes::from(
+            r#"{
+                "action": "review_requested",
+                "requested_reviewer": {"login": "bob"},
+                "sender": {"login": "alice"}
+            }"#,
+        );
+
+        assert_eq!(extract_mentions("pull_request", &body), vec!["bob"]);
+    }
+
+    #[test]
+    fn test_comment_and_review_mentions() {
+        let comment = web::Bytes::from(
+            r#"{
+                "action": "created",
+                "comment": {"body": "@bob can you take a look?"},
+                "sender": {"login": "alice"}
+            }"#,
+        );
+        assert_eq!(extract_mentions("issue_comment", &comment), vec!["bob"]);
+
+        let review = web::Bytes::from(
+            r#"{
+                "action": "submitted",
+                "review": {"body": "LGTM"},
+                "pull_request": {"user": {"login": "alice"}},
+                "sender": {"login": "bob"}
+            }"#,
+        );
+        assert_eq!(
+            extract_mentions("pull_request_review", &review),
+            vec!["alice"]
+        );
+    }
+
+    #[test]
+    fn test_personal_mentions_skip_pushes_and_new_pull_requests() {
+        let push = web::Bytes::from(
+            r#"{
+                "commits": [{"message": "Fix build, thanks @bob"}],
+                "sender": {"login": "alice"}
+            }"#,
+        );
+        assert_eq!(extract_mentions("push", &push), vec!["bob"]);
+        assert!(extract_personal_mentions("push", &push).is_empty());
+
+        let assigned = web::Bytes::from(
+            r#"{
+                "action": "assigned",
+                "assignee": {"login": "bob"},
+                "sender": {"login": "alice"}
+            }"#,
+        );
+        assert_eq!(extract_personal_mentions("issues", &assigned), vec!["bob"]);
+    }
+}
@@ -0,0 +1,89 @@
+use super::utils::parse_webhook_payload;
+use crate::utils::branch_filter::BranchFilter;
+use actix_web::web;
+use html_escape::encode_text;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct MergeGroupEvent {
+    action: String,
+    reason: Option<String>,
+    merge_group: MergeGroup,
+    repository: Repository,
+}
+
+#[derive(Debug, Deserialize)]
+struct MergeGroup {
+    head_sha: String,
+    base_ref: String,
+    head_commit: Option<HeadCommit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HeadCommit {
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Repository {
+    name: String,
+    html_url: String,
+}
+
+pub fn handle_merge_group_event(body: &web::Bytes, branch_filter: Option<&BranchFilter>) -> String {
+    let merge_group_event: MergeGroupEvent = match parse_webhook_payload(body) {
+        Ok(event) => event,
+        Err(e) => {
+            tracing::error!("Failed to parse merge group event: {}", e);
+            tracing::error!("Raw payload: {}", String::from_utf8_lossy(body));
+            return String::new();
+        }
+    };
+
+    let merge_group = &merge_group_event.merge_group;
+    let base_branch_raw = merge_group.base_ref.trim_start_matches("refs/heads/");
+
+    // Apply branch filter if provided (filter based on the queue's target branch)
+    if let Some(filter) = branch_filter {
+        if !filter.should_process(base_branch_raw) {
+            tracing::info!(
+                "Filtered out merge group event for target branch: {}",
+                base_branch_raw
+            );
+            return String::new();
+        }
+    }
+
+    let repository_name = encode_text(&merge_group_event.repository.name);
+    let repository_url = &merge_group_event.repository.html_url;
+    let base_branch = encode_text(base_branch_raw);
+    let head_sha = &merge_group.head_sha;
+    let short_sha = &head_sha[..head_sha.len().min(7)];
+    let commit_url = format!("{repository_url}/commit/{head_sha}");
+
+    match merge_group_event.action.as_str() {
+        "checks_requested" => {
+            let mut message = format!(
+                "🚂 Merge queue for {base_branch} in <a href=\"{repository_url}\">{repository_name}</a> is running checks on <a href=\"{commit_url}\">{short_sha}</a>"
+            );
+            if let Some(commit) = &merge_group.head_commit {
+                let title = commit.message.lines().next().unwrap_or("");
+                message.push_str(&format!("\n{}", encode_text(title)));
+            }
+            message
+        }
+        "destroyed" => {
+            let reason = merge_group_event.reason.as_deref().unwrap_or("unknown");
+            let (emoji, outcome) = match reason {
+                "merged" => ("✅", "was merged"),
+                "invalidated" => ("⚠️", "was invalidated"),
+                "dequeued" => ("❌", "was dequeued"),
+                _ => ("❓", "was removed"),
+            };
+            format!(
+                "{emoji} Merge queue group <a href=\"{commit_url}\">{short_sha}</a> for {base_branch} in <a href=\"{repository_url}\">{repository_name}</a> {outcome}"
+            )
+        }
+        _ => String::new(),
+    }
+}
@@ -1,10 +1,22 @@
+pub mod buttons;
 pub mod check_run;
+pub mod check_suite;
 pub mod comment;
 pub mod create_delete;
+pub mod discussion;
+pub mod fork;
 pub mod issue;
+pub mod member;
+pub mod mentions;
+pub mod merge_group;
 pub mod ping;
+pub mod public;
 pub mod pull_request;
 pub mod push;
+pub mod registry_package;
+pub mod review;
+pub mod security_alert;
+pub mod star;
 #[cfg(test)]
 mod test_branch_filtering;
 mod utils;
@@ -12,11 +24,24 @@ pub mod wiki;
 pub mod workflow_run;
 
 pub use check_run::handle_check_run_event;
+pub use check_suite::handle_check_suite_event;
 pub use comment::handle_comment_event;
 pub use create_delete::{handle_create_event, handle_delete_event};
+pub use discussion::{handle_discussion_comment_event, handle_discussion_event};
+pub use fork::handle_fork_event;
 pub use issue::handle_issue_event;
+pub use member::handle_member_event;
+pub use merge_group::handle_merge_group_event;
 pub use ping::handle_ping_event;
+pub use public::handle_public_event;
 pub use pull_request::handle_pull_request_event;
 pub use push::handle_push_event;
+pub use registry_package::handle_registry_package_event;
+pub use review::handle_review_event;
+pub use security_alert::{
+    handle_code_scanning_alert_event, handle_dependabot_alert_event,
+    handle_repository_vulnerability_alert_event, handle_secret_scanning_alert_event, AlertSeverity,
+};
+pub use star::handle_star_event;
 pub use wiki::handle_wiki_event;
 pub use workflow_run::handle_workflow_run_event;
@@ -0,0 +1,38 @@
+use super::utils::parse_webhook_payload;
+use actix_web::web;
+use html_escape::encode_text;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct PublicEvent {
+    repository: Repository,
+    sender: Sender,
+}
+
+#[derive(Debug, Deserialize)]
+struct Repository {
+    name: String,
+    html_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Sender {
+    login: String,
+}
+
+pub fn handle_public_event(body: &web::Bytes) -> String {
+    let public_event: PublicEvent = match parse_webhook_payload(body) {
+        Ok(event) => event,
+        Err(e) => {
+            tracing::error!("Failed to parse public event: {}", e);
+            tracing::error!("Raw payload: {}", String::from_utf8_lossy(body));
+            return String::new();
+        }
+    };
+
+    let repository_name = encode_text(&public_event.repository.name);
+    let repository_url = &public_event.repository.html_url;
+    let sender = encode_text(&public_event.sender.login);
+
+    format!("🎉 <b>{sender}</b> made <a href=\"{repository_url}\">{repository_name}</a> public")
+}
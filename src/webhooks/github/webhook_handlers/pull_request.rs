@@ -10,6 +10,9 @@ pub struct PullRequestEvent {
     pull_request: PullRequest,
     repository: Repository,
     sender: Sender,
+    requested_reviewer: Option<Sender>,
+    requested_team: Option<Team>,
+    assignee: Option<Sender>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -38,6 +41,11 @@ struct Sender {
     login: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct Team {
+    name: String,
+}
+
 pub fn handle_pull_request_event(
     body: &web::Bytes,
     branch_filter: Option<&BranchFilter>,
@@ -90,6 +98,23 @@ pub fn handle_pull_request_event(
         "reopened" => format!(
             "<b>{sender}</b> reopened pull request <a href=\"{pr_url}\">#{pr_number}</a> in <a href=\"{repository_url}\">{repository_name}</a>"
         ),
+        "review_requested" => {
+            let reviewer = match (&pr_event.requested_reviewer, &pr_event.requested_team) {
+                (Some(reviewer), _) => encode_text(&reviewer.login).into_owned(),
+                (None, Some(team)) => format!("team {}", encode_text(&team.name)),
+                (None, None) => return String::new(),
+            };
+            format!(
+                "<b>{sender}</b> requested review from <b>{reviewer}</b> on pull request <a href=\"{pr_url}\">#{pr_number}</a> in <a href=\"{repository_url}\">{repository_name}</a>:\n{pr_title}"
+            )
+        }
+        "assigned" => match &pr_event.assignee {
+            Some(assignee) => format!(
+                "<b>{sender}</b> assigned <b>{}</b> to pull request <a href=\"{pr_url}\">#{pr_number}</a> in <a href=\"{repository_url}\">{repository_name}</a>:\n{pr_title}",
+                encode_text(&assignee.login)
+            ),
+            None => String::new(),
+        },
         _ => String::new(),
     }
 }
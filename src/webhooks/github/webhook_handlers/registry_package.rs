@@ -0,0 +1,199 @@
+use super::utils::parse_webhook_payload;
+use crate::utils::tag_filter::TagFilter;
+use actix_web::web;
+use html_escape::encode_text;
+use serde::Deserialize;
+
+/// Payload shared by the `package` and `registry_package` events. GitHub sends the
+/// same package object under a different key depending on the event.
+#[derive(Debug, Deserialize)]
+pub struct RegistryPackageEvent {
+    action: String,
+    #[serde(alias = "package")]
+    registry_package: Package,
+    repository: Option<Repository>,
+    sender: Sender,
+}
+
+#[derive(Debug, Deserialize)]
+struct Package {
+    name: String,
+    namespace: Option<String>,
+    package_type: Option<String>,
+    html_url: Option<String>,
+    package_version: Option<PackageVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PackageVersion {
+    version: Option<String>,
+    html_url: Option<String>,
+    package_url: Option<String>,
+    container_metadata: Option<ContainerMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContainerMetadata {
+    tag: Option<ContainerTag>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContainerTag {
+    name: Option<String>,
+    digest: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Repository {
+    name: String,
+    html_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Sender {
+    login: String,
+}
+
+pub fn handle_registry_package_event(body: &web::Bytes, tag_filter: Option<&TagFilter>) -> String {
+    let package_event: RegistryPackageEvent = match parse_webhook_payload(body) {
+        Ok(event) => event,
+        Err(e) => {
+            tracing::error!("Failed to parse registry package event: {}", e);
+            tracing::error!("Raw payload: {}", String::from_utf8_lossy(body));
+            return String::new();
+        }
+    };
+
+    let action = match package_event.action.as_str() {
+        "published" => "published",
+        "updated" => "updated",
+        _ => return String::new(),
+    };
+
+    let package = &package_event.registry_package;
+    let version = package.package_version.as_ref();
+    let container_tag = version
+        .and_then(|v| v.container_metadata.as_ref())
+        .and_then(|m| m.tag.as_ref());
+
+    // Container versions carry the tag separately; other ecosystems use the version string
+    let tag_raw = container_tag
+        .and_then(|t| t.name.as_deref())
+        .filter(|t| !t.is_empty())
+        .or_else(|| {
+            version
+                .and_then(|v| v.version.as_deref())
+                .filter(|v| !v.starts_with("sha256:"))
+        })
+        .unwrap_or("");
+    let digest_raw = container_tag.and_then(|t| t.digest.as_deref()).or_else(|| {
+        version
+            .and_then(|v| v.version.as_deref())
+            .filter(|v| v.starts_with("sha256:"))
+    });
+
+    if let Some(filter) = tag_filter {
+        if !filter.should_process(tag_raw) {
+            tracing::info!("Filtered out registry package event for tag: {}", tag_raw);
+            return String::new();
+        }
+    }
+
+    let package_name = match &package.namespace {
+        Some(namespace) => encode_text(&format!("{}/{}", namespace, package.name)).into_owned(),
+        None => encode_text(&package.name).into_owned(),
+    };
+    let package_url = version
+        .and_then(|v| v.html_url.as_deref())
+        .or(package.html_url.as_deref());
+    let package_link = match package_url {
+        Some(url) => format!("<a href=\"{url}\">{package_name}</a>"),
+        None => format!("<b>{package_name}</b>"),
+    };
+    let package_type = package
+        .package_type
+        .as_deref()
+        .map(|t| t.to_lowercase())
+        .unwrap_or_else(|| "package".to_string());
+    let sender = encode_text(&package_event.sender.login);
+
+    let mut message = if tag_raw.is_empty() {
+        format!("📦 <b>{sender}</b> {action} {package_type} {package_link}")
+    } else {
+        let tag = encode_text(tag_raw);
+        format!("📦 <b>{sender}</b> {action} {package_type} {package_link}:<code>{tag}</code>")
+    };
+
+    if let Some(repository) = &package_event.repository {
+        let repository_name = encode_text(&repository.name);
+        message.push_str(&format!(
+            " in <a href=\"{}\">{repository_name}</a>",
+            repository.html_url
+        ));
+    }
+
+    if let Some(digest) = digest_raw {
+        message.push_str(&format!("\nDigest: <code>{}</code>", encode_text(digest)));
+    }
+
+    if let Some(pull_url) = version.and_then(|v| v.package_url.as_deref()) {
+        message.push_str(&format!("\n<code>{}</code>", encode_text(pull_url)));
+    }
+
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTAINER_PAYLOAD: &str = r#"{
+        "action": "published",
+        "registry_package": {
+            "name": "api",
+            "namespace": "acme",
+            "package_type": "CONTAINER",
+            "html_url": "https://github.com/orgs/acme/packages/container/package/api",
+            "package_version": {
+                "version": "sha256:1f2e3d",
+                "html_url": "https://github.com/orgs/acme/packages/container/api/123",
+                "package_url": "ghcr.io/acme/api:v1.2.0",
+                "container_metadata": {
+                    "tag": {"name": "v1.2.0", "digest": "sha256:1f2e3d"}
+                }
+            }
+        },
+        "repository": {"name": "api", "html_url": "https://github.com/acme/api"},
+        "sender": {"login": "octocat"}
+    }"#;
+
+    #[test]
+    fn test_container_package_published() {
+        let body = web::Bytes::from(CONTAINER_PAYLOAD);
+        let result = handle_registry_package_event(&body, None);
+
+        assert!(result.contains("<b>octocat</b> published container"));
+        assert!(result.contains("acme/api</a>:<code>v1.2.0</code>"));
+        assert!(result.contains("Digest: <code>sha256:1f2e3d</code>"));
+        assert!(result.contains("ghcr.io/acme/api:v1.2.0"));
+    }
+
+    #[test]
+    fn test_package_event_alias() {
+        let body = web::Bytes::from(CONTAINER_PAYLOAD.replace("registry_package", "package"));
+        let result = handle_registry_package_event(&body, None);
+
+        assert!(result.contains("<code>v1.2.0</code>"));
+    }
+
+    #[test]
+    fn test_tag_filter() {
+        let body = web::Bytes::from(CONTAINER_PAYLOAD);
+
+        let release_only = TagFilter::new(Some("v*"), None).unwrap();
+        assert!(!handle_registry_package_event(&body, Some(&release_only)).is_empty());
+
+        let latest_only = TagFilter::new(Some("latest"), None).unwrap();
+        assert!(handle_registry_package_event(&body, Some(&latest_only)).is_empty());
+    }
+}
@@ -0,0 +1,100 @@
+use super::utils::{parse_webhook_payload, truncate_body};
+use actix_web::web;
+use html_escape::encode_text;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewEvent {
+    action: String,
+    review: Review,
+    pull_request: PullRequest,
+    repository: Repository,
+    sender: Sender,
+}
+
+#[derive(Debug, Deserialize)]
+struct Review {
+    html_url: String,
+    state: String,
+    body: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequest {
+    number: i64,
+    title: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Repository {
+    name: String,
+    html_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Sender {
+    login: String,
+}
+
+pub fn handle_review_event(body: &web::Bytes) -> String {
+    let review_event: ReviewEvent = match parse_webhook_payload(body) {
+        Ok(event) => event,
+        Err(e) => {
+            tracing::error!("Failed to parse pull request review event: {}", e);
+            tracing::error!("Raw payload: {}", String::from_utf8_lossy(body));
+            return String::new();
+        }
+    };
+
+    let review = &review_event.review;
+    let review_url = &review.html_url;
+    let pr_number = review_event.pull_request.number;
+    let pr_title = encode_text(&review_event.pull_request.title);
+    let repository_name = encode_text(&review_event.repository.name);
+    let repository_url = &review_event.repository.html_url;
+    let sender = encode_text(&review_event.sender.login);
+
+    let verb = match (review_event.action.as_str(), review.state.as_str()) {
+        ("submitted", "approved") => "✅ approved",
+        ("submitted", "changes_requested") => "🔁 requested changes on",
+        ("submitted", "commented") => "💬 reviewed",
+        ("dismissed", _) => "dismissed a review on",
+        _ => return String::new(),
+    };
+
+    let mut message = format!(
+        "<b>{sender}</b> {verb} pull request <a href=\"{review_url}\">#{pr_number}</a> in <a href=\"{repository_url}\">{repository_name}</a>:\n{pr_title}"
+    );
+
+    if let Some(review_body) = review.body.as_deref().filter(|b| !b.trim().is_empty()) {
+        message.push_str(&format!("\n\n{}", truncate_body(review_body, review_url)));
+    }
+
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_review_submitted() {
+        let body = web::Bytes::from(
+            r#"{
+                "action": "submitted",
+                "review": {
+                    "html_url": "https://github.com/acme/api/pull/12#pullrequestreview-1",
+                    "state": "changes_requested",
+                    "body": "Please add tests"
+                },
+                "pull_request": {"number": 12, "title": "Add caching", "user": {"login": "alice"}},
+                "repository": {"name": "api", "html_url": "https://github.com/acme/api"},
+                "sender": {"login": "bob"}
+            }"#,
+        );
+        let result = handle_review_event(&body);
+
+        assert!(result.starts_with("<b>bob</b> 🔁 requested changes on pull request"));
+        assert!(result.ends_with("Add caching\n\nPlease add tests"));
+    }
+}
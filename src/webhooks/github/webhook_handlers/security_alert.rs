@@ -0,0 +1,570 @@
+use super::utils::parse_webhook_payload;
+use actix_web::web;
+use html_escape::encode_text;
+use serde::Deserialize;
+
+/// Normalized severity shared by all GitHub security alert types, ordered so that
+/// a per-webhook minimum severity can be compared directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AlertSeverity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl AlertSeverity {
+    pub fn parse(s: &str) -> Option<AlertSeverity> {
+        match s.to_lowercase().as_str() {
+            "low" | "note" => Some(AlertSeverity::Low),
+            "medium" | "moderate" | "warning" => Some(AlertSeverity::Medium),
+            "high" | "error" => Some(AlertSeverity::High),
+            "critical" => Some(AlertSeverity::Critical),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertSeverity::Low => "low",
+            AlertSeverity::Medium => "medium",
+            AlertSeverity::High => "high",
+            AlertSeverity::Critical => "critical",
+        }
+    }
+
+    pub fn emoji(&self) -> &'static str {
+        match self {
+            AlertSeverity::Low => "🔵",
+            AlertSeverity::Medium => "🟡",
+            AlertSeverity::High => "🟠",
+            AlertSeverity::Critical => "🔴",
+        }
+    }
+}
+
+fn is_below_minimum(severity: AlertSeverity, min_severity: Option<AlertSeverity>) -> bool {
+    match min_severity {
+        Some(min) if severity < min => {
+            tracing::info!(
+                "Filtered out {} security alert below minimum severity {}",
+                severity.as_str(),
+                min.as_str()
+            );
+            true
+        }
+        _ => false,
+    }
+}
+
+fn advisory_links(ghsa_id: Option<&str>, cve_id: Option<&str>) -> Option<String> {
+    let mut links = Vec::with_capacity(2);
+
+    if let Some(ghsa) = ghsa_id {
+        let ghsa = encode_text(ghsa);
+        links.push(format!(
+            "<a href=\"https://github.com/advisories/{ghsa}\">{ghsa}</a>"
+        ));
+    }
+    if let Some(cve) = cve_id {
+        let cve = encode_text(cve);
+        links.push(format!(
+            "<a href=\"https://nvd.nist.gov/vuln/detail/{cve}\">{cve}</a>"
+        ));
+    }
+
+    if links.is_empty() {
+        None
+    } else {
+        Some(links.join(" · "))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Repository {
+    name: String,
+    html_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Sender {
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DependabotAlertEvent {
+    action: String,
+    alert: DependabotAlert,
+    repository: Repository,
+    sender: Option<Sender>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DependabotAlert {
+    number: i64,
+    html_url: String,
+    dependency: Dependency,
+    security_advisory: SecurityAdvisory,
+    security_vulnerability: Option<SecurityVulnerability>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Dependency {
+    package: DependencyPackage,
+    manifest_path: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DependencyPackage {
+    ecosystem: String,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SecurityAdvisory {
+    ghsa_id: Option<String>,
+    cve_id: Option<String>,
+    summary: String,
+    severity: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SecurityVulnerability {
+    first_patched_version: Option<PatchedVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PatchedVersion {
+    identifier: String,
+}
+
+pub fn handle_dependabot_alert_event(
+    body: &web::Bytes,
+    min_severity: Option<AlertSeverity>,
+) -> String {
+    let alert_event: DependabotAlertEvent = match parse_webhook_payload(body) {
+        Ok(event) => event,
+        Err(e) => {
+            tracing::error!("Failed to parse dependabot alert event: {}", e);
+            tracing::error!("Raw payload: {}", String::from_utf8_lossy(body));
+            return String::new();
+        }
+    };
+
+    let alert = &alert_event.alert;
+    let advisory = &alert.security_advisory;
+    let severity = AlertSeverity::parse(&advisory.severity).unwrap_or(AlertSeverity::Medium);
+
+    if is_below_minimum(severity, min_severity) {
+        return String::new();
+    }
+
+    let alert_url = &alert.html_url;
+    let alert_number = alert.number;
+    let repository_name = encode_text(&alert_event.repository.name);
+    let repository_url = &alert_event.repository.html_url;
+    let alert_link = format!(
+        "Dependabot alert <a href=\"{alert_url}\">#{alert_number}</a> in <a href=\"{repository_url}\">{repository_name}</a>"
+    );
+
+    match alert_event.action.as_str() {
+        "created" | "reopened" | "reintroduced" | "auto_reopened" => {
+            let action = match alert_event.action.as_str() {
+                "created" => "New",
+                _ => "Reopened",
+            };
+            let package_name = encode_text(&alert.dependency.package.name);
+            let ecosystem = encode_text(&alert.dependency.package.ecosystem);
+            let summary = encode_text(&advisory.summary);
+
+            let mut message = format!(
+                "{} {action} {alert_link} ({})\n<b>{package_name}</b> ({ecosystem})",
+                severity.emoji(),
+                severity.as_str()
+            );
+            if let Some(manifest) = &alert.dependency.manifest_path {
+                message.push_str(&format!(" in <code>{}</code>", encode_text(manifest)));
+            }
+            message.push_str(&format!("\n{summary}"));
+            if let Some(links) =
+                advisory_links(advisory.ghsa_id.as_deref(), advisory.cve_id.as_deref())
+            {
+                message.push_str(&format!("\n{links}"));
+            }
+            if let Some(patched) = alert
+                .security_vulnerability
+                .as_ref()
+                .and_then(|v| v.first_patched_version.as_ref())
+            {
+                message.push_str(&format!(
+                    "\nPatched in <code>{}</code>",
+                    encode_text(&patched.identifier)
+                ));
+            }
+            message
+        }
+        "fixed" => format!("✅ {alert_link} was fixed"),
+        "dismissed" | "auto_dismissed" => match &alert_event.sender {
+            Some(sender) if alert_event.action == "dismissed" => format!(
+                "⚪ {alert_link} was dismissed by <b>{}</b>",
+                encode_text(&sender.login)
+            ),
+            _ => format!("⚪ {alert_link} was dismissed"),
+        },
+        _ => String::new(),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CodeScanningAlertEvent {
+    action: String,
+    alert: CodeScanningAlert,
+    #[serde(rename = "ref")]
+    ref_name: Option<String>,
+    repository: Repository,
+}
+
+#[derive(Debug, Deserialize)]
+struct CodeScanningAlert {
+    number: i64,
+    html_url: String,
+    rule: CodeScanningRule,
+    tool: CodeScanningTool,
+    most_recent_instance: Option<CodeScanningInstance>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CodeScanningRule {
+    description: String,
+    severity: Option<String>,
+    security_severity_level: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CodeScanningTool {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CodeScanningInstance {
+    location: Option<CodeScanningLocation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CodeScanningLocation {
+    path: Option<String>,
+    start_line: Option<i64>,
+}
+
+pub fn handle_code_scanning_alert_event(
+    body: &web::Bytes,
+    min_severity: Option<AlertSeverity>,
+) -> String {
+    let alert_event: CodeScanningAlertEvent = match parse_webhook_payload(body) {
+        Ok(event) => event,
+        Err(e) => {
+            tracing::error!("Failed to parse code scanning alert event: {}", e);
+            tracing::error!("Raw payload: {}", String::from_utf8_lossy(body));
+            return String::new();
+        }
+    };
+
+    let alert = &alert_event.alert;
+    // Prefer the security severity; plain rule severity is only set for non-security queries
+    let severity = alert
+        .rule
+        .security_severity_level
+        .as_deref()
+        .or(alert.rule.severity.as_deref())
+        .and_then(AlertSeverity::parse)
+        .unwrap_or(AlertSeverity::Low);
+
+    if is_below_minimum(severity, min_severity) {
+        return String::new();
+    }
+
+    let alert_url = &alert.html_url;
+    let alert_number = alert.number;
+    let repository_name = encode_text(&alert_event.repository.name);
+    let repository_url = &alert_event.repository.html_url;
+    let tool_name = encode_text(&alert.tool.name);
+    let alert_link = format!(
+        "{tool_name} alert <a href=\"{alert_url}\">#{alert_number}</a> in <a href=\"{repository_url}\">{repository_name}</a>"
+    );
+
+    match alert_event.action.as_str() {
+        "created" | "reopened" | "reopened_by_user" | "appeared_in_branch" => {
+            let description = encode_text(&alert.rule.description);
+            let mut message = format!(
+                "{} {alert_link} ({})\n{description}",
+                severity.emoji(),
+                severity.as_str()
+            );
+            let location = alert
+                .most_recent_instance
+                .as_ref()
+                .and_then(|i| i.location.as_ref());
+            if let Some(path) = location.and_then(|l| l.path.as_deref()) {
+                let path = encode_text(path);
+                match location.and_then(|l| l.start_line) {
+                    Some(line) => message.push_str(&format!("\n<code>{path}:{line}</code>")),
+                    None => message.push_str(&format!("\n<code>{path}</code>")),
+                }
+            }
+            if let Some(ref_name) = &alert_event.ref_name {
+                let branch = encode_text(ref_name.trim_start_matches("refs/heads/"));
+                message.push_str(&format!(" on {branch}"));
+            }
+            message
+        }
+        "fixed" => format!("✅ {alert_link} was fixed"),
+        "closed_by_user" => format!("⚪ {alert_link} was closed"),
+        _ => String::new(),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SecretScanningAlertEvent {
+    action: String,
+    alert: SecretScanningAlert,
+    repository: Repository,
+}
+
+#[derive(Debug, Deserialize)]
+struct SecretScanningAlert {
+    number: i64,
+    html_url: String,
+    secret_type: String,
+    secret_type_display_name: Option<String>,
+    resolution: Option<String>,
+}
+
+pub fn handle_secret_scanning_alert_event(
+    body: &web::Bytes,
+    min_severity: Option<AlertSeverity>,
+) -> String {
+    let alert_event: SecretScanningAlertEvent = match parse_webhook_payload(body) {
+        Ok(event) => event,
+        Err(e) => {
+            tracing::error!("Failed to parse secret scanning alert event: {}", e);
+            tracing::error!("Raw payload: {}", String::from_utf8_lossy(body));
+            return String::new();
+        }
+    };
+
+    // A leaked credential has no advisory severity, so always treat it as critical
+    let severity = AlertSeverity::Critical;
+
+    if is_below_minimum(severity, min_severity) {
+        return String::new();
+    }
+
+    let alert = &alert_event.alert;
+    let alert_url = &alert.html_url;
+    let alert_number = alert.number;
+    let repository_name = encode_text(&alert_event.repository.name);
+    let repository_url = &alert_event.repository.html_url;
+    let secret_type = encode_text(
+        alert
+            .secret_type_display_name
+            .as_deref()
+            .unwrap_or(&alert.secret_type),
+    );
+    let alert_link = format!(
+        "Secret scanning alert <a href=\"{alert_url}\">#{alert_number}</a> in <a href=\"{repository_url}\">{repository_name}</a>"
+    );
+
+    match alert_event.action.as_str() {
+        "created" => format!(
+            "{} {alert_link}\nExposed secret: <b>{secret_type}</b>",
+            severity.emoji()
+        ),
+        "publicly_leaked" => format!(
+            "{} {alert_link}\n<b>{secret_type}</b> was found in a public location",
+            severity.emoji()
+        ),
+        "reopened" => format!(
+            "{} Reopened {alert_link}\nExposed secret: <b>{secret_type}</b>",
+            severity.emoji()
+        ),
+        "resolved" => {
+            let resolution = encode_text(alert.resolution.as_deref().unwrap_or("resolved"));
+            format!("✅ {alert_link} was resolved ({resolution})")
+        }
+        _ => String::new(),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RepositoryVulnerabilityAlertEvent {
+    action: String,
+    alert: RepositoryVulnerabilityAlert,
+    repository: Repository,
+}
+
+#[derive(Debug, Deserialize)]
+struct RepositoryVulnerabilityAlert {
+    affected_package_name: String,
+    affected_range: Option<String>,
+    external_identifier: Option<String>,
+    ghsa_id: Option<String>,
+    severity: Option<String>,
+    fixed_in: Option<String>,
+}
+
+pub fn handle_repository_vulnerability_alert_event(
+    body: &web::Bytes,
+    min_severity: Option<AlertSeverity>,
+) -> String {
+    let alert_event: RepositoryVulnerabilityAlertEvent = match parse_webhook_payload(body) {
+        Ok(event) => event,
+        Err(e) => {
+            tracing::error!(
+                "Failed to parse repository vulnerability alert event: {}",
+                e
+            );
+            tracing::error!("Raw payload: {}", String::from_utf8_lossy(body));
+            return String::new();
+        }
+    };
+
+    let alert = &alert_event.alert;
+    let severity = alert
+        .severity
+        .as_deref()
+        .and_then(AlertSeverity::parse)
+        .unwrap_or(AlertSeverity::Medium);
+
+    if is_below_minimum(severity, min_severity) {
+        return String::new();
+    }
+
+    let repository_name = encode_text(&alert_event.repository.name);
+    let repository_url = &alert_event.repository.html_url;
+    let package_name = encode_text(&alert.affected_package_name);
+
+    match alert_event.action.as_str() {
+        "create" | "reopen" => {
+            let mut message = format!(
+                "{} Vulnerable dependency <b>{package_name}</b> in <a href=\"{repository_url}\">{repository_name}</a> ({})",
+                severity.emoji(),
+                severity.as_str()
+            );
+            if let Some(range) = &alert.affected_range {
+                message.push_str(&format!("\nAffected: <code>{}</code>", encode_text(range)));
+            }
+            let cve_id = alert
+                .external_identifier
+                .as_deref()
+                .filter(|id| id.starts_with("CVE-"));
+            if let Some(links) = advisory_links(alert.ghsa_id.as_deref(), cve_id) {
+                message.push_str(&format!("\n{links}"));
+            }
+            if let Some(fixed_in) = &alert.fixed_in {
+                message.push_str(&format!(
+                    "\nPatched in <code>{}</code>",
+                    encode_text(fixed_in)
+                ));
+            }
+            message
+        }
+        "resolve" => format!(
+            "✅ Vulnerable dependency <b>{package_name}</b> in <a href=\"{repository_url}\">{repository_name}</a> was resolved"
+        ),
+        "dismiss" => format!(
+            "⚪ Vulnerable dependency <b>{package_name}</b> in <a href=\"{repository_url}\">{repository_name}</a> was dismissed"
+        ),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEPENDABOT_PAYLOAD: &str = r#"{
+        "action": "created",
+        "alert": {
+            "number": 7,
+            "state": "open",
+            "html_url": "https://github.com/acme/api/security/dependabot/7",
+            "dependency": {
+                "package": {"ecosystem": "npm", "name": "lodash"},
+                "manifest_path": "package-lock.json",
+                "scope": "runtime"
+            },
+            "security_advisory": {
+                "ghsa_id": "GHSA-jf85-cpcp-j695",
+                "cve_id": "CVE-2019-10744",
+                "summary": "Prototype Pollution in lodash",
+                "severity": "critical"
+            },
+            "security_vulnerability": {
+                "severity": "critical",
+                "first_patched_version": {"identifier": "4.17.12"}
+            }
+        },
+        "repository": {"name": "api", "html_url": "https://github.com/acme/api"},
+        "sender": {"login": "dependabot[bot]"}
+    }"#;
+
+    #[test]
+    fn test_severity_ordering_and_parsing() {
+        assert!(AlertSeverity::Critical > AlertSeverity::High);
+        assert!(AlertSeverity::Medium > AlertSeverity::Low);
+        assert_eq!(
+            AlertSeverity::parse("moderate"),
+            Some(AlertSeverity::Medium)
+        );
+        assert_eq!(AlertSeverity::parse("error"), Some(AlertSeverity::High));
+        assert_eq!(AlertSeverity::parse("unknown"), None);
+    }
+
+    #[test]
+    fn test_dependabot_alert_created() {
+        let body = web::Bytes::from(DEPENDABOT_PAYLOAD);
+        let result = handle_dependabot_alert_event(&body, None);
+
+        assert!(result.starts_with("🔴 New Dependabot alert"));
+        assert!(result.contains("<b>lodash</b> (npm) in <code>package-lock.json</code>"));
+        assert!(result.contains("https://github.com/advisories/GHSA-jf85-cpcp-j695"));
+        assert!(result.contains("https://nvd.nist.gov/vuln/detail/CVE-2019-10744"));
+        assert!(result.contains("Patched in <code>4.17.12</code>"));
+    }
+
+    #[test]
+    fn test_min_severity_filter() {
+        let low_payload = DEPENDABOT_PAYLOAD.replace("\"critical\"", "\"low\"");
+        let body = web::Bytes::from(low_payload);
+
+        assert!(handle_dependabot_alert_event(&body, Some(AlertSeverity::High)).is_empty());
+        assert!(!handle_dependabot_alert_event(&body, Some(AlertSeverity::Low)).is_empty());
+    }
+
+    #[test]
+    fn test_code_scanning_alert_uses_security_severity() {
+        let payload = r#"{
+            "action": "created",
+            "alert": {
+                "number": 3,
+                "html_url": "https://github.com/acme/api/security/code-scanning/3",
+                "rule": {
+                    "description": "Database query built from user-controlled sources",
+                    "severity": "warning",
+                    "security_severity_level": "high"
+                },
+                "tool": {"name": "CodeQL"},
+                "most_recent_instance": {
+                    "location": {"path": "src/db.rs", "start_line": 42}
+                }
+            },
+            "ref": "refs/heads/main",
+            "repository": {"name": "api", "html_url": "https://github.com/acme/api"}
+        }"#;
+        let body = web::Bytes::from(payload);
+        let result = handle_code_scanning_alert_event(&body, Some(AlertSeverity::High));
+
+        assert!(result.starts_with("🟠 CodeQL alert"));
+        assert!(result.contains("<code>src/db.rs:42</code> on main"));
+    }
+}
@@ -0,0 +1,51 @@
+use super::utils::parse_webhook_payload;
+use actix_web::web;
+use html_escape::encode_text;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct StarEvent {
+    action: String,
+    repository: Repository,
+    sender: Sender,
+}
+
+#[derive(Debug, Deserialize)]
+struct Repository {
+    name: String,
+    html_url: String,
+    stargazers_count: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct Sender {
+    login: String,
+    html_url: String,
+}
+
+pub fn handle_star_event(body: &web::Bytes) -> String {
+    let star_event: StarEvent = match parse_webhook_payload(body) {
+        Ok(event) => event,
+        Err(e) => {
+            tracing::error!("Failed to parse star event: {}", e);
+            tracing::error!("Raw payload: {}", String::from_utf8_lossy(body));
+            return String::new();
+        }
+    };
+
+    let repository_name = encode_text(&star_event.repository.name);
+    let repository_url = &star_event.repository.html_url;
+    let stargazers_count = star_event.repository.stargazers_count;
+    let sender = encode_text(&star_event.sender.login);
+    let sender_url = &star_event.sender.html_url;
+
+    match star_event.action.as_str() {
+        "created" => format!(
+            "⭐ <a href=\"{sender_url}\">{sender}</a> starred <a href=\"{repository_url}\">{repository_name}</a> ({stargazers_count} stars)"
+        ),
+        "deleted" => format!(
+            "<a href=\"{sender_url}\">{sender}</a> unstarred <a href=\"{repository_url}\">{repository_name}</a> ({stargazers_count} stars)"
+        ),
+        _ => String::new(),
+    }
+}
@@ -1,6 +1,20 @@
+use html_escape::encode_text;
 use serde::de::DeserializeOwned;
 use serde_urlencoded;
-use ureq::serde_json;
+
+/// Escapes a comment or review body for HTML and cuts it to 100 characters, with a
+/// link to the full text.
+pub fn truncate_body(body: &str, url: &str) -> String {
+    let body = encode_text(body.trim());
+
+    if body.chars().count() <= 100 {
+        body.into_owned()
+    } else {
+        let mut truncated: String = body.chars().take(100).collect();
+        truncated.push_str(&format!("<a href=\"{}\">...</a>", url));
+        truncated
+    }
+}
 
 pub fn parse_webhook_payload<T>(body: &[u8]) -> Result<T, String>
 where
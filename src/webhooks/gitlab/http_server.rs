@@ -3,8 +3,17 @@ use crate::webhooks::common::{process_webhook, WebhookContext};
 use crate::webhooks::gitlab::webhook_handlers::job::handle_job_event;
 use crate::webhooks::gitlab::webhook_handlers::merge_request::handle_merge_request_event;
 use crate::webhooks::gitlab::webhook_handlers::{
-    issue::handle_issue_event, note::handle_note_event, push::handle_push_event,
-    tag_push::handle_tag_push_event, unknown_event::handle_unknown_event,
+    access_token::handle_access_token_event,
+    buttons::extract_buttons,
+    emoji::handle_emoji_event,
+    feature_flag::handle_feature_flag_event,
+    issue::handle_issue_event,
+    mentions::{extract_mentions, extract_personal_mentions},
+    note::handle_note_event,
+    push::handle_push_event,
+    release::handle_release_event,
+    tag_push::handle_tag_push_event,
+    unknown_event::handle_unknown_event,
 };
 use actix_web::{post, web, HttpRequest, HttpResponse, Responder};
 use notifine::db::DbPool;
@@ -131,10 +140,24 @@ pub async fn handle_gitlab_webhook(
             "Issue Hook" => handle_issue_event(&body),
             "Note Hook" => handle_note_event(&body, full_message),
             "Merge Request Hook" => handle_merge_request_event(&body, branch_filter.as_ref()),
-            "Job Hook" => handle_job_event(&body),
+            "Job Hook" => handle_job_event(&body, branch_filter.as_ref()),
+            "Release Hook" => handle_release_event(&body),
+            "Feature Flag Hook" => handle_feature_flag_event(&body),
+            "Emoji Hook" => handle_emoji_event(&body),
+            "Resource Access Token Hook" => handle_access_token_event(&body),
             name => handle_unknown_event(name.to_string()),
         };
 
+        let (mentions, direct_mentions, buttons) = if message.is_empty() {
+            (Vec::new(), Vec::new(), Vec::new())
+        } else {
+            (
+                extract_mentions(event_str, &body),
+                extract_personal_mentions(event_str, &body),
+                extract_buttons(event_str, &body),
+            )
+        };
+
         let gitlab_token = match env::var("GITLAB_TELOXIDE_TOKEN") {
             Ok(token) => token,
             Err(_) => {
@@ -151,6 +174,9 @@ pub async fn handle_gitlab_webhook(
             token: gitlab_token,
             event_name: event_str,
             source: "gitlab",
+            mentions,
+            direct_mentions,
+            buttons,
         })
         .await
     } else {
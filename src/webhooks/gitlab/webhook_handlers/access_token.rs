@@ -0,0 +1,106 @@
+use actix_web::web;
+use html_escape::encode_text;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct AccessTokenEvent {
+    event_name: String,
+    project: Option<Project>,
+    group: Option<Group>,
+    object_attributes: AccessToken,
+}
+
+#[derive(Debug, Deserialize)]
+struct Project {
+    name: String,
+    web_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Group {
+    group_name: String,
+    full_path: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AccessToken {
+    name: String,
+    expires_at: Option<String>,
+}
+
+pub fn handle_access_token_event(body: &web::Bytes) -> String {
+    let access_token_event: AccessTokenEvent = match serde_json::from_slice(body) {
+        Ok(e) => e,
+        Err(e) => {
+            tracing::error!("Failed to parse GitLab access token event: {}", e);
+            return String::new();
+        }
+    };
+
+    let token_name = encode_text(&access_token_event.object_attributes.name);
+    let expires_at = access_token_event
+        .object_attributes
+        .expires_at
+        .as_deref()
+        .map(encode_text)
+        .unwrap_or_else(|| "an unknown date".into());
+
+    let owner = match (&access_token_event.project, &access_token_event.group) {
+        (Some(project), _) => format!(
+            "project <a href=\"{}\">{}</a>",
+            project.web_url,
+            encode_text(&project.name)
+        ),
+        (None, Some(group)) => format!(
+            "group <b>{}</b> ({})",
+            encode_text(&group.group_name),
+            encode_text(&group.full_path)
+        ),
+        (None, None) => return String::new(),
+    };
+
+    match access_token_event.event_name.as_str() {
+        "expiring_access_token" => format!(
+            "⚠️ Access token <b>{token_name}</b> for {owner} expires on {expires_at}\nRotate it before pipelines and integrations using it start failing.\n"
+        ),
+        "expired_access_token" => format!(
+            "🔴 Access token <b>{token_name}</b> for {owner} expired on {expires_at}\n"
+        ),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_token_expiring() {
+        let body = web::Bytes::from(
+            r#"{
+                "object_kind": "access_token",
+                "project": {"id": 7, "name": "api", "web_url": "https://gitlab.com/acme/api"},
+                "object_attributes": {"user_id": 90, "created_at": "2024-01-24 16:27:40 UTC", "id": 25, "name": "ci-deploy", "expires_at": "2024-01-26"},
+                "event_name": "expiring_access_token"
+            }"#,
+        );
+        let result = handle_access_token_event(&body);
+
+        assert!(result.starts_with("⚠️ Access token <b>ci-deploy</b> for project <a href=\"https://gitlab.com/acme/api\">api</a> expires on 2024-01-26"));
+    }
+
+    #[test]
+    fn test_group_token_expiring() {
+        let body = web::Bytes::from(
+            r#"{
+                "object_kind": "access_token",
+                "group": {"group_name": "Acme", "group_path": "acme", "group_id": 35, "full_path": "acme"},
+                "object_attributes": {"user_id": 90, "id": 25, "name": "registry", "expires_at": "2024-01-26"},
+                "event_name": "expiring_access_token"
+            }"#,
+        );
+        let result = handle_access_token_event(&body);
+
+        assert!(result.contains("for group <b>Acme</b> (acme) expires on 2024-01-26"));
+    }
+}
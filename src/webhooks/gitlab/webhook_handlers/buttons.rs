@@ -0,0 +1,96 @@
+use crate::services::actions::{Action, NotificationButton};
+use actix_web::web;
+use serde_json::Value;
+
+/// Inline keyboard buttons for a GitLab notification. Action buttons are only shown by
+/// `process_webhook` when the chat has stored an API token.
+pub fn extract_buttons(event: &str, body: &web::Bytes) -> Vec<NotificationButton> {
+    let payload: Value = match serde_json::from_slice(body) {
+        Ok(payload) => payload,
+        Err(_) => return Vec::new(),
+    };
+
+    match event {
+        "Merge Request Hook" => {
+            let attributes = &payload["object_attributes"];
+            let Some(url) = attributes["url"].as_str() else {
+                return Vec::new();
+            };
+
+            let mut buttons = vec![NotificationButton::url("Open MR", url)];
+            if matches!(
+                attributes["action"].as_str(),
+                Some("open" | "reopen" | "update")
+            ) {
+                buttons.push(NotificationButton::url(
+                    "View diff",
+                    &format!("{url}/diffs"),
+                ));
+                if let (Some(project_id), Some(iid)) = (
+                    payload["project"]["id"].as_i64(),
+                    attributes["iid"].as_i64(),
+                ) {
+                    buttons.push(NotificationButton::action(
+                        "Approve",
+                        Action::GitlabApprove { project_id, iid },
+                    ));
+                }
+            }
+            buttons
+        }
+        "Job Hook" => {
+            let (Some(homepage), Some(job_id)) = (
+                payload["repository"]["homepage"].as_str(),
+                payload["build_id"].as_i64(),
+            ) else {
+                return Vec::new();
+            };
+
+            let mut buttons = vec![NotificationButton::url(
+                "Open job",
+                &format!("{homepage}/-/jobs/{job_id}"),
+            )];
+            if payload["build_status"].as_str() == Some("failed") {
+                if let Some(project_id) = payload["project_id"].as_i64() {
+                    buttons.push(NotificationButton::action(
+                        "Retry job",
+                        Action::GitlabRetryJob { project_id, job_id },
+                    ));
+                }
+            }
+            buttons
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failed_job_buttons() {
+        let body = web::Bytes::from(
+            r#"{
+                "project_id": 15,
+                "build_id": 1977,
+                "build_status": "failed",
+                "repository": {"homepage": "https://gitlab.com/acme/api"}
+            }"#,
+        );
+
+        assert_eq!(
+            extract_buttons("Job Hook", &body),
+            vec![
+                NotificationButton::url("Open job", "https://gitlab.com/acme/api/-/jobs/1977"),
+                NotificationButton::action(
+                    "Retry job",
+                    Action::GitlabRetryJob {
+                        project_id: 15,
+                        job_id: 1977
+                    }
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use actix_web::web;
+use html_escape::encode_text;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct EmojiEvent {
+    event_type: String,
+    user: User,
+    object_attributes: Award,
+    issue: Option<Awardable>,
+    merge_request: Option<Awardable>,
+    note: Option<Note>,
+}
+
+#[derive(Debug, Deserialize)]
+struct User {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Award {
+    name: String,
+    awardable_type: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Awardable {
+    title: String,
+    url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Note {
+    url: String,
+}
+
+pub fn handle_emoji_event(body: &web::Bytes) -> String {
+    let emoji_event: EmojiEvent = match serde_json::from_slice(body) {
+        Ok(e) => e,
+        Err(e) => {
+            tracing::error!("Failed to parse GitLab emoji event: {}", e);
+            return String::new();
+        }
+    };
+
+    let (action, preposition) = match emoji_event.event_type.as_str() {
+        "award" => ("reacted with", "on"),
+        "revoke" => ("removed", "from"),
+        _ => return String::new(),
+    };
+
+    let user_name = encode_text(&emoji_event.user.name);
+    let emoji_name = encode_text(&emoji_event.object_attributes.name);
+
+    let target = match emoji_event.object_attributes.awardable_type.as_str() {
+        "Note" => emoji_event
+            .note
+            .as_ref()
+            .map(|note| format!("a <a href=\"{}\">comment</a>", note.url)),
+        "Issue" => emoji_event.issue.as_ref().map(|issue| {
+            format!(
+                "issue <a href=\"{}\">{}</a>",
+                issue.url,
+                encode_text(&issue.title)
+            )
+        }),
+        "MergeRequest" => emoji_event.merge_request.as_ref().map(|merge_request| {
+            format!(
+                "merge request <a href=\"{}\">{}</a>",
+                merge_request.url,
+                encode_text(&merge_request.title)
+            )
+        }),
+        _ => None,
+    };
+
+    match target {
+        Some(target) => {
+            format!("<b>{user_name}</b> {action} :{emoji_name}: {preposition} {target}\n")
+        }
+        None => String::new(),
+    }
+}
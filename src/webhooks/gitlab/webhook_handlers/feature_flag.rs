@@ -0,0 +1,114 @@
+use actix_web::web;
+use html_escape::encode_text;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct FeatureFlagEvent {
+    user: User,
+    project: Project,
+    object_attributes: FeatureFlag,
+}
+
+#[derive(Debug, Deserialize)]
+struct User {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Project {
+    name: String,
+    web_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct FeatureFlag {
+    name: String,
+    active: bool,
+    #[serde(default)]
+    strategies: Vec<Strategy>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Strategy {
+    #[serde(default)]
+    scopes: Vec<Scope>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Scope {
+    environment_scope: String,
+}
+
+pub fn handle_feature_flag_event(body: &web::Bytes) -> String {
+    let feature_flag_event: FeatureFlagEvent = match serde_json::from_slice(body) {
+        Ok(e) => e,
+        Err(e) => {
+            tracing::error!("Failed to parse GitLab feature flag event: {}", e);
+            return String::new();
+        }
+    };
+
+    let feature_flag = &feature_flag_event.object_attributes;
+    let user_name = encode_text(&feature_flag_event.user.name);
+    let flag_name = encode_text(&feature_flag.name);
+    let project_url = &feature_flag_event.project.web_url;
+    let project_name = encode_text(&feature_flag_event.project.name);
+    let flags_url = format!("{project_url}/-/feature_flags");
+    let (emoji, state) = if feature_flag.active {
+        ("🟢", "enabled")
+    } else {
+        ("⚪", "disabled")
+    };
+
+    let mut message = format!(
+        "{emoji} <b>{user_name}</b> {state} feature flag <a href=\"{flags_url}\">{flag_name}</a> in <a href=\"{project_url}\">{project_name}</a>\n"
+    );
+
+    let mut environments: Vec<&str> = feature_flag
+        .strategies
+        .iter()
+        .flat_map(|strategy| strategy.scopes.iter())
+        .map(|scope| scope.environment_scope.as_str())
+        .collect();
+    environments.sort_unstable();
+    environments.dedup();
+
+    if !environments.is_empty() {
+        message.push_str(&format!(
+            "Environments: {}\n",
+            encode_text(&environments.join(", "))
+        ));
+    }
+
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feature_flag_toggled() {
+        let body = web::Bytes::from(
+            r#"{
+                "object_kind": "feature_flag",
+                "project": {"name": "api", "web_url": "https://gitlab.com/acme/api"},
+                "user": {"name": "Alice", "username": "alice"},
+                "object_attributes": {
+                    "id": 6,
+                    "name": "new_checkout",
+                    "description": "",
+                    "active": true,
+                    "strategies": [
+                        {"name": "default", "scopes": [{"environment_scope": "production"}, {"environment_scope": "staging"}]},
+                        {"name": "userWithId", "scopes": [{"environment_scope": "production"}]}
+                    ]
+                }
+            }"#,
+        );
+        let result = handle_feature_flag_event(&body);
+
+        assert!(result.starts_with("🟢 <b>Alice</b> enabled feature flag <a href=\"https://gitlab.com/acme/api/-/feature_flags\">new_checkout</a>"));
+        assert!(result.contains("Environments: production, staging"));
+    }
+}
@@ -1,7 +1,6 @@
 use actix_web::web;
 use html_escape::encode_text;
 use serde::Deserialize;
-use ureq::serde_json;
 
 #[derive(Debug)]
 enum IssueAction {
@@ -1,13 +1,24 @@
+use crate::utils::branch_filter::BranchFilter;
 use actix_web::web;
+use html_escape::encode_text;
 use serde::Deserialize;
-use ureq::serde_json;
 
 #[derive(Debug, Deserialize)]
 struct JobEvent {
+    #[serde(rename = "ref")]
+    ref_field: Option<String>,
+    #[serde(default)]
+    tag: bool,
     build_id: u64,
     build_name: String,
+    build_stage: Option<String>,
     build_status: String,
     build_duration: Option<f64>,
+    #[serde(default)]
+    build_allow_failure: bool,
+    build_failure_reason: Option<String>,
+    environment: Option<Environment>,
+    runner: Option<Runner>,
     repository: Repository,
     user: User,
 }
@@ -22,9 +33,18 @@ struct Repository {
     homepage: String,
 }
 
-// TODO also implement environment name
+#[derive(Debug, Deserialize)]
+struct Environment {
+    name: String,
+    action: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Runner {
+    description: Option<String>,
+}
 
-pub fn handle_job_event(body: &web::Bytes) -> String {
+pub fn handle_job_event(body: &web::Bytes, branch_filter: Option<&BranchFilter>) -> String {
     let job_event = match serde_json::from_slice::<JobEvent>(body) {
         Ok(event) => event,
         Err(e) => {
@@ -34,27 +54,165 @@ pub fn handle_job_event(body: &web::Bytes) -> String {
         }
     };
 
+    // Only finished jobs are reported, intermediate states would just add noise
+    if matches!(
+        job_event.build_status.as_str(),
+        "created" | "pending" | "running" | "waiting_for_resource" | "preparing" | "manual"
+    ) {
+        tracing::debug!("Skipping job event with status: {}", job_event.build_status);
+        return String::new();
+    }
+
+    let ref_raw = job_event.ref_field.as_deref().unwrap_or("");
+
+    // Apply branch filter if provided, tag pipelines are not tied to a branch
+    if let Some(filter) = branch_filter {
+        if !job_event.tag && !filter.should_process(ref_raw) {
+            tracing::info!("Filtered out GitLab job event for branch: {}", ref_raw);
+            return String::new();
+        }
+    }
+
     // convert build_duration to seconds remove decimal places
     let build_duration = job_event.build_duration.unwrap_or(0.0) as u64; // Default to 0 if null
     let build_url = &format!(
         "{}/-/jobs/{}",
         job_event.repository.homepage, job_event.build_id
     );
-    let build_name = &job_event.build_name;
+    let build_name = encode_text(&job_event.build_name);
 
-    match job_event.build_status.as_str() {
+    let mut message = match job_event.build_status.as_str() {
         "success" => format!(
             "✅ CI: <a href=\"{build_url}\">{build_name}</a> succeeded after <b>{build_duration}</b> seconds"
         ),
+        "failed" if job_event.build_allow_failure => format!(
+            "⚠️ CI: <a href=\"{build_url}\">{build_name}</a> failed after <b>{build_duration}</b> seconds (allowed to fail)"
+        ),
         "failed" => format!(
             "❌ CI: <a href=\"{build_url}\">{build_name}</a> failed after <b>{build_duration}</b> seconds"
         ),
         "canceled" => {
-            let user_name = &job_event.user.name;
+            let user_name = encode_text(&job_event.user.name);
             format!(
                 "❌ CI: <a href=\"{build_url}\">{build_name}</a> was canceled by {user_name} after <b>{build_duration}</b> seconds"
             )
         },
-        _ => String::new(), // Return an empty string for unknown statuses
+        _ => return String::new(), // Return an empty string for unknown statuses
+    };
+
+    let mut context = Vec::new();
+    if let Some(stage) = job_event.build_stage.as_deref().filter(|s| !s.is_empty()) {
+        context.push(format!("Stage: {}", encode_text(stage)));
+    }
+    if !ref_raw.is_empty() {
+        let ref_kind = if job_event.tag { "Tag" } else { "Ref" };
+        context.push(format!("{ref_kind}: <code>{}</code>", encode_text(ref_raw)));
+    }
+    if !context.is_empty() {
+        message.push_str(&format!("\n{}", context.join(" · ")));
+    }
+
+    if let Some(environment) = &job_event.environment {
+        let environment_name = encode_text(&environment.name);
+        match environment.action.as_deref() {
+            Some(action) => message.push_str(&format!(
+                "\nEnvironment: {environment_name} ({})",
+                encode_text(action)
+            )),
+            None => message.push_str(&format!("\nEnvironment: {environment_name}")),
+        }
+    }
+
+    if let Some(runner) = job_event
+        .runner
+        .as_ref()
+        .and_then(|r| r.description.as_deref())
+        .filter(|d| !d.is_empty())
+    {
+        message.push_str(&format!("\nRunner: {}", encode_text(runner)));
+    }
+
+    if job_event.build_status == "failed" {
+        if let Some(reason) = job_event
+            .build_failure_reason
+            .as_deref()
+            .filter(|r| *r != "unknown_failure")
+        {
+            message.push_str(&format!(
+                "\nReason: {}",
+                encode_text(&reason.replace('_', " "))
+            ));
+        }
+        message.push_str(&format!("\n📄 <a href=\"{build_url}/raw\">Job log</a>"));
+    }
+
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload(status: &str) -> String {
+        format!(
+            r#"{{
+                "object_kind": "build",
+                "ref": "main",
+                "tag": false,
+                "build_id": 1977,
+                "build_name": "deploy",
+                "build_stage": "deploy",
+                "build_status": "{status}",
+                "build_duration": 42.7,
+                "build_allow_failure": false,
+                "build_failure_reason": "script_failure",
+                "environment": {{"name": "production", "action": "start", "deployment_tier": "production"}},
+                "runner": {{"id": 380987, "description": "shared-runners-1"}},
+                "repository": {{"homepage": "https://gitlab.com/acme/api"}},
+                "user": {{"name": "Alice"}}
+            }}"#
+        )
+    }
+
+    #[test]
+    fn test_failed_job_details() {
+        let result = handle_job_event(&web::Bytes::from(payload("failed")), None);
+
+        assert!(result.starts_with("❌ CI: <a href=\"https://gitlab.com/acme/api/-/jobs/1977\">deploy</a> failed after <b>42</b> seconds"));
+        assert!(result.contains("Stage: deploy · Ref: <code>main</code>"));
+        assert!(result.contains("Environment: production (start)"));
+        assert!(result.contains("Runner: shared-runners-1"));
+        assert!(result.contains("Reason: script failure"));
+        assert!(
+            result.contains("<a href=\"https://gitlab.com/acme/api/-/jobs/1977/raw\">Job log</a>")
+        );
+    }
+
+    #[test]
+    fn test_allowed_failure() {
+        let body = payload("failed").replace(
+            "\"build_allow_failure\": false",
+            "\"build_allow_failure\": true",
+        );
+        let result = handle_job_event(&web::Bytes::from(body), None);
+
+        assert!(result.starts_with("⚠️ CI:"));
+        assert!(result.contains("(allowed to fail)"));
+    }
+
+    #[test]
+    fn test_successful_job_has_no_failure_details() {
+        let result = handle_job_event(&web::Bytes::from(payload("success")), None);
+
+        assert!(result.starts_with("✅ CI:"));
+        assert!(!result.contains("Reason:"));
+        assert!(!result.contains("Job log"));
+    }
+
+    #[test]
+    fn test_intermediate_states_are_skipped() {
+        for status in ["created", "pending", "running"] {
+            assert!(handle_job_event(&web::Bytes::from(payload(status)), None).is_empty());
+        }
     }
 }
@@ -0,0 +1,126 @@
+use crate::utils::mentions::extract_at_mentions;
+use actix_web::web;
+use serde_json::Value;
+
+fn username(value: &Value) -> Option<String> {
+    value["username"].as_str().map(str::to_lowercase)
+}
+
+fn usernames(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|users| users.iter().filter_map(username).collect())
+        .unwrap_or_default()
+}
+
+fn text_mentions(value: &Value) -> Vec<String> {
+    value.as_str().map(extract_at_mentions).unwrap_or_default()
+}
+
+/// Users added to a `changes` entry such as `assignees` or `reviewers`.
+fn added_users(change: &Value) -> Vec<String> {
+    let previous = usernames(&change["previous"]);
+    usernames(&change["current"])
+        .into_iter()
+        .filter(|user| !previous.contains(user))
+        .collect()
+}
+
+/// Collects the GitLab usernames an event is relevant to: assignees, reviewers and
+/// `@mentions` in descriptions, notes and commit messages. The user who triggered the
+/// event is never included.
+pub fn extract_mentions(event: &str, body: &web::Bytes) -> Vec<String> {
+    let payload: Value = match serde_json::from_slice(body) {
+        Ok(payload) => payload,
+        Err(_) => return Vec::new(),
+    };
+    let attributes = &payload["object_attributes"];
+    let action = attributes["action"].as_str().unwrap_or("");
+
+    let mentioned: Vec<String> = match (event, action) {
+        ("Push Hook", _) => payload["commits"]
+            .as_array()
+            .map(|commits| {
+                commits
+                    .iter()
+                    .flat_map(|commit| text_mentions(&commit["message"]))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        ("Merge Request Hook", "open") => {
+            let mut mentioned = usernames(&payload["reviewers"]);
+            mentioned.extend(usernames(&payload["assignees"]));
+            mentioned.extend(text_mentions(&attributes["description"]));
+            mentioned
+        }
+        ("Merge Request Hook", "update") => {
+            let changes = &payload["changes"];
+            let mut mentioned = added_users(&changes["reviewers"]);
+            mentioned.extend(added_users(&changes["assignees"]));
+            mentioned
+        }
+        ("Issue Hook", "open") => {
+            let mut mentioned = usernames(&payload["assignees"]);
+            mentioned.extend(text_mentions(&attributes["description"]));
+            mentioned
+        }
+        ("Issue Hook", "update") => added_users(&payload["changes"]["assignees"]),
+        ("Note Hook", _) => text_mentions(&attributes["note"]),
+        _ => Vec::new(),
+    };
+
+    let sender = username(&payload["user"])
+        .or_else(|| payload["user_username"].as_str().map(str::to_lowercase));
+    let mut mentions: Vec<String> = Vec::new();
+    for user in mentioned {
+        if Some(&user) != sender.as_ref() && !mentions.contains(&user) {
+            mentions.push(user);
+        }
+    }
+
+    mentions
+}
+
+/// The subset of [`extract_mentions`] that warrants a direct message to linked users.
+/// Mentions in commit messages only go to the group chat.
+pub fn extract_personal_mentions(event: &str, body: &web::Bytes) -> Vec<String> {
+    match event {
+        "Push Hook" => Vec::new(),
+        _ => extract_mentions(event, body),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_request_reviewer_added() {
+        let body = web::Bytes::from(
+            r#"{
+                "user": {"name": "Alice", "username": "alice"},
+                "object_attributes": {"action": "update"},
+                "changes": {
+                    "reviewers": {
+                        "previous": [{"username": "carol"}],
+                        "current": [{"username": "carol"}, {"username": "Bob"}]
+                    }
+                }
+            }"#,
+        );
+
+        assert_eq!(extract_mentions("Merge Request Hook", &body), vec!["bob"]);
+    }
+
+    #[test]
+    fn test_note_mentions() {
+        let body = web::Bytes::from(
+            r#"{
+                "user": {"name": "Alice", "username": "alice"},
+                "object_attributes": {"note": "@bob @alice please check"}
+            }"#,
+        );
+
+        assert_eq!(extract_mentions("Note Hook", &body), vec!["bob"]);
+    }
+}
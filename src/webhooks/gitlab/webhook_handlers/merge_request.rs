@@ -2,12 +2,13 @@ use crate::utils::branch_filter::BranchFilter;
 use actix_web::web;
 use html_escape::encode_text;
 use serde::Deserialize;
-use ureq::serde_json;
 
 #[derive(Debug, Deserialize)]
 struct MergeRequestEvent {
     user: User,
     object_attributes: MergeRequestDetails,
+    #[serde(default)]
+    changes: Changes,
 }
 
 #[derive(Debug, Deserialize)]
@@ -17,6 +18,9 @@ struct MergeRequestDetails {
     source_branch: String,
     target_branch: String,
     action: Option<String>,
+    merge_status: Option<String>,
+    detailed_merge_status: Option<String>,
+    oldrev: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -24,7 +28,107 @@ struct User {
     name: String,
 }
 
-// TODO also implement environment name
+#[derive(Debug, Default, Deserialize)]
+struct Changes {
+    draft: Option<Change<bool>>,
+    labels: Option<Change<Vec<Label>>>,
+    assignees: Option<Change<Vec<User>>>,
+    reviewers: Option<Change<Vec<User>>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Change<T> {
+    previous: Option<T>,
+    current: Option<T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Label {
+    title: String,
+}
+
+/// Returns the entries of `current` missing from `previous` and vice versa.
+fn diff_names<'a>(previous: &'a [String], current: &'a [String]) -> (Vec<&'a str>, Vec<&'a str>) {
+    let added = current
+        .iter()
+        .filter(|name| !previous.contains(name))
+        .map(String::as_str)
+        .collect();
+    let removed = previous
+        .iter()
+        .filter(|name| !current.contains(name))
+        .map(String::as_str)
+        .collect();
+    (added, removed)
+}
+
+fn describe_change(change: &Change<Vec<String>>, subject: &str) -> Vec<String> {
+    let previous = change.previous.as_deref().unwrap_or_default();
+    let current = change.current.as_deref().unwrap_or_default();
+    let (added, removed) = diff_names(previous, current);
+
+    let mut lines = Vec::new();
+    if !added.is_empty() {
+        lines.push(format!(
+            "➕ {subject} added: {}",
+            encode_text(&added.join(", "))
+        ));
+    }
+    if !removed.is_empty() {
+        lines.push(format!(
+            "➖ {subject} removed: {}",
+            encode_text(&removed.join(", "))
+        ));
+    }
+    lines
+}
+
+fn names_change<T>(change: &Change<Vec<T>>, name: impl Fn(&T) -> String) -> Change<Vec<String>> {
+    Change {
+        previous: change
+            .previous
+            .as_ref()
+            .map(|items| items.iter().map(&name).collect()),
+        current: change
+            .current
+            .as_ref()
+            .map(|items| items.iter().map(&name).collect()),
+    }
+}
+
+/// Lines describing what changed in an `update` event, taken from the `changes` object.
+fn change_details(changes: &Changes, has_new_commits: bool) -> Vec<String> {
+    let mut details = Vec::new();
+
+    if has_new_commits {
+        details.push("🔀 New commits pushed".to_string());
+    }
+    if let Some(labels) = &changes.labels {
+        details.extend(describe_change(
+            &names_change(labels, |label| label.title.clone()),
+            "Labels",
+        ));
+    }
+    if let Some(assignees) = &changes.assignees {
+        details.extend(describe_change(
+            &names_change(assignees, |user| user.name.clone()),
+            "Assignees",
+        ));
+    }
+    if let Some(reviewers) = &changes.reviewers {
+        details.extend(describe_change(
+            &names_change(reviewers, |user| user.name.clone()),
+            "Reviewers",
+        ));
+    }
+
+    details
+}
+
+fn has_conflicts(details: &MergeRequestDetails) -> bool {
+    details.detailed_merge_status.as_deref() == Some("conflict")
+        || details.merge_status.as_deref() == Some("cannot_be_merged")
+}
 
 pub fn handle_merge_request_event(
     body: &web::Bytes,
@@ -61,37 +165,173 @@ pub fn handle_merge_request_event(
         None => "none",
     };
 
-    if action == "open" {
-        format!(
+    let mut message = match action {
+        "open" => format!(
             "<b>{sender}</b> opened a new merge request <a href=\"{url}\">{title}</a> \
              from <code>{source_branch}</code> to <code>{target_branch}</code>\n",
-        )
-    } else if action == "update" {
-        format!(
-            "<b>{sender}</b> updated merge request <a href=\"{url}\">{title}</a> \
-             from <code>{source_branch}</code> to <code>{target_branch}</code>\n",
-        )
-    } else if action == "merge" {
-        format!(
+        ),
+        "update" => match merge_request_event
+            .changes
+            .draft
+            .as_ref()
+            .and_then(|draft| draft.current)
+        {
+            Some(true) => format!(
+                "📝 <b>{sender}</b> marked merge request <a href=\"{url}\">{title}</a> as draft\n",
+            ),
+            Some(false) => format!(
+                "👀 <b>{sender}</b> marked merge request <a href=\"{url}\">{title}</a> as ready for review\n",
+            ),
+            None => format!(
+                "<b>{sender}</b> updated merge request <a href=\"{url}\">{title}</a> \
+                 from <code>{source_branch}</code> to <code>{target_branch}</code>\n",
+            ),
+        },
+        "merge" => format!(
             "<b>{sender}</b> merged merge request <a href=\"{url}\">{title}</a> \
              from <code>{source_branch}</code> to <code>{target_branch}</code>\n",
-        )
-    } else if action == "close" {
-        format!(
+        ),
+        "close" => format!(
             "<b>{sender}</b> closed merge request <a href=\"{url}\">{title}</a> \
              from <code>{source_branch}</code> to <code>{target_branch}</code>\n",
-        )
-    } else if action == "reopen" {
-        format!(
+        ),
+        "reopen" => format!(
             "<b>{sender}</b> reopened merge request <a href=\"{url}\">{title}</a> \
              from <code>{source_branch}</code> to <code>{target_branch}</code>\n",
-        )
-    } else if action == "none" {
-        format!(
+        ),
+        // "approved" is sent once all required approvals are in, "approval" for each one before that
+        "approved" => format!(
+            "✅ <b>{sender}</b> approved merge request <a href=\"{url}\">{title}</a>, it is now fully approved\n",
+        ),
+        "approval" => format!(
+            "👍 <b>{sender}</b> approved merge request <a href=\"{url}\">{title}</a>\n",
+        ),
+        "unapproved" | "unapproval" => format!(
+            "↩️ <b>{sender}</b> revoked their approval of merge request <a href=\"{url}\">{title}</a>\n",
+        ),
+        "none" => format!(
             "<b>{sender}</b> opened a new merge request <a href=\"{url}\">{title}</a> \
              from <code>{source_branch}</code> to <code>{target_branch}</code> (Merge Request without any action)\n",
-        )
-    } else {
-        String::from("Unknown merge request action")
+        ),
+        _ => return String::from("Unknown merge request action"),
+    };
+
+    if action == "update" {
+        let details = change_details(
+            &merge_request_event.changes,
+            merge_request_details.oldrev.is_some(),
+        );
+        for line in details {
+            message.push_str(&line);
+            message.push('\n');
+        }
+    }
+
+    if matches!(action, "open" | "update" | "reopen") && has_conflicts(merge_request_details) {
+        message.push_str(&format!(
+            "⚠️ Merge conflicts with <code>{target_branch}</code> need to be resolved\n"
+        ));
+    }
+
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload(action: &str, extra_attributes: &str, changes: &str) -> web::Bytes {
+        web::Bytes::from(format!(
+            r#"{{
+                "object_kind": "merge_request",
+                "user": {{"name": "Alice"}},
+                "object_attributes": {{
+                    "title": "Add caching",
+                    "url": "https://gitlab.com/acme/api/-/merge_requests/7",
+                    "source_branch": "feature/cache",
+                    "target_branch": "main",
+                    "action": "{action}"{extra_attributes}
+                }},
+                "changes": {changes}
+            }}"#
+        ))
+    }
+
+    #[test]
+    fn test_approval_actions() {
+        let approved = handle_merge_request_event(&payload("approved", "", "{}"), None);
+        assert!(approved.starts_with("✅ <b>Alice</b> approved merge request"));
+        assert!(approved.contains("fully approved"));
+
+        let approval = handle_merge_request_event(&payload("approval", "", "{}"), None);
+        assert!(approval.starts_with("👍 <b>Alice</b> approved merge request"));
+
+        let unapproved = handle_merge_request_event(&payload("unapproved", "", "{}"), None);
+        assert!(unapproved.contains("revoked their approval"));
+    }
+
+    #[test]
+    fn test_draft_transitions() {
+        let ready = handle_merge_request_event(
+            &payload(
+                "update",
+                "",
+                r#"{"draft": {"previous": true, "current": false}}"#,
+            ),
+            None,
+        );
+        assert!(ready.contains("as ready for review"));
+
+        let draft = handle_merge_request_event(
+            &payload(
+                "update",
+                "",
+                r#"{"draft": {"previous": false, "current": true}}"#,
+            ),
+            None,
+        );
+        assert!(draft.contains("as draft"));
+    }
+
+    #[test]
+    fn test_label_reviewer_and_assignee_changes() {
+        let result = handle_merge_request_event(
+            &payload(
+                "update",
+                r#", "oldrev": "abc123""#,
+                r#"{
+                    "labels": {
+                        "previous": [{"title": "backend"}, {"title": "needs-review"}],
+                        "current": [{"title": "backend"}, {"title": "ready"}]
+                    },
+                    "assignees": {"previous": [], "current": [{"name": "Bob"}]},
+                    "reviewers": {"previous": [{"name": "Carol"}], "current": [{"name": "Dave"}]}
+                }"#,
+            ),
+            None,
+        );
+
+        assert!(result.contains("🔀 New commits pushed"));
+        assert!(result.contains("➕ Labels added: ready"));
+        assert!(result.contains("➖ Labels removed: needs-review"));
+        assert!(!result.contains("backend"));
+        assert!(result.contains("➕ Assignees added: Bob"));
+        assert!(result.contains("➕ Reviewers added: Dave"));
+        assert!(result.contains("➖ Reviewers removed: Carol"));
+    }
+
+    #[test]
+    fn test_merge_conflicts() {
+        let result = handle_merge_request_event(
+            &payload("update", r#", "detailed_merge_status": "conflict""#, "{}"),
+            None,
+        );
+        assert!(result.contains("⚠️ Merge conflicts with <code>main</code>"));
+
+        let mergeable = handle_merge_request_event(
+            &payload("update", r#", "detailed_merge_status": "mergeable""#, "{}"),
+            None,
+        );
+        assert!(!mergeable.contains("Merge conflicts"));
     }
 }
@@ -1,11 +1,17 @@
+pub mod access_token;
 pub mod build;
+pub mod buttons;
+pub mod emoji;
+pub mod feature_flag;
 pub mod issue;
 pub mod job;
+pub mod mentions;
 pub mod merge_request;
 pub mod new_branch_push;
 pub mod note;
 pub mod pipeline;
 pub mod push;
+pub mod release;
 pub mod tag_push;
 #[cfg(test)]
 mod test_branch_filtering;
@@ -2,7 +2,6 @@ use crate::utils::branch_filter::BranchFilter;
 use actix_web::web;
 use html_escape::encode_text;
 use serde::Deserialize;
-use ureq::serde_json;
 
 #[derive(Debug, Deserialize)]
 struct PushEvent {
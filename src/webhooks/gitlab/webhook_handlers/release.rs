@@ -0,0 +1,154 @@
+use actix_web::web;
+use html_escape::encode_text;
+use serde::Deserialize;
+
+const DESCRIPTION_EXCERPT_LENGTH: usize = 200;
+
+#[derive(Debug, Deserialize)]
+struct ReleaseEvent {
+    action: String,
+    name: Option<String>,
+    tag: String,
+    description: Option<String>,
+    url: String,
+    project: Project,
+    assets: Option<Assets>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Project {
+    name: String,
+    web_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Assets {
+    #[serde(default)]
+    links: Vec<AssetLink>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AssetLink {
+    name: String,
+    url: String,
+}
+
+fn description_excerpt(description: &str, url: &str) -> String {
+    let description = description.trim();
+    if description.chars().count() <= DESCRIPTION_EXCERPT_LENGTH {
+        return encode_text(description).into_owned();
+    }
+
+    let truncated: String = description
+        .chars()
+        .take(DESCRIPTION_EXCERPT_LENGTH)
+        .collect();
+    format!("{}<a href=\"{url}\">...</a>", encode_text(&truncated))
+}
+
+pub fn handle_release_event(body: &web::Bytes) -> String {
+    let release_event: ReleaseEvent = match serde_json::from_slice(body) {
+        Ok(e) => e,
+        Err(e) => {
+            tracing::error!("Failed to parse GitLab release event: {}", e);
+            return String::new();
+        }
+    };
+
+    let action = match release_event.action.as_str() {
+        "create" => "published",
+        "update" => "updated",
+        "delete" => "deleted",
+        _ => return String::new(),
+    };
+
+    let url = &release_event.url;
+    let tag = encode_text(&release_event.tag);
+    let name = release_event
+        .name
+        .as_deref()
+        .filter(|name| !name.is_empty())
+        .map(encode_text)
+        .unwrap_or_else(|| tag.clone());
+    let project_url = &release_event.project.web_url;
+    let project_name = encode_text(&release_event.project.name);
+
+    let mut message = format!(
+        "🚀 Release <a href=\"{url}\">{name}</a> ({tag}) {action} in <a href=\"{project_url}\">{project_name}</a>\n"
+    );
+
+    if action == "deleted" {
+        return message;
+    }
+
+    if let Some(description) = release_event
+        .description
+        .as_deref()
+        .filter(|d| !d.trim().is_empty())
+    {
+        message.push_str(&description_excerpt(description, url));
+        message.push('\n');
+    }
+
+    let links = release_event
+        .assets
+        .as_ref()
+        .map(|assets| assets.links.as_slice())
+        .unwrap_or_default();
+    if !links.is_empty() {
+        message.push_str("\n<b>Assets:</b>\n");
+        for link in links {
+            message.push_str(&format!(
+                "• <a href=\"{}\">{}</a>\n",
+                link.url,
+                encode_text(&link.name)
+            ));
+        }
+    }
+
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RELEASE_PAYLOAD: &str = r#"{
+        "object_kind": "release",
+        "action": "create",
+        "name": "Version 1.4",
+        "tag": "v1.4.0",
+        "description": "Adds <b>dark mode</b>",
+        "url": "https://gitlab.com/acme/api/-/releases/v1.4.0",
+        "project": {"name": "api", "web_url": "https://gitlab.com/acme/api"},
+        "assets": {
+            "count": 3,
+            "links": [{"id": 1, "name": "linux-amd64", "url": "https://gitlab.com/acme/api/-/releases/v1.4.0/downloads/linux-amd64"}],
+            "sources": [{"format": "zip", "url": "https://gitlab.com/acme/api/-/archive/v1.4.0/api-v1.4.0.zip"}]
+        }
+    }"#;
+
+    #[test]
+    fn test_release_created() {
+        let body = web::Bytes::from(RELEASE_PAYLOAD);
+        let result = handle_release_event(&body);
+
+        assert!(result.starts_with("🚀 Release <a href=\"https://gitlab.com/acme/api/-/releases/v1.4.0\">Version 1.4</a> (v1.4.0) published"));
+        assert!(result.contains("Adds &lt;b&gt;dark mode&lt;/b&gt;"));
+        assert!(result.contains("<b>Assets:</b>\n• <a href=\"https://gitlab.com/acme/api/-/releases/v1.4.0/downloads/linux-amd64\">linux-amd64</a>"));
+    }
+
+    #[test]
+    fn test_long_description_is_truncated() {
+        let long_description = "a".repeat(300);
+        let body =
+            web::Bytes::from(RELEASE_PAYLOAD.replace("Adds <b>dark mode</b>", &long_description));
+        let result = handle_release_event(&body);
+
+        assert!(result.contains(&format!(
+            "{}<a href=\"https://gitlab.com/acme/api/-/releases/v1.4.0\">...</a>",
+            "a".repeat(DESCRIPTION_EXCERPT_LENGTH)
+        )));
+        assert!(!result.contains(&"a".repeat(DESCRIPTION_EXCERPT_LENGTH + 1)));
+    }
+}
@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
     use crate::utils::branch_filter::BranchFilter;
+    use crate::webhooks::gitlab::webhook_handlers::job::handle_job_event;
     use crate::webhooks::gitlab::webhook_handlers::merge_request::handle_merge_request_event;
     use crate::webhooks::gitlab::webhook_handlers::push::handle_push_event;
     use actix_web::web;
@@ -298,4 +299,37 @@ mod tests {
         let feature_result = handle_push_event(&feature_bytes, None);
         assert!(!feature_result.is_empty());
     }
+
+    #[test]
+    fn test_gitlab_job_event_branch_filtering() {
+        let filter = BranchFilter::new(Some("main"), None).unwrap();
+
+        let job_payload = |git_ref: &str, tag: bool| {
+            format!(
+                r#"{{
+                    "ref": "{git_ref}",
+                    "tag": {tag},
+                    "build_id": 1,
+                    "build_name": "test",
+                    "build_status": "failed",
+                    "build_duration": 10.0,
+                    "repository": {{"homepage": "https://gitlab.com/test/project"}},
+                    "user": {{"name": "testuser"}}
+                }}"#
+            )
+        };
+
+        let main_bytes = web::Bytes::from(job_payload("main", false));
+        let feature_bytes = web::Bytes::from(job_payload("feature/test", false));
+        let tag_bytes = web::Bytes::from(job_payload("v1.0.0", true));
+
+        // Should process jobs on main
+        assert!(!handle_job_event(&main_bytes, Some(&filter)).is_empty());
+
+        // Should filter out jobs on feature branches
+        assert!(handle_job_event(&feature_bytes, Some(&filter)).is_empty());
+
+        // Tag jobs are not subject to branch filtering
+        assert!(!handle_job_event(&tag_bytes, Some(&filter)).is_empty());
+    }
 }
@@ -1,4 +1,5 @@
 pub mod beep;
 pub mod common;
+pub mod dockerhub;
 pub mod github;
 pub mod gitlab;